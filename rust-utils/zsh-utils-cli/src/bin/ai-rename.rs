@@ -0,0 +1,195 @@
+//! Proposes descriptive, consistent file names for a messy directory
+//! (think ~/Downloads) by showing the LLM whatever evidence each file
+//! carries: text heads, PDF titles, image EXIF dates.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+
+use zsh_utils::llm::{ChatMessage, LLMClient};
+use zsh_utils::rename::{RenameItem, RenamePlan};
+use zsh_utils::{glyphs, logger};
+
+#[derive(Parser)]
+#[command(name = "ai-rename", about = "LLM-assisted batch rename for a directory")]
+struct Args {
+    /// Directory whose files should be renamed
+    dir: PathBuf,
+
+    /// Apply without the interactive confirmation
+    #[arg(short = 'y', long)]
+    yes: bool,
+
+    /// Maximum number of files to consider
+    #[arg(long, default_value_t = 50)]
+    limit: usize,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+const SYSTEM_PROMPT: &str = "You rename files. For each file you get its current name \
+and some extracted evidence. Reply with one line per file in the exact format \
+`old name -> new name`. Keep the original extension, use lowercase-with-dashes, \
+include dates as YYYY-MM-DD when known, and keep the set of names consistent. \
+Output nothing else.";
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+
+    let files = collect_files(&args.dir, args.limit)?;
+    if files.is_empty() {
+        logger::info("nothing to rename");
+        return Ok(());
+    }
+
+    logger::step(format!("inspecting {} files", files.len()));
+    let mut prompt = String::new();
+    for file in &files {
+        let name = file.file_name().unwrap_or_default().to_string_lossy();
+        prompt.push_str(&format!("file: {name}\n"));
+        for line in gather_evidence(file) {
+            prompt.push_str(&format!("  {line}\n"));
+        }
+    }
+
+    let client = LLMClient::from_config()?;
+    logger::step(format!("asking {} for names", client.model()));
+    let reply = client.complete(&[
+        ChatMessage::system(SYSTEM_PROMPT),
+        ChatMessage::user(prompt),
+    ])?;
+
+    let plan = parse_plan(&files, &reply)?;
+    if plan.is_empty() {
+        logger::info("model proposed no changes");
+        return Ok(());
+    }
+
+    print_preview(&plan);
+    if !args.yes && !confirm()? {
+        logger::info("aborted");
+        return Ok(());
+    }
+    let moved = plan.apply()?;
+    logger::success(format!("renamed {moved} files"));
+    Ok(())
+}
+
+fn collect_files(dir: &Path, limit: usize) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("reading {}", dir.display()))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            p.file_name()
+                .map(|n| !n.to_string_lossy().starts_with('.'))
+                .unwrap_or(false)
+        })
+        .collect();
+    files.sort();
+    files.truncate(limit);
+    Ok(files)
+}
+
+/// Pulls whatever cheap evidence is available for one file. External
+/// helpers (pdfinfo, exiftool) are best-effort: missing tools just mean
+/// less context for the model.
+fn gather_evidence(path: &Path) -> Vec<String> {
+    let mut evidence = Vec::new();
+    if let Ok(meta) = path.metadata() {
+        evidence.push(format!("size: {} bytes", meta.len()));
+    }
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "pdf" => {
+            if let Some(title) = tool_line("pdfinfo", path, "Title:") {
+                evidence.push(format!("pdf title: {title}"));
+            }
+        }
+        "jpg" | "jpeg" | "png" | "heic" => {
+            if let Some(date) = tool_line("exiftool", path, "Date/Time Original") {
+                evidence.push(format!("exif date: {date}"));
+            }
+        }
+        _ => {
+            if let Some(head) = text_head(path) {
+                evidence.push(format!("head: {head}"));
+            }
+        }
+    }
+    evidence
+}
+
+fn tool_line(tool: &str, path: &Path, prefix: &str) -> Option<String> {
+    let output = Command::new(tool).arg(path).output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .lines()
+        .find(|l| l.starts_with(prefix))
+        .and_then(|l| l.split_once(':'))
+        .map(|(_, v)| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn text_head(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    let head = std::str::from_utf8(bytes.get(..512.min(bytes.len()))?).ok()?;
+    let head: String = head.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!head.is_empty()).then(|| head.chars().take(200).collect())
+}
+
+fn parse_plan(files: &[PathBuf], reply: &str) -> Result<RenamePlan> {
+    let mut items = Vec::new();
+    for line in reply.lines() {
+        let Some((old, new)) = line.split_once("->") else {
+            continue;
+        };
+        let (old, new) = (old.trim(), new.trim());
+        let Some(from) = files
+            .iter()
+            .find(|f| f.file_name().unwrap_or_default().to_string_lossy() == old)
+        else {
+            continue;
+        };
+        if old != new {
+            items.push(RenameItem { from: from.clone(), to: new.to_string() });
+        }
+    }
+    RenamePlan::new(items)
+}
+
+fn print_preview(plan: &RenamePlan) {
+    let arrow = glyphs::pick("→", "->");
+    let width = plan
+        .items()
+        .iter()
+        .map(|i| i.from.file_name().unwrap_or_default().to_string_lossy().len())
+        .max()
+        .unwrap_or(0);
+    for item in plan.items() {
+        let old = item.from.file_name().unwrap_or_default().to_string_lossy();
+        println!("  {old:width$} {arrow} {}", item.to);
+    }
+}
+
+fn confirm() -> Result<bool> {
+    print!("apply {}? [y/N] ", glyphs::pick("✏️", "renames"));
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
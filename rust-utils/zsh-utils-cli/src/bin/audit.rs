@@ -0,0 +1,73 @@
+//! Views the opt-in command audit log (see `zsh_utils::audit`).
+
+use anyhow::Result;
+use clap::Parser;
+
+use zsh_utils::{audit, display, glyphs, logger};
+
+#[derive(Parser)]
+#[command(
+    name = "audit",
+    about = "Show commands the toolbox ran on your behalf",
+    after_help = zsh_utils::examples::after_help("audit")
+)]
+struct Args {
+    /// Only the most recent N entries
+    #[arg(short = 'n', long, value_name = "N")]
+    tail: Option<usize>,
+
+    /// Truncate the log
+    #[arg(long, conflicts_with = "tail")]
+    clear: bool,
+
+    /// Stable tab-separated records on stdout for scripting
+    #[arg(long, global = true)]
+    porcelain: bool,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+    display::init_porcelain(args.porcelain);
+    if args.clear {
+        audit::clear()?;
+        logger::success("audit log cleared");
+        return Ok(());
+    }
+    let entries = audit::read()?;
+    if entries.is_empty() {
+        logger::info("audit log is empty");
+        return Ok(());
+    }
+    let skip = args
+        .tail
+        .map(|n| entries.len().saturating_sub(n))
+        .unwrap_or(0);
+    for entry in &entries[skip..] {
+        if display::is_porcelain() {
+            let code = entry.exit_code.map(|c| c.to_string()).unwrap_or_default();
+            display::porcelain(&[
+                "run",
+                &entry.timestamp,
+                &code,
+                &entry.cwd,
+                &entry.command,
+            ]);
+            continue;
+        }
+        let code = match entry.exit_code {
+            Some(code) => format!("{code:>3}"),
+            None => "  ?".to_string(),
+        };
+        println!("{}  [{code}]  {}  $ {}", entry.timestamp, entry.cwd, entry.command);
+    }
+    Ok(())
+}
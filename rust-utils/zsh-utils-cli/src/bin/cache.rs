@@ -0,0 +1,81 @@
+//! Inspects the content-addressed LLM completion cache (see
+//! `zsh_utils::cache`).
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+use zsh_utils::cache::LlmCache;
+use zsh_utils::{display, glyphs, logger};
+
+#[derive(Parser)]
+#[command(
+    name = "cache",
+    about = "Manage the cached LLM completions",
+    after_help = zsh_utils::examples::after_help("cache")
+)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Stable tab-separated records on stdout for scripting
+    #[arg(long, global = true)]
+    porcelain: bool,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Entry count and total size (the default)
+    Stats,
+    /// Remove every cached completion
+    Clear,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+    display::init_porcelain(args.porcelain);
+    let cache = LlmCache::open();
+    match args.command.unwrap_or(Command::Stats) {
+        Command::Stats => {
+            let stats = cache.stats()?;
+            if display::is_porcelain() {
+                display::porcelain(&[
+                    "stats",
+                    &stats.entries.to_string(),
+                    &stats.bytes.to_string(),
+                ]);
+                return Ok(());
+            }
+            println!(
+                "{} cached completions, {}",
+                stats.entries,
+                human_bytes(stats.bytes)
+            );
+        }
+        Command::Clear => {
+            let removed = cache.clear()?;
+            if display::is_porcelain() {
+                display::porcelain(&["cleared", &removed.to_string()]);
+            }
+            logger::success(format!("removed {removed} cached completions"));
+        }
+    }
+    Ok(())
+}
+
+fn human_bytes(bytes: u64) -> String {
+    match bytes {
+        0..=999 => format!("{bytes} B"),
+        1_000..=999_999 => format!("{:.1} KB", bytes as f64 / 1_000.0),
+        1_000_000..=999_999_999 => format!("{:.1} MB", bytes as f64 / 1_000_000.0),
+        _ => format!("{:.1} GB", bytes as f64 / 1_000_000_000.0),
+    }
+}
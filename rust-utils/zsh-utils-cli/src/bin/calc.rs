@@ -0,0 +1,323 @@
+//! Calculator with bit operations, base conversion, and unit
+//! conversions — `calc "3 GiB in MB"`, `calc "0xff << 2 in bin"`, or
+//! just `calc` for a REPL.
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use serde::Deserialize;
+
+use zsh_utils::{glyphs, logger};
+
+#[derive(Parser)]
+#[command(name = "calc", about = "Calculator with units, bases, and bit ops")]
+struct Args {
+    /// Expression; omit for an interactive REPL
+    expression: Vec<String>,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+
+    if !args.expression.is_empty() {
+        let expr = args.expression.join(" ");
+        println!("{}", evaluate(&expr)?);
+        return Ok(());
+    }
+
+    // REPL: one expression per line, empty line or Ctrl+D quits.
+    loop {
+        print!("calc> ");
+        std::io::stdout().flush()?;
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line)? == 0 || line.trim().is_empty() {
+            return Ok(());
+        }
+        match evaluate(line.trim()) {
+            Ok(result) => println!("{result}"),
+            Err(err) => logger::error(format!("{err:#}")),
+        }
+    }
+}
+
+/// Entry point: handles `<expr>`, `<expr> in <base>`, and
+/// `<value> <unit> in <unit>`.
+fn evaluate(input: &str) -> Result<String> {
+    if let Some((left, target)) = input.rsplit_once(" in ") {
+        let target = target.trim();
+        // Output base conversion?
+        if matches!(target, "hex" | "bin" | "oct" | "dec") {
+            let value = eval_expr(left)?;
+            let int = value as i64;
+            return Ok(match target {
+                "hex" => format!("0x{int:x}"),
+                "bin" => format!("0b{int:b}"),
+                "oct" => format!("0o{int:o}"),
+                _ => format!("{int}"),
+            });
+        }
+        // Unit conversion: the left side ends with a unit.
+        if let Some((amount_expr, unit)) = left.trim().rsplit_once(' ') {
+            if let Some(result) = convert(eval_expr(amount_expr)?, unit, target)? {
+                return Ok(result);
+            }
+        }
+        bail!("unknown conversion target {target:?}");
+    }
+    let value = eval_expr(input)?;
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        Ok(format!("{}", value as i64))
+    } else {
+        Ok(format!("{value}"))
+    }
+}
+
+// --- units ---------------------------------------------------------------
+
+/// Factor to the canonical unit of each family (bytes, seconds).
+fn unit_factor(unit: &str) -> Option<(f64, &'static str)> {
+    let (factor, family): (f64, &str) = match unit.to_lowercase().as_str() {
+        "b" => (1.0, "bytes"),
+        "kb" => (1e3, "bytes"),
+        "mb" => (1e6, "bytes"),
+        "gb" => (1e9, "bytes"),
+        "tb" => (1e12, "bytes"),
+        "kib" => (1024.0, "bytes"),
+        "mib" => (1024.0 * 1024.0, "bytes"),
+        "gib" => (1024.0_f64.powi(3), "bytes"),
+        "tib" => (1024.0_f64.powi(4), "bytes"),
+        "ms" => (1e-3, "seconds"),
+        "s" | "sec" => (1.0, "seconds"),
+        "min" => (60.0, "seconds"),
+        "h" | "hr" => (3600.0, "seconds"),
+        "d" | "day" | "days" => (86400.0, "seconds"),
+        "w" | "week" | "weeks" => (7.0 * 86400.0, "seconds"),
+        _ => return None,
+    };
+    Some((factor, family))
+}
+
+fn convert(amount: f64, from: &str, to: &str) -> Result<Option<String>> {
+    if let (Some((ff, fam_from)), Some((tf, fam_to))) = (unit_factor(from), unit_factor(to)) {
+        if fam_from != fam_to {
+            bail!("cannot convert {fam_from} to {fam_to}");
+        }
+        let result = amount * ff / tf;
+        return Ok(Some(format!("{} {to}", trim_float(result))));
+    }
+    // Currency codes are three letters; rates come from the cache.
+    if from.len() == 3 && to.len() == 3 {
+        let rates = load_rates()?;
+        let (from, to) = (from.to_uppercase(), to.to_uppercase());
+        let from_rate = *rates.get(&from).with_context(|| format!("no rate for {from}"))?;
+        let to_rate = *rates.get(&to).with_context(|| format!("no rate for {to}"))?;
+        let result = amount / from_rate * to_rate;
+        return Ok(Some(format!("{:.2} {to}", result)));
+    }
+    Ok(None)
+}
+
+fn trim_float(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{value:.4}").trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+#[derive(Deserialize)]
+struct RatesFile {
+    rates: HashMap<String, f64>,
+}
+
+/// USD-based rates, cached for a day under the cache dir. Offline use
+/// keeps working from a stale cache with a warning.
+fn load_rates() -> Result<HashMap<String, f64>> {
+    let cache = dirs::cache_dir()
+        .unwrap_or_default()
+        .join("zsh-utils")
+        .join("rates.json");
+    let fresh = cache
+        .metadata()
+        .and_then(|m| m.modified())
+        .map(|m| m.elapsed().unwrap_or_default().as_secs() < 86400)
+        .unwrap_or(false);
+    if !fresh {
+        match fetch_rates() {
+            Ok(body) => {
+                if let Some(dir) = cache.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                std::fs::write(&cache, &body)?;
+            }
+            Err(err) if cache.exists() => {
+                logger::warn(format!("using stale rates: {err:#}"));
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    let raw = std::fs::read_to_string(&cache)?;
+    let parsed: RatesFile = serde_json::from_str(&raw).context("parsing cached rates")?;
+    Ok(parsed.rates)
+}
+
+fn fetch_rates() -> Result<String> {
+    reqwest::blocking::get("https://open.er-api.com/v6/latest/USD")
+        .context("fetching exchange rates")?
+        .error_for_status()?
+        .text()
+        .context("reading exchange rates")
+}
+
+// --- expression parser ---------------------------------------------------
+
+struct Parser2<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    Number(f64),
+    Op(&'a str),
+    LParen,
+    RParen,
+}
+
+fn eval_expr(input: &str) -> Result<f64> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser2 { tokens, pos: 0 };
+    let value = parser.expr(0)?;
+    if parser.pos != parser.tokens.len() {
+        bail!("unexpected input after expression");
+    }
+    Ok(value)
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let mut rest = input.trim();
+    while !rest.is_empty() {
+        let len;
+        if rest.starts_with("0x") || rest.starts_with("0b") || rest.starts_with("0o") {
+            let radix = match &rest[..2] {
+                "0x" => 16,
+                "0b" => 2,
+                _ => 8,
+            };
+            len = 2 + rest[2..]
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(rest.len() - 2);
+            let value = i64::from_str_radix(&rest[2..len], radix)
+                .with_context(|| format!("bad literal {:?}", &rest[..len]))?;
+            tokens.push(Token::Number(value as f64));
+        } else if rest.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+            len = rest
+                .find(|c: char| !c.is_ascii_digit() && c != '.' && c != '_')
+                .unwrap_or(rest.len());
+            let value: f64 = rest[..len].replace('_', "").parse()
+                .with_context(|| format!("bad number {:?}", &rest[..len]))?;
+            tokens.push(Token::Number(value));
+        } else if rest.starts_with('(') {
+            len = 1;
+            tokens.push(Token::LParen);
+        } else if rest.starts_with(')') {
+            len = 1;
+            tokens.push(Token::RParen);
+        } else {
+            let ops = ["<<", ">>", "**", "+", "-", "*", "/", "%", "&", "|", "^"];
+            let Some(op) = ops.iter().find(|o| rest.starts_with(**o)) else {
+                bail!("unexpected character {:?}", rest.chars().next().unwrap());
+            };
+            len = op.len();
+            tokens.push(Token::Op(&rest[..len]));
+        }
+        rest = rest[len..].trim_start();
+    }
+    Ok(tokens)
+}
+
+fn binding_power(op: &str) -> (u8, u8) {
+    match op {
+        "|" => (1, 2),
+        "^" => (3, 4),
+        "&" => (5, 6),
+        "<<" | ">>" => (7, 8),
+        "+" | "-" => (9, 10),
+        "*" | "/" | "%" => (11, 12),
+        "**" => (14, 13), // right associative
+        _ => (0, 0),
+    }
+}
+
+impl<'a> Parser2<'a> {
+    fn expr(&mut self, min_bp: u8) -> Result<f64> {
+        let mut lhs = match self.next() {
+            Some(Token::Number(n)) => n,
+            Some(Token::Op("-")) => -self.expr(13)?,
+            Some(Token::LParen) => {
+                let value = self.expr(0)?;
+                if self.next() != Some(Token::RParen) {
+                    bail!("missing closing parenthesis");
+                }
+                value
+            }
+            other => bail!("expected a value, got {other:?}"),
+        };
+        while let Some(Token::Op(op)) = self.peek() {
+            let (left_bp, right_bp) = binding_power(op);
+            if left_bp < min_bp {
+                break;
+            }
+            self.next();
+            let rhs = self.expr(right_bp)?;
+            lhs = apply(op, lhs, rhs)?;
+        }
+        Ok(lhs)
+    }
+
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let token = self.peek();
+        if token.is_some() {
+            self.pos += 1;
+        }
+        token
+    }
+}
+
+fn apply(op: &str, lhs: f64, rhs: f64) -> Result<f64> {
+    Ok(match op {
+        "+" => lhs + rhs,
+        "-" => lhs - rhs,
+        "*" => lhs * rhs,
+        "/" => {
+            if rhs == 0.0 {
+                bail!("division by zero");
+            }
+            lhs / rhs
+        }
+        "%" => lhs % rhs,
+        "**" => lhs.powf(rhs),
+        "&" => ((lhs as i64) & (rhs as i64)) as f64,
+        "|" => ((lhs as i64) | (rhs as i64)) as f64,
+        "^" => ((lhs as i64) ^ (rhs as i64)) as f64,
+        "<<" => (((lhs as i64) as u64) << (rhs as u32)) as f64,
+        ">>" => (((lhs as i64) as u64) >> (rhs as u32)) as f64,
+        other => bail!("unknown operator {other:?}"),
+    })
+}
@@ -0,0 +1,49 @@
+//! Converts a ChatGPT data-export archive into the same Markdown
+//! archive claude-export writes, so every AI conversation ends up in
+//! one place and format.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+
+use zsh_utils::claude::chatgpt;
+use zsh_utils::claude::export::Exporter;
+use zsh_utils::{display, glyphs, logger};
+
+#[derive(Parser)]
+#[command(
+    name = "chatgpt-export",
+    about = "Import a ChatGPT export archive into the Markdown archive",
+    after_help = zsh_utils::examples::after_help("chatgpt-export")
+)]
+struct Args {
+    /// OpenAI export zip, its conversations.json, or a directory it
+    /// was unpacked into
+    archive: PathBuf,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+    let sessions = chatgpt::stage(&args.archive)?;
+    if sessions.is_empty() {
+        logger::info("no conversations in the archive");
+        return Ok(());
+    }
+    let exporter = Exporter::new();
+    for session in &sessions {
+        let out = exporter.export_markdown(session)?;
+        logger::info(format!("exported {}", display::path_link(&out)));
+    }
+    logger::success(format!("exported {} conversations", sessions.len()));
+    Ok(())
+}
@@ -0,0 +1,84 @@
+//! Turns a Claude Code session into a tutorial-style blog post draft.
+//!
+//! Most sessions that solved a real problem are one editing pass away
+//! from publishable notes; this does the first pass.
+
+use anyhow::Result;
+use clap::Parser;
+
+use zsh_utils::claude::export::Exporter;
+use zsh_utils::claude::models::TranscriptEntry;
+use zsh_utils::claude::{parser, sessions};
+use zsh_utils::llm::{ChatMessage, LLMClient};
+use zsh_utils::{display, glyphs, logger};
+
+#[derive(Parser)]
+#[command(
+    name = "claude-blogify",
+    about = "Draft a tutorial-style blog post from a Claude Code session"
+)]
+struct Args {
+    /// Session id (or unique prefix) or path to a transcript file
+    session: String,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+const SYSTEM_PROMPT: &str = "You turn a raw pair-programming transcript into a \
+tutorial-style Markdown blog post. Structure it as: the problem, the approach, \
+the final code, lessons learned. Write in first person, keep only the code that \
+made it into the final solution, and drop dead ends unless they taught \
+something. Output only the Markdown post.";
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+
+    let session = sessions::find_session(&args.session)?;
+    let transcript = parser::parse_file(&session.path)?;
+
+    let mut conversation = String::new();
+    for entry in &transcript.entries {
+        let speaker = match entry {
+            TranscriptEntry::User { .. } => "Me",
+            TranscriptEntry::Assistant { .. } => "Assistant",
+            _ => continue,
+        };
+        let text = entry
+            .message()
+            .map(|m| m.content.plain_text())
+            .unwrap_or_default();
+        if !text.trim().is_empty() {
+            conversation.push_str(&format!("{speaker}: {text}\n\n"));
+        }
+    }
+    if conversation.is_empty() {
+        logger::error("session has no conversational content");
+        std::process::exit(1);
+    }
+
+    let client = LLMClient::from_config()?;
+    logger::step(format!("drafting post with {}", client.model()));
+    let post = zsh_utils::cache::LlmCache::open().complete_cached(
+        &client,
+        &[
+            ChatMessage::system(SYSTEM_PROMPT),
+            ChatMessage::user(conversation),
+        ],
+        "blogify-v1",
+    )?;
+
+    let exporter = Exporter::new();
+    let dir = exporter.session_dir(&session);
+    std::fs::create_dir_all(&dir)?;
+    let out = dir.join(format!("{}-blog.md", session.id));
+    std::fs::write(&out, post)?;
+    logger::success(format!("wrote {}", display::path_link(&out)));
+    Ok(())
+}
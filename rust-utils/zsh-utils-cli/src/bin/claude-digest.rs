@@ -0,0 +1,215 @@
+//! Daily digest of Claude Code activity, built for launchd/cron: what
+//! ran yesterday, what it cost, which projects and files it touched.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use chrono::{Duration, Local, NaiveDate};
+use clap::Parser;
+
+use zsh_utils::claude::models::{ContentBlock, MessageContent, TranscriptEntry};
+use zsh_utils::claude::pricing::Pricing;
+use zsh_utils::claude::store::SessionStore;
+use zsh_utils::llm::{ChatMessage, LLMClient};
+use zsh_utils::{display, glyphs, logger};
+
+#[derive(Parser)]
+#[command(name = "claude-digest", about = "Write a daily digest of Claude Code sessions")]
+struct Args {
+    /// Digest yesterday (the launchd/cron mode)
+    #[arg(long, conflicts_with = "date")]
+    daily: bool,
+
+    /// Digest a specific day (YYYY-MM-DD); defaults to today
+    #[arg(long)]
+    date: Option<NaiveDate>,
+
+    /// Add an LLM-written summary paragraph (needs llm.toml)
+    #[arg(long)]
+    summarize: bool,
+
+    /// Send a desktop notification when the digest is written
+    #[arg(long)]
+    notify: bool,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+/// Where digests land: `$ZSH_NOTES` or `~/notes`.
+fn notes_dir() -> PathBuf {
+    std::env::var("ZSH_NOTES")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| dirs::home_dir().unwrap_or_default().join("notes"))
+}
+
+#[derive(Default)]
+struct DayStats {
+    sessions: usize,
+    cost_usd: f64,
+    by_project: BTreeMap<String, usize>,
+    files: BTreeMap<String, u32>,
+    /// First user message of each session, fodder for the summarizer.
+    openers: Vec<String>,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+
+    let day = match (args.daily, args.date) {
+        (true, _) => Local::now().date_naive() - Duration::days(1),
+        (false, Some(date)) => date,
+        (false, None) => Local::now().date_naive(),
+    };
+
+    let stats = collect(day)?;
+    if stats.sessions == 0 {
+        logger::info(format!("no sessions on {day}"));
+        return Ok(());
+    }
+
+    let mut digest = render(day, &stats);
+    if args.summarize {
+        match summarize(&stats) {
+            Ok(summary) => digest.push_str(&format!("\n## Summary\n\n{summary}\n")),
+            Err(err) => logger::warn(format!("summary skipped: {err:#}")),
+        }
+    }
+
+    let dir = notes_dir();
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("creating {}", dir.display()))?;
+    let out = dir.join(format!("claude-digest-{day}.md"));
+    std::fs::write(&out, digest)
+        .with_context(|| format!("writing {}", out.display()))?;
+    logger::success(format!("digest written to {}", display::path_link(&out)));
+
+    if args.notify {
+        notify(&format!(
+            "{} sessions, ${:.2} across {} projects",
+            stats.sessions,
+            stats.cost_usd,
+            stats.by_project.len()
+        ));
+    }
+    Ok(())
+}
+
+fn collect(day: NaiveDate) -> Result<DayStats> {
+    let store = SessionStore::open()?;
+    let pricing = Pricing::builtin();
+    let mut stats = DayStats::default();
+    for project in store.projects() {
+        for session in project.sessions()? {
+            let Some(start) = session.start_time() else {
+                continue;
+            };
+            if start.with_timezone(&Local).date_naive() != day {
+                continue;
+            }
+            let transcript = store.load(&session)?;
+            stats.sessions += 1;
+            stats.cost_usd += pricing.estimate(&transcript).total_usd;
+            *stats
+                .by_project
+                .entry(project.friendly_name())
+                .or_default() += 1;
+            for entry in &transcript.entries {
+                let Some(message) = entry.message() else { continue };
+                if let MessageContent::Blocks(blocks) = &message.content {
+                    for block in blocks {
+                        if let ContentBlock::ToolUse { input, .. } = block {
+                            if let Some(path) =
+                                input.get("file_path").and_then(|p| p.as_str())
+                            {
+                                *stats.files.entry(path.to_string()).or_default() += 1;
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(opener) = transcript.entries.iter().find_map(|e| match e {
+                TranscriptEntry::User { message, .. } => {
+                    Some(message.content.plain_text())
+                }
+                _ => None,
+            }) {
+                let opener: String =
+                    opener.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !opener.is_empty() {
+                    stats.openers.push(opener.chars().take(200).collect());
+                }
+            }
+        }
+    }
+    Ok(stats)
+}
+
+fn render(day: NaiveDate, stats: &DayStats) -> String {
+    let mut out = format!("# Claude digest — {day}\n\n");
+    out.push_str(&format!(
+        "- Sessions: {}\n- Estimated cost: ${:.2}\n\n",
+        stats.sessions, stats.cost_usd
+    ));
+    out.push_str("## Projects\n\n");
+    for (project, count) in &stats.by_project {
+        out.push_str(&format!("- {project}: {count} sessions\n"));
+    }
+    let mut files: Vec<(&String, &u32)> = stats.files.iter().collect();
+    files.sort_by_key(|(_, count)| std::cmp::Reverse(**count));
+    if !files.is_empty() {
+        out.push_str("\n## Notable files\n\n");
+        for (path, count) in files.iter().take(10) {
+            out.push_str(&format!("- `{path}` ({count} touches)\n"));
+        }
+    }
+    out
+}
+
+fn summarize(stats: &DayStats) -> Result<String> {
+    let client = LLMClient::from_config()?;
+    let mut prompt = String::from(
+        "Summarize this day of coding-assistant sessions in one short \
+         paragraph, then up to three bullet takeaways. Session openers:\n",
+    );
+    for opener in stats.openers.iter().take(30) {
+        prompt.push_str(&format!("- {opener}\n"));
+    }
+    zsh_utils::cache::LlmCache::open().complete_cached(
+        &client,
+        &[
+            ChatMessage::system("You write terse engineering digests."),
+            ChatMessage::user(prompt),
+        ],
+        "digest-v1",
+    )
+}
+
+/// Best-effort desktop notification: osascript on macOS, notify-send
+/// elsewhere. Failure only warns — cron must not see an error exit.
+fn notify(body: &str) {
+    let sent = Command::new("osascript")
+        .args([
+            "-e",
+            &format!(r#"display notification "{body}" with title "claude-digest""#),
+        ])
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+        || Command::new("notify-send")
+            .args(["claude-digest", body])
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false);
+    if !sent {
+        logger::warn("could not send a desktop notification");
+    }
+}
@@ -0,0 +1,90 @@
+//! Reports corruption and gaps in the Claude transcript store that
+//! the tolerant parser otherwise hides (see `zsh_utils::claude::doctor`).
+
+use anyhow::Result;
+use clap::Parser;
+
+use zsh_utils::claude::doctor;
+use zsh_utils::{display, glyphs, logger};
+
+#[derive(Parser)]
+#[command(
+    name = "claude-doctor",
+    about = "Check every transcript for malformed lines, unknown entry types, and gaps"
+)]
+struct Args {
+    /// Stable tab-separated records on stdout for scripting
+    #[arg(long, global = true)]
+    porcelain: bool,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+    display::init_porcelain(args.porcelain);
+
+    let report = doctor::check()?;
+    for file in &report.files {
+        let path = file.path.display().to_string();
+        if display::is_porcelain() {
+            for line in &file.malformed {
+                display::porcelain(&["malformed", &path, &line.to_string()]);
+            }
+            for (kind, count) in &file.unknown_types {
+                display::porcelain(&[
+                    "unknown-type",
+                    &path,
+                    kind,
+                    &count.to_string(),
+                ]);
+            }
+            for id in &file.orphaned_snapshots {
+                display::porcelain(&["orphaned-snapshot", &path, id]);
+            }
+            if file.missing_timestamps {
+                display::porcelain(&["missing-timestamps", &path]);
+            }
+            continue;
+        }
+        println!("{} ({})", display::path_link(&file.path), file.project);
+        if !file.malformed.is_empty() {
+            println!(
+                "  {} malformed line{} (first at line {})",
+                file.malformed.len(),
+                if file.malformed.len() == 1 { "" } else { "s" },
+                file.malformed[0],
+            );
+        }
+        for (kind, count) in &file.unknown_types {
+            println!("  unknown entry type {kind:?} x{count}");
+        }
+        for id in &file.orphaned_snapshots {
+            println!("  snapshot references unknown message {id}");
+        }
+        if file.missing_timestamps {
+            println!("  no entry carries a timestamp");
+        }
+    }
+
+    let checked = format!(
+        "{} sessions across {} projects",
+        report.sessions_checked, report.projects_checked
+    );
+    if report.healthy() {
+        logger::success(format!("no problems in {checked}"));
+    } else {
+        logger::warn(format!(
+            "{} of {checked} need attention",
+            report.files.len()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,830 @@
+//! Exports Claude Code sessions to readable Markdown.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::{Parser, ValueEnum};
+
+use zsh_utils::claude::archive::{self, ArchiveFormat};
+use zsh_utils::claude::checkpoint::{self, Checkpoint};
+use zsh_utils::claude::dedup::{self, DedupIndex};
+use zsh_utils::claude::export::{ExportConfig, Exporter, RenderOptions, Sections};
+use zsh_utils::claude::hooks::{HookEvent, Hooks};
+use zsh_utils::claude::sessions::ProjectMatcher;
+use zsh_utils::claude::snapshots::SnapshotPolicy;
+use zsh_utils::claude::store::{self, SessionStore};
+use zsh_utils::claude::sync::Sync;
+use zsh_utils::claude::tags::Tags;
+use zsh_utils::claude::webhook::WebhookPublisher;
+use zsh_utils::claude::pricing::Pricing;
+use zsh_utils::claude::notion::NotionClient;
+use zsh_utils::claude::{bundle, desktop, merge, parser, picker, schedule, sessions};
+use zsh_utils::llm::LLMClient;
+use zsh_utils::{display, glyphs, logger};
+
+#[derive(Parser)]
+#[command(
+    name = "claude-export",
+    about = "Export Claude Code sessions to Markdown",
+    after_help = zsh_utils::examples::after_help("claude-export")
+)]
+struct Args {
+    /// Session id (or unique prefix) or path to a transcript file.
+    /// Omit to export every session of the project given with -p.
+    session: Option<String>,
+
+    /// Project(s) to export when no session is given: fuzzy-matched
+    /// against friendly names, comma-separated patterns are OR-ed
+    #[arg(short = 'p', long)]
+    project: Option<String>,
+
+    /// Match -p case-sensitively
+    #[arg(long, requires = "project")]
+    match_case: bool,
+
+    /// Minimum fuzzy-match score for -p (0 to 1)
+    #[arg(long, value_name = "SCORE", default_value_t = 0.5, requires = "project")]
+    min_score: f64,
+
+    /// Score prefix matches no higher than other substring matches
+    #[arg(long, requires = "project")]
+    no_prefix_boost: bool,
+
+    /// Output format
+    #[arg(short = 'f', long, value_enum, default_value_t = Format::Markdown)]
+    format: Format,
+
+    /// Where to read conversations from: Claude Code transcripts,
+    /// Claude Desktop's local store, or both
+    #[arg(long, value_enum, default_value_t = Source::Code, conflicts_with = "session")]
+    source: Source,
+
+    /// Only export sessions started on/after this date (YYYY-MM-DD)
+    #[arg(long)]
+    since: Option<String>,
+
+    /// Only export sessions started on/before this date (YYYY-MM-DD)
+    #[arg(long)]
+    until: Option<String>,
+
+    /// Skip sessions with fewer than this many messages
+    #[arg(long, value_name = "N")]
+    min_messages: Option<usize>,
+
+    /// Skip sessions with fewer than this many input+output tokens
+    #[arg(long, value_name = "N")]
+    min_tokens: Option<u64>,
+
+    /// With a session: attach these tags to it (comma-separated) and
+    /// exit without exporting. Without one: only export sessions
+    /// carrying at least one of these tags
+    #[arg(long, value_name = "TAGS", value_delimiter = ',')]
+    tag: Vec<String>,
+
+    /// Remove these tags from the given session
+    #[arg(long, value_name = "TAGS", value_delimiter = ',', requires = "session")]
+    untag: Vec<String>,
+
+    /// Pick sessions from an interactive list instead of exporting
+    /// everything that matches
+    #[arg(short = 'i', long)]
+    interactive: bool,
+
+    /// Export every project instead of showing the project picker when
+    /// no session or -p is given
+    #[arg(long, conflicts_with = "project")]
+    all: bool,
+
+    /// TOML file with per-model pricing overrides for cost estimates
+    #[arg(long, value_name = "FILE")]
+    pricing_file: Option<std::path::PathBuf>,
+
+    /// Refresh the claude-search index after exporting
+    #[arg(long)]
+    reindex: bool,
+
+    /// Shell command run after each exported session, on top of any
+    /// configured hooks; gets the metadata as JSON on stdin and as
+    /// CLAUDE_EXPORT_* environment variables (repeatable)
+    #[arg(long, value_name = "CMD")]
+    post_hook: Vec<String>,
+
+    /// After exporting, git add/commit the export directory (and push
+    /// when it has a remote); also enabled by [sync] git in the config
+    #[arg(long)]
+    sync_git: bool,
+
+    /// After exporting, mirror the export directory to this target:
+    /// s3://bucket/prefix (aws CLI) or an rclone remote (WebDAV etc.)
+    #[arg(long, value_name = "TARGET")]
+    sync_remote: Option<String>,
+
+    /// Add LLM-written "What Happened"/"Lessons Learned" sections to
+    /// Markdown exports (uses the llm.toml endpoint)
+    #[arg(long)]
+    summarize: bool,
+
+    /// Also publish each exported session somewhere
+    #[arg(long, value_enum)]
+    publish: Option<Publish>,
+
+    /// Notion database id for --publish notion
+    #[arg(long, requires = "publish")]
+    database: Option<String>,
+
+    /// Endpoint for --publish webhook (auth via $CLAUDE_WEBHOOK_TOKEN)
+    #[arg(long, requires = "publish")]
+    url: Option<String>,
+
+    /// Store file versions from tool uses as base + unified diffs and
+    /// link them from the Markdown
+    #[arg(long)]
+    snapshots: bool,
+
+    /// Keep only the last N versions of each snapshotted file
+    #[arg(long, value_name = "N", requires = "snapshots")]
+    keep_versions: Option<usize>,
+
+    /// Skip snapshots of files over this size (e.g. 5MB, 500KB, 4096)
+    #[arg(long, value_name = "SIZE", requires = "snapshots")]
+    max_snapshot_size: Option<String>,
+
+    /// Skip snapshots of files that look binary
+    #[arg(long, requires = "snapshots")]
+    skip_binary: bool,
+
+    /// Output directory is cloud-synced: zip snapshots per session and
+    /// write files atomically
+    #[arg(long)]
+    synced: bool,
+
+    /// Pause this many milliseconds between writes so sync clients
+    /// keep up; defaults to 50 when the output looks iCloud/Dropbox
+    #[arg(long, value_name = "MS", num_args = 0..=1, default_missing_value = "50")]
+    io_throttle: Option<u64>,
+
+    /// Skip sessions whose content was already exported in this format
+    /// (content-hash index at the export root)
+    #[arg(long)]
+    dedup: bool,
+
+    /// Delete each chosen project's export directory first for a
+    /// from-scratch rebuild (bulk exports only; the directory is moved
+    /// aside before deletion so a crash can't half-empty it)
+    #[arg(long, conflicts_with_all = ["session", "interactive"])]
+    clean: bool,
+
+    /// Rewrite home paths, username, hostname, and email addresses to
+    /// generic placeholders, for publishing sessions publicly
+    #[arg(long)]
+    anonymize: bool,
+
+    /// Tera template replacing the built-in Markdown layout (context:
+    /// `session` model plus the default `markdown` rendering)
+    #[arg(long, value_name = "FILE")]
+    template: Option<std::path::PathBuf>,
+
+    /// Merge resumed sessions into one document per piece of work
+    /// (Markdown only; continuations get "Resumed" separators)
+    #[arg(long, conflicts_with_all = ["session", "interactive", "site"])]
+    merge: bool,
+
+    /// Embed a Mermaid gantt timeline of assistant turns and tool
+    /// invocations in each Markdown export
+    #[arg(long)]
+    timeline: bool,
+
+    /// Add a "Commits during session" section from git log over the
+    /// session's time window in its recorded cwd
+    #[arg(long)]
+    git_log: bool,
+
+    /// Leave tool result blocks out of the Markdown entirely
+    #[arg(long)]
+    no_tool_results: bool,
+
+    /// Cap each tool result at this many lines (the rest becomes a
+    /// "... (+N more lines)" marker)
+    #[arg(long, value_name = "LINES")]
+    max_tool_output: Option<usize>,
+
+    /// Cap each tool result at this size (e.g. 20KB); the full output
+    /// lands in a sidecar file the document links to
+    #[arg(long, value_name = "SIZE")]
+    max_tool_result: Option<String>,
+
+    /// Only render these tools' calls and results (comma-separated,
+    /// e.g. Edit,Write)
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    tools: Vec<String>,
+
+    /// Only emit these sections of the document (comma-separated:
+    /// context, summary, transcript, files, tokens); default is all
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    sections: Vec<String>,
+
+    /// Bundle each exported project directory into one compressed
+    /// archive with a manifest
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    archive: Option<Archive>,
+
+    /// Write the session as one portable .claudepack bundle (raw
+    /// transcript, rendered Markdown/HTML/JSON, snapshots, manifest);
+    /// unpack on another machine with claude-import
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "session",
+        conflicts_with_all = ["format", "archive", "publish", "dedup"]
+    )]
+    bundle: Option<std::path::PathBuf>,
+
+    /// Generate a browsable HTML site of every project and session
+    /// under <export dir>/site instead of per-session exports
+    #[arg(long, conflicts_with_all = ["session", "project", "interactive", "all"])]
+    site: bool,
+
+    /// Install a recurring export job (launchd agent on macOS, systemd
+    /// user timer on Linux) running `claude-export --all --dedup`;
+    /// accepts daily@HH:MM or hourly[@:MM]
+    #[arg(long, value_name = "SPEC", exclusive = true)]
+    install_schedule: Option<String>,
+
+    /// Unload and remove the recurring export job
+    #[arg(long, exclusive = true)]
+    uninstall_schedule: bool,
+
+    /// Stable tab-separated records on stdout for scripting (no
+    /// emoji, colors, or progress)
+    #[arg(long, global = true)]
+    porcelain: bool,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Markdown,
+    Json,
+    /// One flat record per message, for DuckDB/pandas.
+    Jsonl,
+    /// Emacs org-mode with properties drawers and foldable results.
+    Org,
+    /// Typeset via an external HTML-to-PDF converter.
+    Pdf,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Source {
+    Code,
+    Desktop,
+    All,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Publish {
+    Notion,
+    Webhook,
+}
+
+enum Publisher {
+    Notion(NotionClient),
+    Webhook(WebhookPublisher),
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Archive {
+    Zip,
+    #[value(name = "tar.gz")]
+    TarGz,
+}
+
+impl From<Archive> for ArchiveFormat {
+    fn from(archive: Archive) -> Self {
+        match archive {
+            Archive::Zip => ArchiveFormat::Zip,
+            Archive::TarGz => ArchiveFormat::TarGz,
+        }
+    }
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+    display::init_porcelain(args.porcelain);
+    if let Some(spec) = &args.install_schedule {
+        let path = schedule::install(&schedule::Spec::parse(spec)?)?;
+        logger::success(format!(
+            "scheduled export installed ({})",
+            display::path_link(&path)
+        ));
+        return Ok(());
+    }
+    if args.uninstall_schedule {
+        schedule::uninstall()?;
+        logger::success("scheduled export removed");
+        return Ok(());
+    }
+    // Bundles stage their artifacts in a scratch directory instead of
+    // the real export tree.
+    let staging = args.bundle.as_ref().map(|_| {
+        std::env::temp_dir().join(format!("claudepack-{}", std::process::id()))
+    });
+    let mut exporter = match &staging {
+        Some(dir) => Exporter::with_root(dir.clone()),
+        None => Exporter::new(),
+    };
+    // Collision-proof the per-project directories up front; a missing
+    // ~/.claude only matters later, when sessions are actually listed.
+    if let Ok(projects) = sessions::projects() {
+        exporter = exporter.with_project_names(sessions::unique_names(&projects));
+    }
+    if let Some(path) = &args.pricing_file {
+        exporter = exporter.with_pricing(Pricing::with_overrides(path)?);
+    }
+    if args.summarize {
+        exporter = exporter.with_summarizer(LLMClient::from_config()?);
+    }
+    if let Some(path) = &args.template {
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("reading template {}", path.display()))?;
+        exporter = exporter.with_template(source);
+    }
+    let throttle = args.io_throttle.or_else(|| {
+        zsh_utils::claude::export::looks_synced(
+            &zsh_utils::claude::export::export_root(),
+        )
+        .then_some(50)
+    });
+    exporter = exporter
+        .with_io_throttle(throttle.map(std::time::Duration::from_millis))
+        .with_snapshots(args.snapshots)
+        .with_snapshot_policy(SnapshotPolicy {
+            keep_versions: args.keep_versions,
+            max_bytes: args.max_snapshot_size.as_deref().map(parse_size).transpose()?,
+            skip_binary: args.skip_binary,
+        })
+        .with_synced(args.synced)
+        .with_anonymize(args.anonymize)
+        .with_timeline(args.timeline)
+        .with_git_log(args.git_log)
+        .with_render_options(RenderOptions {
+            tool_results: !args.no_tool_results,
+            max_tool_output: args.max_tool_output,
+            max_tool_result: args
+                .max_tool_result
+                .as_deref()
+                .map(parse_size)
+                .transpose()?,
+            tools: (!args.tools.is_empty())
+                .then(|| args.tools.iter().cloned().collect()),
+            ignored: ExportConfig::from_config()?.ignore_tools.into_iter().collect(),
+            sections: if args.sections.is_empty() {
+                Sections::default()
+            } else {
+                Sections::parse(&args.sections)?
+            },
+        });
+    let publisher = match args.publish {
+        Some(Publish::Notion) => {
+            let database = args
+                .database
+                .clone()
+                .context("--publish notion needs --database <id>")?;
+            Some(Publisher::Notion(NotionClient::new(database)?))
+        }
+        Some(Publish::Webhook) => {
+            let url = args
+                .url
+                .clone()
+                .context("--publish webhook needs --url <endpoint>")?;
+            Some(Publisher::Webhook(WebhookPublisher::new(url)))
+        }
+        None => None,
+    };
+    let publish_pricing = match &args.pricing_file {
+        Some(path) => Pricing::with_overrides(path)?,
+        None => Pricing::builtin(),
+    };
+
+    if args.site {
+        let root = zsh_utils::claude::export::export_root().join("site");
+        let index = zsh_utils::claude::site::generate(&root, &publish_pricing)?;
+        logger::success(format!("site written to {}", display::path_link(&index)));
+        return reindex(args.reindex);
+    }
+
+    let hooks = args
+        .post_hook
+        .iter()
+        .cloned()
+        .fold(Hooks::from_config()?, Hooks::with_command);
+    let format_name = match args.format {
+        Format::Markdown => "markdown",
+        Format::Json => "json",
+        Format::Jsonl => "jsonl",
+        Format::Org => "org",
+        Format::Pdf => "pdf",
+    };
+    let export_root = zsh_utils::claude::export::export_root();
+    let dedup_index = args
+        .dedup
+        .then(|| std::cell::RefCell::new(DedupIndex::load(&export_root)));
+    let export = |session: &sessions::Session| {
+        let dedup_key = match &dedup_index {
+            Some(_) => Some(dedup::key(&session.path, format_name)?),
+            None => None,
+        };
+        if let (Some(index), Some(key)) = (&dedup_index, &dedup_key) {
+            if let Some(existing) = index.borrow().lookup(key).map(str::to_string) {
+                let path = export_root.join(&existing);
+                if path.is_file() {
+                    logger::info(format!(
+                        "{} is identical to {existing}; skipped",
+                        session.id
+                    ));
+                    report("skipped", &path);
+                    return Ok(path);
+                }
+            }
+        }
+        let out = match args.format {
+            Format::Markdown => exporter.export_markdown(session),
+            Format::Json => exporter.export_json(session),
+            Format::Jsonl => exporter.export_jsonl(session),
+            Format::Org => exporter.export_org(session),
+            Format::Pdf => exporter.export_pdf(session),
+        }?;
+        if let (Some(index), Some(key)) = (&dedup_index, dedup_key) {
+            let artifact = out
+                .strip_prefix(&export_root)
+                .unwrap_or(&out)
+                .to_string_lossy()
+                .into_owned();
+            index.borrow_mut().record(key, artifact)?;
+        }
+        if !hooks.is_empty() {
+            let transcript = parser::parse_file(&session.path)?;
+            hooks.fire(&HookEvent {
+                session_id: session.id.clone(),
+                project: session.project.friendly_name(),
+                path: out.display().to_string(),
+                format: format_name.to_string(),
+                estimated_cost_usd: publish_pricing.estimate(&transcript).total_usd,
+            });
+        }
+        match &publisher {
+            Some(Publisher::Notion(notion)) => {
+                let transcript = parser::parse_file(&session.path)?;
+                let url =
+                    notion.publish_session(session, &transcript, &publish_pricing)?;
+                logger::info(format!("published {url}"));
+            }
+            Some(Publisher::Webhook(webhook)) => {
+                let transcript = parser::parse_file(&session.path)?;
+                webhook.publish_session(session, &transcript, &publish_pricing)?;
+                logger::info("published session summary");
+            }
+            None => {}
+        }
+        Ok::<_, anyhow::Error>(out)
+    };
+
+    if let Some(query) = &args.session {
+        let session = sessions::find_session(query)?;
+        // With a session named, --tag/--untag edit the tag store and
+        // stop; exporting stays a separate run.
+        if !args.tag.is_empty() || !args.untag.is_empty() {
+            let mut tags = Tags::load();
+            if !args.tag.is_empty() {
+                tags.add(&session.id, &args.tag)?;
+            }
+            if !args.untag.is_empty() {
+                tags.remove(&session.id, &args.untag)?;
+            }
+            let current = tags.for_session(&session.id);
+            if display::is_porcelain() {
+                display::porcelain(&["tags", &session.id, &current.join(",")]);
+            }
+            if current.is_empty() {
+                logger::success(format!("{} has no tags", session.id));
+            } else {
+                logger::success(format!("{}: {}", session.id, current.join(", ")));
+            }
+            return Ok(());
+        }
+        if let Some(out) = &args.bundle {
+            let out = bundle::export_bundle(&exporter, &session, out)?;
+            if let Some(dir) = &staging {
+                let _ = std::fs::remove_dir_all(dir);
+            }
+            logger::success(format!("bundled {}", display::path_link(&out)));
+            report("bundled", &out);
+            return Ok(());
+        }
+        let out = export(&session)?;
+        logger::success(format!("exported {}", display::path_link(&out)));
+        report("exported", &out);
+        archive_projects(
+            out.parent().map(|p| p.to_path_buf()).into_iter().collect(),
+            args.archive,
+        )?;
+        sync_exports(&args)?;
+        return reindex(args.reindex);
+    }
+
+    let since = args.since.as_deref().map(parse_date).transpose()?;
+    let until = args.until.as_deref().map(parse_date).transpose()?;
+    // In bulk runs --tag is a filter; untagged sessions never match it.
+    let tag_filter = (!args.tag.is_empty()).then(Tags::load);
+    let keep_tags = |s: &sessions::Session| {
+        tag_filter
+            .as_ref()
+            .is_none_or(|tags| tags.matches_any(&s.id, &args.tag))
+    };
+    let matcher = args.project.as_deref().map(|spec| {
+        ProjectMatcher::new(spec)
+            .with_case_sensitive(args.match_case)
+            .with_min_score(args.min_score)
+            .with_prefer_prefix(!args.no_prefix_boost)
+    });
+
+    // Desktop chats arrive pre-converted into transcript shape (see
+    // `desktop::stage`) so the date/size filters apply unchanged.
+    // `--source all` quietly skips machines with no Desktop store;
+    // asking for desktop alone still errors loudly.
+    let want_desktop = match args.source {
+        Source::Code => false,
+        Source::Desktop => true,
+        Source::All => desktop::available(),
+    };
+    let stage_desktop = || -> Result<(Vec<sessions::Session>, usize)> {
+        let (kept, dropped): (Vec<_>, Vec<_>) =
+            desktop::stage()?.into_iter().partition(|s| {
+                store::in_range(s, since, until)
+                    && store::meets_size(s, args.min_messages, args.min_tokens)
+                    && keep_tags(s)
+            });
+        Ok((kept, dropped.len()))
+    };
+
+    if args.interactive {
+        let mut candidates: Vec<_> = match args.source {
+            Source::Desktop => Vec::new(),
+            _ => SessionStore::open()?
+                .sessions_matching(matcher.as_ref(), since, until)?
+                .into_iter()
+                .filter(|s| {
+                    store::meets_size(s, args.min_messages, args.min_tokens)
+                        && keep_tags(s)
+                })
+                .collect(),
+        };
+        if want_desktop {
+            candidates.extend(stage_desktop()?.0);
+        }
+        let picked = picker::pick_sessions(candidates)?;
+        if picked.is_empty() {
+            logger::info("nothing selected");
+            return Ok(());
+        }
+        let mut dirs = std::collections::BTreeSet::new();
+        for session in &picked {
+            let out = export(session)?;
+            logger::info(format!("exported {}", display::path_link(&out)));
+            report("exported", &out);
+            dirs.extend(out.parent().map(|p| p.to_path_buf()));
+        }
+        logger::success(format!("exported {} sessions", picked.len()));
+        archive_projects(dirs, args.archive)?;
+        sync_exports(&args)?;
+        return reindex(args.reindex);
+    }
+
+    // No session, no -p: pick a project interactively (or take them
+    // all with --all) rather than silently exporting everything.
+    let chosen = match &matcher {
+        _ if args.source == Source::Desktop => Vec::new(),
+        Some(matcher) => choose_projects(matcher)?,
+        None if args.all => sessions::projects()?,
+        None => match picker::pick_project(sessions::projects()?)? {
+            Some(project) => vec![project],
+            None => {
+                logger::info("nothing selected");
+                return Ok(());
+            }
+        },
+    };
+    if args.merge && args.format != Format::Markdown {
+        anyhow::bail!("--merge only applies to Markdown exports");
+    }
+    // An interrupted bulk run leaves a checkpoint behind; this one
+    // skips what it already finished. --clean steps aside while a
+    // resume is pending — wiping the directory would throw away the
+    // very progress being resumed.
+    let mut checkpoint = Checkpoint::load(&export_root, format_name);
+    if checkpoint.resuming() {
+        logger::info(format!(
+            "resuming interrupted export ({} sessions already done)",
+            checkpoint.done()
+        ));
+        if args.clean {
+            logger::warn("--clean skipped while resuming; re-run it afterwards");
+        }
+    }
+    let mut count = 0;
+    let mut skipped = 0;
+    let mut dirs = std::collections::BTreeSet::new();
+    for project in chosen {
+        if args.clean && !checkpoint.resuming() {
+            checkpoint::clean_dir(&exporter.project_dir(&project))?;
+        }
+        let (in_range, filtered): (Vec<_>, Vec<_>) =
+            project.sessions()?.into_iter().partition(|s| {
+                store::in_range(s, since, until)
+                    && store::meets_size(s, args.min_messages, args.min_tokens)
+                    && keep_tags(s)
+            });
+        skipped += filtered.len();
+        if args.merge {
+            for chain in merge::chains(in_range)? {
+                let out = exporter.export_merged(&chain.sessions)?;
+                if chain.sessions.len() > 1 {
+                    logger::info(format!(
+                        "merged {} resumed sessions into {}",
+                        chain.sessions.len(),
+                        display::path_link(&out)
+                    ));
+                    report("merged", &out);
+                } else {
+                    logger::info(format!("exported {}", display::path_link(&out)));
+                    report("exported", &out);
+                }
+                dirs.extend(out.parent().map(|p| p.to_path_buf()));
+                count += chain.sessions.len();
+            }
+            continue;
+        }
+        for session in in_range {
+            if checkpoint.is_done(&project.encoded_name, &session.id) {
+                continue;
+            }
+            let out = export(&session)?;
+            logger::info(format!("exported {}", display::path_link(&out)));
+            report("exported", &out);
+            dirs.extend(out.parent().map(|p| p.to_path_buf()));
+            count += 1;
+            checkpoint.mark_done(&project.encoded_name, &session.id)?;
+        }
+    }
+    if want_desktop {
+        let (desktop_sessions, dropped) = stage_desktop()?;
+        skipped += dropped;
+        for session in desktop_sessions {
+            if checkpoint.is_done(&session.project.encoded_name, &session.id) {
+                continue;
+            }
+            let out = export(&session)?;
+            logger::info(format!("exported {}", display::path_link(&out)));
+            report("exported", &out);
+            dirs.extend(out.parent().map(|p| p.to_path_buf()));
+            count += 1;
+            checkpoint.mark_done(&session.project.encoded_name, &session.id)?;
+        }
+    }
+    checkpoint.finish()?;
+    if skipped > 0 {
+        logger::info(format!(
+            "skipped {skipped} sessions outside the date/size/tag filters"
+        ));
+    }
+    logger::success(format!("exported {count} sessions"));
+    archive_projects(dirs, args.archive)?;
+    sync_exports(&args)?;
+    reindex(args.reindex)
+}
+
+/// The built-in publish step (git auto-commit, remote mirror): the
+/// `[sync]` config table plus whatever the per-run flags add.
+fn sync_exports(args: &Args) -> Result<()> {
+    let mut sync = Sync::from_config()?;
+    sync.git |= args.sync_git;
+    if args.sync_remote.is_some() {
+        sync.remote = args.sync_remote.clone();
+    }
+    if sync.is_empty() {
+        return Ok(());
+    }
+    sync.run(&zsh_utils::claude::export::export_root())
+}
+
+/// Under `--porcelain`, one stable record per artifact; the human
+/// logger lines alongside it go quiet on their own.
+fn report(kind: &str, path: &std::path::Path) {
+    if display::is_porcelain() {
+        display::porcelain(&[kind, &path.display().to_string()]);
+    }
+}
+
+/// Bundles every project directory this run exported into, once each.
+fn archive_projects(
+    dirs: std::collections::BTreeSet<std::path::PathBuf>,
+    format: Option<Archive>,
+) -> Result<()> {
+    let Some(format) = format else { return Ok(()) };
+    for dir in dirs {
+        let out = archive::archive_project(&dir, format.into())?;
+        logger::success(format!("archived {}", display::path_link(&out)));
+        report("archived", &out);
+    }
+    Ok(())
+}
+
+/// Keeps the search index warm from the tool people already run;
+/// refresh is incremental so this is cheap when little has changed.
+fn reindex(wanted: bool) -> Result<()> {
+    if !wanted {
+        return Ok(());
+    }
+    let stats = zsh_utils::claude::index::Index::open()?.refresh()?;
+    logger::info(format!(
+        "search index: {} sessions refreshed, {} removed",
+        stats.indexed, stats.removed
+    ));
+    Ok(())
+}
+
+/// The projects a `-p` spec selects. A clearly best match wins
+/// outright; when several score within a whisker of each other the
+/// user picks, instead of everything being exported at once. OR-ed
+/// patterns (`-p "zshrc,dotfiles"`) deliberately select them all.
+fn choose_projects(
+    matcher: &ProjectMatcher,
+) -> Result<Vec<zsh_utils::claude::sessions::Project>> {
+    use std::io::Write;
+
+    let projects = sessions::projects()?;
+    let ranked = matcher.rank(&projects);
+    match ranked.as_slice() {
+        [] => anyhow::bail!("no project matches the -p filter"),
+        [(only, _)] => Ok(vec![(*only).clone()]),
+        ranked if matcher.is_multi() => {
+            Ok(ranked.iter().map(|(p, _)| (*p).clone()).collect())
+        }
+        ranked if ranked[0].1 - ranked[1].1 >= 0.2 => Ok(vec![ranked[0].0.clone()]),
+        ranked => {
+            println!("several projects match:");
+            for (i, (project, score)) in ranked.iter().enumerate() {
+                println!("  {}) {} ({score:.2})", i + 1, project.friendly_name());
+            }
+            print!("pick one [1-{}]: ", ranked.len());
+            std::io::stdout().flush()?;
+            let mut answer = String::new();
+            std::io::stdin().read_line(&mut answer)?;
+            let choice: usize = answer
+                .trim()
+                .parse()
+                .with_context(|| format!("not a number: {:?}", answer.trim()))?;
+            let (project, _) = ranked
+                .get(choice.checked_sub(1).context("choice out of range")?)
+                .context("choice out of range")?;
+            Ok(vec![(*project).clone()])
+        }
+    }
+}
+
+/// Accepts bare bytes or a KB/MB/GB suffix (decimal, case-insensitive).
+fn parse_size(raw: &str) -> Result<u64> {
+    let raw = raw.trim();
+    let (digits, multiplier) = match raw.to_ascii_uppercase() {
+        s if s.ends_with("GB") => (&raw[..raw.len() - 2], 1_000_000_000),
+        s if s.ends_with("MB") => (&raw[..raw.len() - 2], 1_000_000),
+        s if s.ends_with("KB") => (&raw[..raw.len() - 2], 1_000),
+        s if s.ends_with('B') => (&raw[..raw.len() - 1], 1),
+        _ => (raw, 1),
+    };
+    let value: u64 = digits
+        .trim()
+        .parse()
+        .with_context(|| format!("cannot parse size {raw:?}"))?;
+    Ok(value * multiplier)
+}
+
+/// Accepts YYYY-MM-DD (midnight UTC) or a full RFC 3339 timestamp.
+fn parse_date(raw: &str) -> Result<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        return Ok(date
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is valid")
+            .and_utc());
+    }
+    DateTime::parse_from_rfc3339(raw)
+        .map(|t| t.with_timezone(&Utc))
+        .with_context(|| format!("cannot parse date {raw:?}"))
+}
+
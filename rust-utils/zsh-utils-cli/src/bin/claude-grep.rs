@@ -0,0 +1,139 @@
+//! Grep over Claude Code transcripts without building an index: streams
+//! every `projects/**/*.jsonl`, matches against message text only (not
+//! the JSON noise around it), and prints hits with context.
+
+use std::io::BufRead;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use regex::RegexBuilder;
+
+use zsh_utils::claude::models::TranscriptEntry;
+use zsh_utils::claude::sessions::{self, Session};
+use zsh_utils::{glyphs, logger, term};
+
+#[derive(Parser)]
+#[command(name = "claude-grep", about = "Grep message text in Claude Code transcripts")]
+struct Args {
+    /// Regular expression to search for
+    pattern: String,
+
+    /// Restrict to one project (friendly name)
+    #[arg(short = 'p', long)]
+    project: Option<String>,
+
+    /// Only search messages with this role (user or assistant)
+    #[arg(short = 'r', long)]
+    role: Option<String>,
+
+    /// Case-insensitive matching
+    #[arg(short = 'i', long)]
+    ignore_case: bool,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+
+    let regex = RegexBuilder::new(&args.pattern)
+        .case_insensitive(args.ignore_case)
+        .build()
+        .with_context(|| format!("bad pattern {:?}", args.pattern))?;
+
+    let mut hits = 0;
+    for project in sessions::projects()? {
+        if args
+            .project
+            .as_deref()
+            .is_some_and(|name| project.friendly_name() != name)
+        {
+            continue;
+        }
+        for session in project.sessions()? {
+            hits += grep_session(&session, &regex, args.role.as_deref())?;
+        }
+    }
+    if hits == 0 {
+        logger::info("no matches");
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Streams one transcript line by line so huge sessions never need to
+/// fit in memory; malformed lines are simply not matches.
+fn grep_session(
+    session: &Session,
+    regex: &regex::Regex,
+    role_filter: Option<&str>,
+) -> Result<usize> {
+    let file = std::fs::File::open(&session.path)
+        .with_context(|| format!("opening {}", session.path.display()))?;
+    let reader = std::io::BufReader::new(file);
+    let mut hits = 0;
+    for line in reader.lines() {
+        let Ok(line) = line else { continue };
+        let Ok(entry) = serde_json::from_str::<TranscriptEntry>(&line) else {
+            continue;
+        };
+        let role = match &entry {
+            TranscriptEntry::User { .. } => "user",
+            TranscriptEntry::Assistant { .. } => "assistant",
+            _ => continue,
+        };
+        if role_filter.is_some_and(|want| want != role) {
+            continue;
+        }
+        let text = entry.message().expect("user/assistant have messages");
+        let text = text.content.plain_text();
+        for text_line in text.lines() {
+            if regex.is_match(text_line) {
+                print_hit(session, &entry, role, text_line, regex);
+                hits += 1;
+            }
+        }
+    }
+    Ok(hits)
+}
+
+fn print_hit(
+    session: &Session,
+    entry: &TranscriptEntry,
+    role: &str,
+    line: &str,
+    regex: &regex::Regex,
+) {
+    let date = entry
+        .meta()
+        .and_then(|m| m.timestamp.as_deref())
+        .map(|t| t.chars().take(10).collect::<String>())
+        .unwrap_or_else(|| "----------".to_string());
+    let id_short: String = session.id.chars().take(8).collect();
+    println!(
+        "{}  {}  {}  {}: {}",
+        term::paint("2", &date),
+        term::paint("1;36", &session.project.friendly_name()),
+        term::paint("2", &id_short),
+        role,
+        highlight(line, regex),
+    );
+}
+
+/// Wraps every match in bold yellow, or `[...]` when colors are off so
+/// matches stay visible in pipes.
+fn highlight(line: &str, regex: &regex::Regex) -> String {
+    let replacement = if term::colors_enabled() {
+        "\x1b[1;33m$0\x1b[0m"
+    } else {
+        "[$0]"
+    };
+    regex.replace_all(line, replacement).into_owned()
+}
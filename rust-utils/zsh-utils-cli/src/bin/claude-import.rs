@@ -0,0 +1,51 @@
+//! Unpacks a `.claudepack` session bundle made by `claude-export
+//! --bundle` on another machine.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+
+use zsh_utils::claude::bundle;
+use zsh_utils::{display, glyphs, logger};
+
+#[derive(Parser)]
+#[command(
+    name = "claude-import",
+    about = "Import a .claudepack session bundle into this machine's archive",
+    after_help = zsh_utils::examples::after_help("claude-import")
+)]
+struct Args {
+    /// Bundle file written by claude-export --bundle
+    bundle: PathBuf,
+
+    /// Overwrite an existing transcript with the same session id
+    #[arg(long)]
+    force: bool,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+    let manifest = bundle::import_bundle(&args.bundle, args.force)?;
+    logger::success(format!(
+        "imported session {} into project {} ({} rendered files)",
+        manifest.session_id,
+        manifest.project,
+        manifest.files.len()
+    ));
+    logger::info(format!(
+        "bundle from {}: {}",
+        manifest.created,
+        display::path_link(&args.bundle)
+    ));
+    Ok(())
+}
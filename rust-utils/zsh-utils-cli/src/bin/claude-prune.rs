@@ -0,0 +1,124 @@
+//! Applies a retention policy to the Claude home.
+
+use anyhow::{Context, Result};
+use clap::Parser;
+
+use zsh_utils::claude::prune::{self, Policy};
+use zsh_utils::progress::human_bytes;
+use zsh_utils::{display, glyphs, logger};
+
+#[derive(Parser)]
+#[command(
+    name = "claude-prune",
+    about = "Delete old Claude transcripts and file history by age or size",
+    after_help = zsh_utils::examples::after_help("claude-prune")
+)]
+struct Args {
+    /// Delete transcripts and file-history entries untouched for more
+    /// than this many days
+    #[arg(long, value_name = "DAYS")]
+    older_than: Option<u64>,
+
+    /// Keep only the newest entries up to this total size
+    /// (e.g. 2GB, 500MB)
+    #[arg(long, value_name = "SIZE")]
+    max_size: Option<String>,
+
+    /// Also delete tagged sessions (spared by default; see
+    /// claude-export --tag)
+    #[arg(long)]
+    prune_tagged: bool,
+
+    /// Report what would be deleted without touching anything
+    #[arg(short = 'n', long)]
+    dry_run: bool,
+
+    /// Stable tab-separated records on stdout for scripting (no
+    /// emoji, colors, or progress)
+    #[arg(long, global = true)]
+    porcelain: bool,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+    display::init_porcelain(args.porcelain);
+    if args.older_than.is_none() && args.max_size.is_none() {
+        anyhow::bail!("nothing to prune: pass --older-than and/or --max-size");
+    }
+    let policy = Policy {
+        max_age_days: args.older_than,
+        max_total_bytes: args.max_size.as_deref().map(parse_size).transpose()?,
+        keep_tagged: !args.prune_tagged,
+    };
+    let plan = prune::plan(&policy)?;
+
+    let verb = if args.dry_run { "would delete" } else { "deleting" };
+    for candidate in &plan.remove {
+        logger::info(format!(
+            "{verb} {} ({}, {}d old, {})",
+            display::path_link(&candidate.path),
+            human_bytes(candidate.bytes),
+            candidate.age_days,
+            candidate.reason
+        ));
+        if display::is_porcelain() {
+            display::porcelain(&[
+                if args.dry_run { "would-delete" } else { "delete" },
+                &candidate.path.display().to_string(),
+                &candidate.bytes.to_string(),
+                candidate.reason,
+            ]);
+        }
+    }
+    if plan.spared_tagged > 0 {
+        logger::info(format!(
+            "{} tagged sessions kept despite the policy",
+            plan.spared_tagged
+        ));
+    }
+    if args.dry_run {
+        logger::success(format!(
+            "dry run: would free {} across {} entries; keeping {} ({})",
+            human_bytes(plan.reclaimed_bytes()),
+            plan.remove.len(),
+            plan.kept,
+            human_bytes(plan.kept_bytes)
+        ));
+        return Ok(());
+    }
+    let freed = plan.apply()?;
+    logger::success(format!(
+        "freed {} across {} entries; keeping {} ({})",
+        human_bytes(freed),
+        plan.remove.len(),
+        plan.kept,
+        human_bytes(plan.kept_bytes)
+    ));
+    Ok(())
+}
+
+/// Accepts bare bytes or a KB/MB/GB suffix (decimal, case-insensitive).
+fn parse_size(raw: &str) -> Result<u64> {
+    let raw = raw.trim();
+    let (digits, multiplier) = match raw.to_ascii_uppercase() {
+        s if s.ends_with("GB") => (&raw[..raw.len() - 2], 1_000_000_000),
+        s if s.ends_with("MB") => (&raw[..raw.len() - 2], 1_000_000),
+        s if s.ends_with("KB") => (&raw[..raw.len() - 2], 1_000),
+        s if s.ends_with('B') => (&raw[..raw.len() - 1], 1),
+        _ => (raw, 1),
+    };
+    let value: u64 = digits
+        .trim()
+        .parse()
+        .with_context(|| format!("cannot parse size {raw:?}"))?;
+    Ok(value * multiplier)
+}
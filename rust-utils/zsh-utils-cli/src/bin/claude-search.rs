@@ -0,0 +1,119 @@
+//! Full-text search across every Claude Code transcript, via the
+//! SQLite index in `claude::index`.
+
+use anyhow::Result;
+use clap::Parser;
+
+use zsh_utils::claude::index::{Index, HIT_END, HIT_START};
+use zsh_utils::llm::LLMClient;
+use zsh_utils::{glyphs, logger, term};
+
+#[derive(Parser)]
+#[command(name = "claude-search", about = "Search Claude Code session transcripts")]
+struct Args {
+    /// FTS query; phrases and boolean operators work,
+    /// e.g. `"tokio panic" NOT test`
+    query: String,
+
+    /// Restrict to one project (friendly name)
+    #[arg(short = 'p', long)]
+    project: Option<String>,
+
+    /// Only sessions started on/after this date (YYYY-MM-DD)
+    #[arg(long)]
+    since: Option<String>,
+
+    /// Only sessions started on/before this date (YYYY-MM-DD)
+    #[arg(long)]
+    until: Option<String>,
+
+    /// Maximum number of hits
+    #[arg(short = 'n', long, default_value_t = 20)]
+    limit: usize,
+
+    /// Skip the incremental reindex before searching
+    #[arg(long)]
+    no_refresh: bool,
+
+    /// Rank by embedding similarity instead of keywords; needs
+    /// embedding_model in llm.toml and embeds new messages on the fly
+    #[arg(long)]
+    semantic: bool,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+
+    let mut index = Index::open()?;
+    if !args.no_refresh {
+        let stats = index.refresh()?;
+        if stats.indexed > 0 || stats.removed > 0 {
+            logger::step(format!(
+                "indexed {} sessions ({} removed)",
+                stats.indexed, stats.removed
+            ));
+        }
+    }
+
+    let hits = if args.semantic {
+        let client = LLMClient::from_config()?;
+        let embedded = index.embed_missing(&client)?;
+        if embedded > 0 {
+            logger::step(format!("embedded {embedded} new messages"));
+        }
+        index.semantic_search(&client, &args.query, args.project.as_deref(), args.limit)?
+    } else {
+        index.search(
+            &args.query,
+            args.project.as_deref(),
+            args.since.as_deref(),
+            args.until.as_deref(),
+            args.limit,
+        )?
+    };
+    if hits.is_empty() {
+        logger::info("no matches");
+        return Ok(());
+    }
+
+    for hit in &hits {
+        let date = hit
+            .start_time
+            .as_deref()
+            .map(|t| t.chars().take(10).collect::<String>())
+            .unwrap_or_else(|| "----------".to_string());
+        let id_short: String = hit.session_id.chars().take(8).collect();
+        println!(
+            "{}  {}  {}  {}: {}",
+            term::paint("2", &date),
+            term::paint("1;36", &hit.project),
+            term::paint("2", &id_short),
+            hit.role,
+            highlight(&hit.snippet),
+        );
+    }
+    logger::success(format!("{} hits", hits.len()));
+    Ok(())
+}
+
+/// Replaces the index's match markers with bold (or brackets when
+/// colors are off, so matches stay visible in pipes).
+fn highlight(snippet: &str) -> String {
+    let one_line: String = snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+    if term::colors_enabled() {
+        one_line
+            .replace(HIT_START, "\x1b[1;33m")
+            .replace(HIT_END, "\x1b[0m")
+    } else {
+        one_line.replace(HIT_START, "[").replace(HIT_END, "]")
+    }
+}
@@ -0,0 +1,39 @@
+//! Live dashboard over Claude Code token usage and estimated cost.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+
+use zsh_utils::claude::{pricing::Pricing, usage};
+use zsh_utils::glyphs;
+
+#[derive(Parser)]
+#[command(
+    name = "claude-usage",
+    about = "Watch today's and this week's Claude Code usage in real time",
+    after_help = zsh_utils::examples::after_help("claude-usage")
+)]
+struct Args {
+    /// TOML file overriding the built-in per-model pricing table
+    #[arg(long, value_name = "FILE")]
+    pricing_file: Option<PathBuf>,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+    let pricing = match &args.pricing_file {
+        Some(path) => Pricing::with_overrides(path)?,
+        None => Pricing::builtin(),
+    };
+    usage::run(pricing)
+}
@@ -0,0 +1,302 @@
+//! CSV/TSV viewer and query tool for the tiny files that do not
+//! deserve a spreadsheet.
+
+use std::io::Read;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+
+use zsh_utils::{glyphs, logger, term};
+
+#[derive(Parser)]
+#[command(name = "csv", about = "Pretty-print, filter, sort, and aggregate CSV/TSV")]
+struct Args {
+    /// Input file; stdin when omitted
+    file: Option<PathBuf>,
+
+    /// Input is tab-separated (auto-detected for .tsv files)
+    #[arg(short = 't', long)]
+    tsv: bool,
+
+    /// Comma-separated list of columns to show
+    #[arg(short = 's', long)]
+    select: Option<String>,
+
+    /// Row filter, e.g. 'amount > 100' or 'name contains foo'
+    #[arg(long = "where")]
+    filter: Option<String>,
+
+    /// Column to sort by (numeric when the column parses as numbers)
+    #[arg(long)]
+    sort: Option<String>,
+
+    /// Sort descending
+    #[arg(long)]
+    desc: bool,
+
+    /// Aggregation, e.g. 'sum:amount', 'avg:price', 'count'
+    #[arg(long)]
+    agg: Option<String>,
+
+    /// Emit rows as a JSON array of objects
+    #[arg(long)]
+    json: bool,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    fn column(&self, name: &str) -> Result<usize> {
+        self.headers
+            .iter()
+            .position(|h| h == name)
+            .with_context(|| format!("no column named {name:?}"))
+    }
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+
+    let tsv = args.tsv
+        || args
+            .file
+            .as_ref()
+            .and_then(|f| f.extension())
+            .is_some_and(|e| e == "tsv");
+    let mut table = read_table(args.file.as_ref(), tsv)?;
+
+    if let Some(filter) = &args.filter {
+        apply_filter(&mut table, filter)?;
+    }
+    if let Some(sort) = &args.sort {
+        sort_rows(&mut table, sort, args.desc)?;
+    }
+    if let Some(select) = &args.select {
+        select_columns(&mut table, select)?;
+    }
+
+    if let Some(agg) = &args.agg {
+        return aggregate(&table, agg);
+    }
+    if args.json {
+        return print_json(&table);
+    }
+    print_table(&table);
+    Ok(())
+}
+
+fn read_table(file: Option<&PathBuf>, tsv: bool) -> Result<Table> {
+    let raw = match file {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("reading {}", path.display()))?,
+        None => {
+            let mut buf = String::new();
+            std::io::stdin().read_to_string(&mut buf)?;
+            buf
+        }
+    };
+    let delimiter = if tsv { b'\t' } else { b',' };
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(raw.as_bytes());
+    let headers = reader
+        .headers()
+        .context("reading header row")?
+        .iter()
+        .map(str::to_string)
+        .collect();
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.context("reading row")?;
+        rows.push(record.iter().map(str::to_string).collect());
+    }
+    Ok(Table { headers, rows })
+}
+
+fn apply_filter(table: &mut Table, filter: &str) -> Result<()> {
+    let parts: Vec<&str> = filter.splitn(3, ' ').collect();
+    let [column, op, value] = parts[..] else {
+        bail!("filter must look like '<column> <op> <value>'");
+    };
+    let idx = table.column(column)?;
+    let keep = |cell: &str| -> bool {
+        match op {
+            "contains" => cell.contains(value),
+            "==" | "=" => cell == value,
+            "!=" => cell != value,
+            ">" | "<" | ">=" | "<=" => {
+                let (Ok(a), Ok(b)) = (cell.parse::<f64>(), value.parse::<f64>()) else {
+                    return false;
+                };
+                match op {
+                    ">" => a > b,
+                    "<" => a < b,
+                    ">=" => a >= b,
+                    _ => a <= b,
+                }
+            }
+            _ => false,
+        }
+    };
+    table.rows.retain(|row| row.get(idx).is_some_and(|c| keep(c)));
+    Ok(())
+}
+
+fn sort_rows(table: &mut Table, column: &str, desc: bool) -> Result<()> {
+    let idx = table.column(column)?;
+    let numeric = table
+        .rows
+        .iter()
+        .all(|r| r.get(idx).is_some_and(|c| c.parse::<f64>().is_ok()));
+    table.rows.sort_by(|a, b| {
+        let (a, b) = (a.get(idx), b.get(idx));
+        if numeric {
+            let a = a.and_then(|v| v.parse::<f64>().ok()).unwrap_or(f64::NAN);
+            let b = b.and_then(|v| v.parse::<f64>().ok()).unwrap_or(f64::NAN);
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        } else {
+            a.cmp(&b)
+        }
+    });
+    if desc {
+        table.rows.reverse();
+    }
+    Ok(())
+}
+
+fn select_columns(table: &mut Table, select: &str) -> Result<()> {
+    let indices: Vec<usize> = select
+        .split(',')
+        .map(|name| table.column(name.trim()))
+        .collect::<Result<_>>()?;
+    table.headers = indices
+        .iter()
+        .map(|&i| table.headers[i].clone())
+        .collect();
+    for row in &mut table.rows {
+        *row = indices
+            .iter()
+            .map(|&i| row.get(i).cloned().unwrap_or_default())
+            .collect();
+    }
+    Ok(())
+}
+
+fn aggregate(table: &Table, agg: &str) -> Result<()> {
+    if agg == "count" {
+        println!("{}", table.rows.len());
+        return Ok(());
+    }
+    let Some((op, column)) = agg.split_once(':') else {
+        bail!("aggregation must look like 'sum:<column>' (or 'count')");
+    };
+    let idx = table.column(column)?;
+    let values: Vec<f64> = table
+        .rows
+        .iter()
+        .filter_map(|r| r.get(idx).and_then(|c| c.parse().ok()))
+        .collect();
+    if values.is_empty() {
+        logger::warn(format!("column {column:?} has no numeric values"));
+        return Ok(());
+    }
+    let result = match op {
+        "sum" => values.iter().sum(),
+        "avg" => values.iter().sum::<f64>() / values.len() as f64,
+        "min" => values.iter().cloned().fold(f64::INFINITY, f64::min),
+        "max" => values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        other => bail!("unknown aggregation {other:?}"),
+    };
+    println!("{result}");
+    Ok(())
+}
+
+fn print_json(table: &Table) -> Result<()> {
+    let objects: Vec<serde_json::Value> = table
+        .rows
+        .iter()
+        .map(|row| {
+            table
+                .headers
+                .iter()
+                .zip(row)
+                .map(|(h, cell)| {
+                    // Numbers stay numbers in the JSON output.
+                    let value = cell
+                        .parse::<f64>()
+                        .map(|n| serde_json::json!(n))
+                        .unwrap_or_else(|_| serde_json::json!(cell));
+                    (h.clone(), value)
+                })
+                .collect::<serde_json::Map<_, _>>()
+                .into()
+        })
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&objects)?);
+    Ok(())
+}
+
+fn print_table(table: &Table) {
+    let mut widths: Vec<usize> = table.headers.iter().map(String::len).collect();
+    for row in &table.rows {
+        for (i, cell) in row.iter().enumerate() {
+            if i < widths.len() {
+                widths[i] = widths[i].max(cell.len());
+            }
+        }
+    }
+    // Keep the table inside the terminal by truncating the widest cells.
+    let max_width = (term::columns() as usize).saturating_sub(3 * widths.len()).max(20);
+    for w in &mut widths {
+        *w = (*w).min(max_width);
+    }
+
+    let sep = glyphs::pick("│", "|");
+    let header = table
+        .headers
+        .iter()
+        .enumerate()
+        .map(|(i, h)| term::paint("1", &format!("{:<w$}", truncate(h, widths[i]), w = widths[i])))
+        .collect::<Vec<_>>()
+        .join(&format!(" {sep} "));
+    println!("{header}");
+
+    for row in &table.rows {
+        let line = row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                let w = widths.get(i).copied().unwrap_or(cell.len());
+                if cell.parse::<f64>().is_ok() {
+                    term::paint("36", &format!("{:>w$}", truncate(cell, w)))
+                } else {
+                    format!("{:<w$}", truncate(cell, w))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(&format!(" {sep} "));
+        println!("{line}");
+    }
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.len() <= width {
+        text.to_string()
+    } else {
+        format!("{}…", &text[..width.saturating_sub(1)])
+    }
+}
@@ -0,0 +1,199 @@
+//! Decoding toolbox: base64, URL-encoding, hex, and JWTs.
+
+use std::io::Read;
+
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use clap::{Parser, Subcommand};
+
+use zsh_utils::{clipboard, glyphs, logger};
+
+#[derive(Parser)]
+#[command(name = "decode", about = "base64 / url / hex / jwt decoding toolbox")]
+struct Args {
+    #[command(subcommand)]
+    command: Cmd,
+
+    /// Read input from the clipboard instead of the argument/stdin
+    #[arg(long, global = true)]
+    paste: bool,
+
+    /// Copy the result to the clipboard as well
+    #[arg(long, global = true)]
+    copy: bool,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+    /// Decode base64 (standard or URL-safe, padding optional)
+    Base64 { input: Option<String> },
+    /// Decode percent-encoding
+    Url { input: Option<String> },
+    /// Decode a hex string to text
+    Hex { input: Option<String> },
+    /// Decode a JWT: pretty-print header and payload, highlight expiry
+    Jwt {
+        input: Option<String>,
+        /// HS256 key; when given, the signature is verified
+        #[arg(short = 'k', long)]
+        key: Option<String>,
+    },
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+
+    let output = match &args.command {
+        Cmd::Base64 { input } => decode_base64(&get_input(input, args.paste)?)?,
+        Cmd::Url { input } => decode_url(&get_input(input, args.paste)?),
+        Cmd::Hex { input } => decode_hex(&get_input(input, args.paste)?)?,
+        Cmd::Jwt { input, key } => decode_jwt(&get_input(input, args.paste)?, key.as_deref())?,
+    };
+
+    println!("{output}");
+    if args.copy {
+        clipboard::copy(&output)?;
+        logger::success("copied to clipboard");
+    }
+    Ok(())
+}
+
+fn get_input(arg: &Option<String>, paste: bool) -> Result<String> {
+    let raw = if paste {
+        clipboard::paste()?
+    } else if let Some(arg) = arg {
+        arg.clone()
+    } else {
+        let mut buf = String::new();
+        std::io::stdin().read_to_string(&mut buf)?;
+        buf
+    };
+    Ok(raw.trim().to_string())
+}
+
+fn decode_base64(input: &str) -> Result<String> {
+    let attempts = [
+        base64::engine::general_purpose::STANDARD,
+        base64::engine::general_purpose::STANDARD_NO_PAD,
+        base64::engine::general_purpose::URL_SAFE,
+        base64::engine::general_purpose::URL_SAFE_NO_PAD,
+    ];
+    for engine in attempts {
+        if let Ok(bytes) = engine.decode(input) {
+            return Ok(String::from_utf8_lossy(&bytes).into_owned());
+        }
+    }
+    bail!("input is not valid base64");
+}
+
+fn decode_url(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        out.push(byte);
+                        i += 3;
+                    }
+                    Err(_) => {
+                        out.push(bytes[i]);
+                        i += 1;
+                    }
+                }
+            }
+            b'+' => {
+                out.push(b' ');
+                i += 1;
+            }
+            other => {
+                out.push(other);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn decode_hex(input: &str) -> Result<String> {
+    let clean: String = input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ':')
+        .collect();
+    if !clean.len().is_multiple_of(2) {
+        bail!("hex input has odd length");
+    }
+    let bytes: Result<Vec<u8>, _> = (0..clean.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&clean[i..i + 2], 16))
+        .collect();
+    Ok(String::from_utf8_lossy(&bytes.context("invalid hex")?).into_owned())
+}
+
+fn decode_jwt(input: &str, key: Option<&str>) -> Result<String> {
+    let parts: Vec<&str> = input.split('.').collect();
+    if parts.len() != 3 {
+        bail!("not a JWT (expected three dot-separated parts)");
+    }
+    let decode_part = |part: &str| -> Result<serde_json::Value> {
+        let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(part)
+            .context("invalid base64url in JWT part")?;
+        serde_json::from_slice(&bytes).context("JWT part is not JSON")
+    };
+    let header = decode_part(parts[0])?;
+    let payload = decode_part(parts[1])?;
+
+    let mut out = String::new();
+    out.push_str("header:\n");
+    out.push_str(&serde_json::to_string_pretty(&header)?);
+    out.push_str("\npayload:\n");
+    out.push_str(&serde_json::to_string_pretty(&payload)?);
+    out.push('\n');
+
+    if let Some(exp) = payload.get("exp").and_then(|e| e.as_i64()) {
+        let when = DateTime::<Utc>::from_timestamp(exp, 0)
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_else(|| exp.to_string());
+        if Utc::now().timestamp() > exp {
+            out.push_str(&format!("{} expired at {when}\n", glyphs::pick("⛔", "[expired]")));
+        } else {
+            out.push_str(&format!("{} valid until {when}\n", glyphs::pick("⏳", "[exp]")));
+        }
+    }
+
+    match key {
+        None => out.push_str("signature: not verified (pass --key to verify HS256)\n"),
+        Some(key) => {
+            let alg = header.get("alg").and_then(|a| a.as_str()).unwrap_or("");
+            if alg != "HS256" {
+                bail!("can only verify HS256, token uses {alg:?}");
+            }
+            use hmac::{Hmac, Mac};
+            let mut mac = Hmac::<sha2::Sha256>::new_from_slice(key.as_bytes())
+                .context("building HMAC")?;
+            mac.update(format!("{}.{}", parts[0], parts[1]).as_bytes());
+            let expected = base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .decode(parts[2])
+                .context("invalid signature encoding")?;
+            match mac.verify_slice(&expected) {
+                Ok(()) => out.push_str("signature: valid (HS256)\n"),
+                Err(_) => out.push_str("signature: INVALID\n"),
+            }
+        }
+    }
+    Ok(out)
+}
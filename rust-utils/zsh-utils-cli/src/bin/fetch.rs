@@ -0,0 +1,231 @@
+//! Download manager: resume, parallel segments, checksums, and a queue
+//! file — the curl invocation I can never remember, with defaults I
+//! actually want.
+
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use sha2::Digest;
+
+use zsh_utils::progress::ProgressReporter;
+use zsh_utils::{display, glyphs, logger};
+
+#[derive(Parser)]
+#[command(name = "fetch", about = "Resumable, parallel downloads with verification")]
+struct Args {
+    /// URL to download (or use --queue)
+    url: Option<String>,
+
+    /// Output file (default: last path segment of the URL)
+    #[arg(short = 'o', long)]
+    output: Option<PathBuf>,
+
+    /// Number of parallel segments (when the server supports ranges)
+    #[arg(short = 'p', long, default_value_t = 4)]
+    parallel: usize,
+
+    /// Expected SHA-256 of the finished file
+    #[arg(long)]
+    sha256: Option<String>,
+
+    /// Queue file: one URL per line, optionally `<url> sha256=<hex>`
+    #[arg(short = 'q', long)]
+    queue: Option<PathBuf>,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+
+    if let Some(queue) = &args.queue {
+        return run_queue(queue, args.parallel);
+    }
+    let Some(url) = &args.url else {
+        bail!("give a URL or --queue <file>");
+    };
+    download(url, args.output.clone(), args.parallel, args.sha256.as_deref())
+}
+
+fn run_queue(queue: &Path, parallel: usize) -> Result<()> {
+    let raw = std::fs::read_to_string(queue)
+        .with_context(|| format!("reading {}", queue.display()))?;
+    for line in raw.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let url = parts.next().expect("non-empty line");
+        let sha256 = parts
+            .find_map(|p| p.strip_prefix("sha256="))
+            .map(str::to_string);
+        if let Err(err) = download(url, None, parallel, sha256.as_deref()) {
+            logger::error(format!("{url}: {err:#}"));
+        }
+    }
+    Ok(())
+}
+
+fn download(
+    url: &str,
+    output: Option<PathBuf>,
+    parallel: usize,
+    sha256: Option<&str>,
+) -> Result<()> {
+    let output = output.unwrap_or_else(|| {
+        let name = url.rsplit('/').next().unwrap_or("download");
+        let name = name.split('?').next().unwrap_or(name);
+        PathBuf::from(if name.is_empty() { "download" } else { name })
+    });
+    if output.exists() {
+        logger::info(format!("{} already exists, verifying", output.display()));
+        return verify(&output, sha256);
+    }
+
+    let client = reqwest::blocking::Client::new();
+    let head = client.head(url).send().context("HEAD request failed")?;
+    let total = head
+        .headers()
+        .get(reqwest::header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    let ranges_ok = head
+        .headers()
+        .get(reqwest::header::ACCEPT_RANGES)
+        .is_some_and(|v| v.to_str().unwrap_or("") == "bytes");
+
+    let part = output.with_extension(format!(
+        "{}part",
+        output
+            .extension()
+            .map(|e| format!("{}.", e.to_string_lossy()))
+            .unwrap_or_default()
+    ));
+    let progress = Arc::new(ProgressReporter::new(
+        output.display().to_string(),
+        total,
+    ));
+
+    match (total, ranges_ok) {
+        (Some(total), true) if parallel > 1 => {
+            segmented(&client, url, &part, total, parallel, &progress)?
+        }
+        _ => sequential(&client, url, &part, ranges_ok, &progress)?,
+    }
+    progress.finish();
+
+    verify(&part, sha256)?;
+    std::fs::rename(&part, &output)
+        .with_context(|| format!("moving into place at {}", output.display()))?;
+    logger::success(format!("downloaded {}", display::path_link(&output)));
+    Ok(())
+}
+
+/// Single-stream download; resumes from the existing `.part` bytes
+/// when the server honors ranges.
+fn sequential(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    part: &Path,
+    ranges_ok: bool,
+    progress: &ProgressReporter,
+) -> Result<()> {
+    let existing = part.metadata().map(|m| m.len()).unwrap_or(0);
+    let mut request = client.get(url);
+    let mut file;
+    if existing > 0 && ranges_ok {
+        progress.set_done(existing);
+        request = request.header(reqwest::header::RANGE, format!("bytes={existing}-"));
+        file = std::fs::OpenOptions::new().append(true).open(part)?;
+    } else {
+        file = std::fs::File::create(part)?;
+    }
+    let mut response = request.send()?.error_for_status()?;
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = response.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        file.write_all(&buf[..n])?;
+        progress.add(n as u64);
+    }
+    Ok(())
+}
+
+/// Range-request segments written into a preallocated file, one thread
+/// per segment.
+fn segmented(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    part: &Path,
+    total: u64,
+    parallel: usize,
+    progress: &Arc<ProgressReporter>,
+) -> Result<()> {
+    let file = std::fs::File::create(part)?;
+    file.set_len(total)?;
+    drop(file);
+
+    let segment = total / parallel as u64;
+    let mut handles = Vec::new();
+    for i in 0..parallel {
+        let start = i as u64 * segment;
+        let end = if i == parallel - 1 { total - 1 } else { (i as u64 + 1) * segment - 1 };
+        let client = client.clone();
+        let url = url.to_string();
+        let part = part.to_path_buf();
+        let progress = Arc::clone(progress);
+        handles.push(std::thread::spawn(move || -> Result<()> {
+            let mut response = client
+                .get(&url)
+                .header(reqwest::header::RANGE, format!("bytes={start}-{end}"))
+                .send()?
+                .error_for_status()?;
+            let mut file = std::fs::OpenOptions::new().write(true).open(&part)?;
+            file.seek(SeekFrom::Start(start))?;
+            let mut buf = [0u8; 64 * 1024];
+            loop {
+                let n = response.read(&mut buf)?;
+                if n == 0 {
+                    break;
+                }
+                file.write_all(&buf[..n])?;
+                progress.add(n as u64);
+            }
+            Ok(())
+        }));
+    }
+    for handle in handles {
+        handle
+            .join()
+            .map_err(|_| anyhow::anyhow!("segment thread panicked"))??;
+    }
+    Ok(())
+}
+
+fn verify(path: &Path, sha256: Option<&str>) -> Result<()> {
+    let Some(expected) = sha256 else {
+        return Ok(());
+    };
+    let mut hasher = sha2::Sha256::new();
+    let mut file = std::fs::File::open(path)?;
+    std::io::copy(&mut file, &mut hasher)?;
+    let actual = format!("{:x}", hasher.finalize());
+    if !actual.eq_ignore_ascii_case(expected) {
+        bail!("checksum mismatch: expected {expected}, got {actual}");
+    }
+    logger::success("checksum verified");
+    Ok(())
+}
@@ -0,0 +1,113 @@
+//! Interactive terminal chat against the configured LLM endpoint.
+
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+
+use zsh_utils::cache::CachedProvider;
+use zsh_utils::llm::mock::MockProvider;
+use zsh_utils::llm::{preset, ChatProvider, LLMClient, LLMConfig};
+use zsh_utils::{chat, logger};
+
+#[derive(Parser)]
+#[command(
+    name = "llm-chat",
+    about = "Chat with the configured LLM in a TUI",
+    after_help = zsh_utils::examples::after_help("llm-chat")
+)]
+struct Args {
+    /// Backend to talk to; `mock` needs no config or API key
+    #[arg(long, value_enum, default_value_t = Provider::Api)]
+    provider: Provider,
+
+    /// Screen-reader friendly mode: a line-oriented prompt/response
+    /// loop with no alternate screen, boxes, or colors
+    #[arg(long)]
+    plain: bool,
+
+    /// Ask for responses in this language (adds a standing "respond
+    /// in X" system instruction); /translate <lang> in the TUI
+    /// translates on demand instead
+    #[arg(long, value_name = "LANGUAGE")]
+    lang: Option<String>,
+
+    /// Ignore the project preset (.utils/llm.toml found walking up
+    /// from the current directory)
+    #[arg(long)]
+    no_preset: bool,
+
+    /// Skip the prompt cache even when llm.toml enables it
+    /// (cache_ttl_minutes)
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Provider {
+    /// The endpoint configured in llm.toml
+    Api,
+    /// Canned offline replies, for demos and testing
+    Mock,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    zsh_utils::glyphs::init(args.ascii);
+    // Inside a repo with .utils/llm.toml, "chat about this repo" is
+    // zero-flag: model override, stack-aware prompt, context files.
+    let project = if args.no_preset { None } else { preset::discover()? };
+    if let Some(project) = &project {
+        logger::info(format!(
+            "using project preset from {}",
+            project.root.join(".utils/llm.toml").display()
+        ));
+    }
+    let model_override = project.as_ref().and_then(|p| p.preset.model.clone());
+    let mut cache_ttl = None;
+    let provider: Box<dyn ChatProvider> = match args.provider {
+        Provider::Mock => Box::new(MockProvider::from_config()?),
+        Provider::Api => {
+            let config = LLMConfig::load().map(|mut config| {
+                if let Some(model) = model_override {
+                    config.model = model;
+                }
+                config
+            });
+            match config {
+                Ok(config) => {
+                    cache_ttl = config.cache_ttl_minutes;
+                    Box::new(LLMClient::new(config))
+                }
+                Err(err) => {
+                    logger::error(format!("{err:#}"));
+                    std::process::exit(1);
+                }
+            }
+        }
+    };
+    // The prompt cache is opt-in via llm.toml; --no-cache wins.
+    let cached;
+    let provider: &dyn ChatProvider = match cache_ttl {
+        Some(minutes) if !args.no_cache => {
+            cached = CachedProvider::new(
+                provider.as_ref(),
+                Some(std::time::Duration::from_secs(minutes * 60)),
+            );
+            &cached
+        }
+        _ => provider.as_ref(),
+    };
+    let preset_text = project.as_ref().map(|p| p.system_text()).transpose()?;
+    let system = chat::standing_system(preset_text.flatten(), args.lang.as_deref());
+    if args.plain {
+        return chat::run_plain(provider, system);
+    }
+    chat::run(provider, system)
+}
@@ -0,0 +1,130 @@
+//! Code review for staged changes: sends `git diff --staged` to the
+//! configured model and prints findings grouped by file.
+
+use std::collections::BTreeMap;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use zsh_utils::llm::chunk::chunk_diff;
+use zsh_utils::llm::{ChatMessage, LLMClient};
+use zsh_utils::{glyphs, logger};
+
+#[derive(Parser)]
+#[command(name = "llm-review", about = "LLM code review of staged changes")]
+struct Args {
+    /// Emit findings as a JSON array (for editor integration)
+    #[arg(long)]
+    json: bool,
+
+    /// Character budget per model request
+    #[arg(long, default_value_t = 12_000)]
+    chunk_size: usize,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+const SYSTEM_PROMPT: &str = "You are reviewing a staged git diff. Report only real \
+problems: bugs, security issues, broken edge cases, misleading names. Skip style \
+nits and praise. Reply with a JSON array of objects with keys \"file\", \"line\" \
+(number or null), \"severity\" (\"high\"|\"medium\"|\"low\") and \"message\". \
+Reply with [] when the diff is fine. Output only JSON.";
+
+#[derive(Serialize, Deserialize)]
+struct Finding {
+    file: String,
+    line: Option<u64>,
+    severity: String,
+    message: String,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+
+    let diff = staged_diff()?;
+    if diff.trim().is_empty() {
+        logger::info("nothing staged");
+        return Ok(());
+    }
+
+    let client = LLMClient::from_config()?;
+    let chunks = chunk_diff(&diff, args.chunk_size);
+    logger::step(format!(
+        "reviewing {} chunk(s) with {}",
+        chunks.len(),
+        client.model()
+    ));
+
+    let mut findings: Vec<Finding> = Vec::new();
+    for chunk in &chunks {
+        let reply = client.complete(&[
+            ChatMessage::system(SYSTEM_PROMPT),
+            ChatMessage::user(chunk.clone()),
+        ])?;
+        findings.extend(parse_findings(&reply));
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&findings)?);
+        return Ok(());
+    }
+
+    if findings.is_empty() {
+        logger::success("no findings");
+        return Ok(());
+    }
+    let mut by_file: BTreeMap<&str, Vec<&Finding>> = BTreeMap::new();
+    for finding in &findings {
+        by_file.entry(&finding.file).or_default().push(finding);
+    }
+    for (file, file_findings) in by_file {
+        println!("\n{file}");
+        for f in file_findings {
+            let badge = severity_badge(&f.severity);
+            match f.line {
+                Some(line) => println!("  {badge} L{line}: {}", f.message),
+                None => println!("  {badge} {}", f.message),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn staged_diff() -> Result<String> {
+    let out = Command::new("git")
+        .args(["diff", "--staged"])
+        .output()
+        .context("running git diff --staged")?;
+    if !out.status.success() {
+        anyhow::bail!("git diff --staged failed (not a git repo?)");
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+/// Models occasionally wrap the JSON in a code fence; strip it first.
+fn parse_findings(reply: &str) -> Vec<Finding> {
+    let trimmed = reply
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+    serde_json::from_str(trimmed).unwrap_or_default()
+}
+
+fn severity_badge(severity: &str) -> &'static str {
+    match severity {
+        "high" => glyphs::pick("🔴", "[high]"),
+        "medium" => glyphs::pick("🟡", "[med ]"),
+        _ => glyphs::pick("🔵", "[low ]"),
+    }
+}
@@ -0,0 +1,65 @@
+//! Render a Markdown file in the terminal, optionally re-rendering on
+//! change.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+
+use zsh_utils::{glyphs, logger, markdown, term};
+
+#[derive(Parser)]
+#[command(name = "md", about = "Render Markdown in the terminal")]
+struct Args {
+    file: PathBuf,
+
+    /// Re-render whenever the file changes
+    #[arg(short = 'w', long)]
+    watch: bool,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+
+    render(&args.file)?;
+    if !args.watch {
+        return Ok(());
+    }
+
+    logger::info("watching for changes (Ctrl+C to stop)");
+    let mut last = mtime(&args.file);
+    loop {
+        std::thread::sleep(Duration::from_millis(500));
+        let current = mtime(&args.file);
+        if current != last {
+            last = current;
+            // Clear and redraw from the top, like a pager would.
+            print!("\x1b[2J\x1b[H");
+            render(&args.file)?;
+        }
+    }
+}
+
+fn render(path: &PathBuf) -> Result<()> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("reading {}", path.display()))?;
+    let width = (term::columns() as usize).saturating_sub(2).max(20);
+    print!("{}", markdown::render_ansi(&raw, width));
+    Ok(())
+}
+
+fn mtime(path: &Path) -> SystemTime {
+    path.metadata()
+        .and_then(|m| m.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
@@ -0,0 +1,210 @@
+//! Process tree viewer: `ps aux | grep` roulette replaced with a tree
+//! you can filter and, in interactive mode, signal a whole subtree of.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+
+use zsh_utils::display::{TreeDisplay, TreeNode};
+use zsh_utils::{glyphs, logger};
+
+#[derive(Parser)]
+#[command(name = "ps-tree", about = "Render the process tree; filter and signal subtrees")]
+struct Args {
+    /// Only show subtrees whose command name contains this string
+    filter: Option<String>,
+
+    /// Interactive: pick a process and send a signal to its subtree
+    #[arg(short = 'i', long)]
+    interactive: bool,
+
+    /// Signal to send in interactive mode
+    #[arg(short = 's', long, default_value = "TERM")]
+    signal: String,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Process {
+    pid: u32,
+    ppid: u32,
+    cpu: f32,
+    rss_kb: u64,
+    name: String,
+}
+
+impl Process {
+    fn label(&self) -> String {
+        format!(
+            "{} [{}] {:.1}% {}",
+            self.name,
+            self.pid,
+            self.cpu,
+            human_rss(self.rss_kb)
+        )
+    }
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+
+    let processes = list_processes()?;
+    let children = child_index(&processes);
+    let by_pid: HashMap<u32, &Process> = processes.iter().map(|p| (p.pid, p)).collect();
+
+    let roots: Vec<u32> = processes
+        .iter()
+        .filter(|p| !by_pid.contains_key(&p.ppid) || p.ppid == 0)
+        .map(|p| p.pid)
+        .collect();
+
+    let filter = args.filter.as_deref();
+    let mut shown = Vec::new();
+    let mut nodes = Vec::new();
+    for root in roots {
+        if let Some(node) = build_node(root, &by_pid, &children, filter, &mut shown) {
+            nodes.push(node);
+        }
+    }
+    if nodes.is_empty() {
+        logger::info("no matching processes");
+        return Ok(());
+    }
+    TreeDisplay::new(TreeNode::branch("processes", nodes)).print();
+
+    if args.interactive {
+        interactive_kill(&shown, &children, &args.signal)?;
+    }
+    Ok(())
+}
+
+/// `ps -axo` works on both macOS and Linux with these column names.
+fn list_processes() -> Result<Vec<Process>> {
+    let out = Command::new("ps")
+        .args(["-axo", "pid=,ppid=,pcpu=,rss=,comm="])
+        .output()
+        .context("running ps")?;
+    let mut processes = Vec::new();
+    for line in String::from_utf8_lossy(&out.stdout).lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(pid), Some(ppid), Some(cpu), Some(rss)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let name = parts.collect::<Vec<_>>().join(" ");
+        let name = name.rsplit('/').next().unwrap_or(&name).to_string();
+        processes.push(Process {
+            pid: pid.parse().unwrap_or(0),
+            ppid: ppid.parse().unwrap_or(0),
+            cpu: cpu.parse().unwrap_or(0.0),
+            rss_kb: rss.parse().unwrap_or(0),
+            name,
+        });
+    }
+    Ok(processes)
+}
+
+fn child_index(processes: &[Process]) -> HashMap<u32, Vec<u32>> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for p in processes {
+        children.entry(p.ppid).or_default().push(p.pid);
+    }
+    children
+}
+
+/// Builds the display node for `pid`, pruning subtrees that contain no
+/// match for the filter. Matched processes are recorded (in display
+/// order) so interactive mode can refer to them by number.
+fn build_node(
+    pid: u32,
+    by_pid: &HashMap<u32, &Process>,
+    children: &HashMap<u32, Vec<u32>>,
+    filter: Option<&str>,
+    shown: &mut Vec<u32>,
+) -> Option<TreeNode> {
+    let process = by_pid.get(&pid)?;
+    let self_matches = filter.is_none_or(|f| process.name.contains(f));
+
+    let child_nodes: Vec<TreeNode> = children
+        .get(&pid)
+        .into_iter()
+        .flatten()
+        // Once a node matches, show its whole subtree.
+        .filter_map(|c| {
+            build_node(*c, by_pid, children, if self_matches { None } else { filter }, shown)
+        })
+        .collect();
+
+    if !self_matches && child_nodes.is_empty() {
+        return None;
+    }
+    shown.push(pid);
+    let label = if self_matches && filter.is_some() {
+        format!("{}. {}", shown.len(), process.label())
+    } else {
+        process.label()
+    };
+    Some(TreeNode::branch(label, child_nodes))
+}
+
+fn interactive_kill(
+    shown: &[u32],
+    children: &HashMap<u32, Vec<u32>>,
+    signal: &str,
+) -> Result<()> {
+    print!("pid to signal (SIG{signal} to its subtree, empty to abort): ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    let Ok(pid) = answer.trim().parse::<u32>() else {
+        logger::info("aborted");
+        return Ok(());
+    };
+    if !shown.contains(&pid) {
+        anyhow::bail!("pid {pid} is not in the displayed tree");
+    }
+
+    let mut targets = vec![pid];
+    let mut queue = vec![pid];
+    while let Some(next) = queue.pop() {
+        for child in children.get(&next).into_iter().flatten() {
+            targets.push(*child);
+            queue.push(*child);
+        }
+    }
+    // Children first so parents cannot respawn them mid-kill.
+    for target in targets.iter().rev() {
+        let status = Command::new("kill")
+            .args(["-s", signal, &target.to_string()])
+            .status()?;
+        if status.success() {
+            logger::info(format!("sent SIG{signal} to {target}"));
+        } else {
+            logger::warn(format!("could not signal {target}"));
+        }
+    }
+    logger::success(format!("signalled {} processes", targets.len()));
+    Ok(())
+}
+
+fn human_rss(kb: u64) -> String {
+    if kb >= 1024 * 1024 {
+        format!("{:.1}G", kb as f64 / (1024.0 * 1024.0))
+    } else if kb >= 1024 {
+        format!("{:.0}M", kb as f64 / 1024.0)
+    } else {
+        format!("{kb}K")
+    }
+}
@@ -0,0 +1,270 @@
+//! Regex playground: test patterns against input, explain them, or
+//! edit them live in a small TUI.
+
+use std::io::Read;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Terminal;
+use regex::Regex;
+
+use zsh_utils::{glyphs, logger, term};
+
+#[derive(Parser)]
+#[command(name = "regex", about = "Test, explain, and live-edit regular expressions")]
+struct Args {
+    #[command(subcommand)]
+    command: Cmd,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+    /// Highlight matches and capture groups in the input
+    Test {
+        pattern: String,
+        /// Input file; stdin when omitted
+        file: Option<PathBuf>,
+    },
+    /// Break the pattern into annotated parts
+    Explain { pattern: String },
+    /// Live-update matches while editing the pattern
+    Tui {
+        /// Sample input file; stdin when omitted
+        file: Option<PathBuf>,
+    },
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+    match args.command {
+        Cmd::Test { pattern, file } => test(&pattern, file),
+        Cmd::Explain { pattern } => explain(&pattern),
+        Cmd::Tui { file } => tui(file),
+    }
+}
+
+fn read_input(file: Option<PathBuf>) -> Result<String> {
+    match file {
+        Some(path) => std::fs::read_to_string(&path)
+            .with_context(|| format!("reading {}", path.display())),
+        None => {
+            let mut buf = String::new();
+            std::io::stdin().read_to_string(&mut buf)?;
+            Ok(buf)
+        }
+    }
+}
+
+/// Group highlight colors, cycled per capture group index.
+const GROUP_COLORS: &[&str] = &["\x1b[33m", "\x1b[35m", "\x1b[36m", "\x1b[32m"];
+const MATCH_COLOR: &str = "\x1b[1;31m";
+const RESET: &str = "\x1b[0m";
+
+fn test(pattern: &str, file: Option<PathBuf>) -> Result<()> {
+    let re = Regex::new(pattern).context("invalid pattern")?;
+    let input = read_input(file)?;
+    let mut matched_lines = 0;
+    for line in input.lines() {
+        if !re.is_match(line) {
+            continue;
+        }
+        matched_lines += 1;
+        println!("{}", highlight_line(&re, line));
+    }
+    if matched_lines == 0 {
+        logger::info("no matches");
+    }
+    Ok(())
+}
+
+fn highlight_line(re: &Regex, line: &str) -> String {
+    if !term::colors_enabled() {
+        return line.to_string();
+    }
+    let mut out = String::new();
+    let mut cursor = 0;
+    for caps in re.captures_iter(line) {
+        let whole = caps.get(0).expect("group 0 always present");
+        out.push_str(&line[cursor..whole.start()]);
+        // Paint the whole match, then overlay capture groups on top.
+        let mut painted = format!("{MATCH_COLOR}{}{RESET}", whole.as_str());
+        for (i, group) in caps.iter().enumerate().skip(1) {
+            if let Some(group) = group {
+                let color = GROUP_COLORS[(i - 1) % GROUP_COLORS.len()];
+                painted = painted.replacen(
+                    group.as_str(),
+                    &format!("{color}{}{MATCH_COLOR}", group.as_str()),
+                    1,
+                );
+            }
+        }
+        out.push_str(&painted);
+        cursor = whole.end();
+    }
+    out.push_str(&line[cursor..]);
+    out
+}
+
+fn explain(pattern: &str) -> Result<()> {
+    // Validate first so the annotation below only runs on real patterns.
+    Regex::new(pattern).context("invalid pattern")?;
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        let (token, note): (String, String) = match c {
+            '^' => ("^".into(), "start of line".into()),
+            '$' => ("$".into(), "end of line".into()),
+            '.' => (".".into(), "any character".into()),
+            '*' => ("*".into(), "previous, zero or more times".into()),
+            '+' => ("+".into(), "previous, one or more times".into()),
+            '?' => ("?".into(), "previous, optional (or lazy modifier)".into()),
+            '|' => ("|".into(), "alternation: match either side".into()),
+            '(' => {
+                if chars.peek() == Some(&'?') {
+                    ("(?".into(), "non-capturing or special group opens".into())
+                } else {
+                    ("(".into(), "capture group opens".into())
+                }
+            }
+            ')' => (")".into(), "group closes".into()),
+            '[' => {
+                let mut class = String::from("[");
+                for c in chars.by_ref() {
+                    class.push(c);
+                    if c == ']' {
+                        break;
+                    }
+                }
+                let note = if class.starts_with("[^") {
+                    "character class: any character NOT listed"
+                } else {
+                    "character class: any one listed character"
+                };
+                (class, note.into())
+            }
+            '{' => {
+                let mut rep = String::from("{");
+                for c in chars.by_ref() {
+                    rep.push(c);
+                    if c == '}' {
+                        break;
+                    }
+                }
+                (rep, "previous, repeated a counted number of times".into())
+            }
+            '\\' => {
+                let next = chars.next().unwrap_or('\\');
+                let note = match next {
+                    'd' => "any digit",
+                    'D' => "any non-digit",
+                    'w' => "word character (letters, digits, _)",
+                    'W' => "non-word character",
+                    's' => "whitespace",
+                    'S' => "non-whitespace",
+                    'b' => "word boundary",
+                    _ => "literal (escaped) character",
+                };
+                (format!("\\{next}"), note.into())
+            }
+            other => (other.to_string(), "literal character".into()),
+        };
+        println!("  {token:<8} {note}");
+    }
+    Ok(())
+}
+
+fn tui(file: Option<PathBuf>) -> Result<()> {
+    let input = read_input(file)?;
+    crossterm::terminal::enable_raw_mode()?;
+    let mut stdout = std::io::stdout();
+    crossterm::execute!(stdout, crossterm::terminal::EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let mut pattern = String::new();
+    let result = loop {
+        let compiled = Regex::new(&pattern);
+        terminal.draw(|frame| {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(3), Constraint::Min(1)])
+                .split(frame.size());
+
+            let status = match &compiled {
+                Ok(re) => format!(" pattern — {} matching lines ", count_lines(re, &input)),
+                Err(_) => " pattern — invalid ".to_string(),
+            };
+            let editor = Paragraph::new(pattern.as_str())
+                .block(Block::default().borders(Borders::ALL).title(status));
+            frame.render_widget(editor, chunks[0]);
+
+            let lines: Vec<Line> = input
+                .lines()
+                .take(chunks[1].height as usize)
+                .map(|l| render_tui_line(&compiled, l))
+                .collect();
+            let sample = Paragraph::new(lines)
+                .block(Block::default().borders(Borders::ALL).title(" input "));
+            frame.render_widget(sample, chunks[1]);
+        })?;
+
+        if let Event::Key(key) = event::read()? {
+            match (key.code, key.modifiers) {
+                (KeyCode::Esc, _) | (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
+                    break Ok(())
+                }
+                (KeyCode::Backspace, _) => {
+                    pattern.pop();
+                }
+                (KeyCode::Char(c), _) => pattern.push(c),
+                _ => {}
+            }
+        }
+    };
+
+    crossterm::terminal::disable_raw_mode()?;
+    crossterm::execute!(
+        terminal.backend_mut(),
+        crossterm::terminal::LeaveAlternateScreen
+    )?;
+    result
+}
+
+fn count_lines(re: &Regex, input: &str) -> usize {
+    input.lines().filter(|l| re.is_match(l)).count()
+}
+
+fn render_tui_line<'a>(
+    compiled: &Result<Regex, regex::Error>,
+    line: &'a str,
+) -> Line<'a> {
+    let Ok(re) = compiled else {
+        return Line::from(line);
+    };
+    let mut spans = Vec::new();
+    let mut cursor = 0;
+    for m in re.find_iter(line) {
+        spans.push(Span::raw(&line[cursor..m.start()]));
+        spans.push(Span::styled(
+            m.as_str(),
+            term::themed(Style::default().fg(Color::Black).bg(Color::Yellow)),
+        ));
+        cursor = m.end();
+    }
+    spans.push(Span::raw(&line[cursor..]));
+    Line::from(spans)
+}
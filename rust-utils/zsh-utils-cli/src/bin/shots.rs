@@ -0,0 +1,184 @@
+//! Screenshot organizer: sweep the Desktop into a dated folder tree,
+//! optionally naming files after their content.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use clap::Parser;
+
+use zsh_utils::llm::{ChatMessage, LLMClient};
+use zsh_utils::{glyphs, logger};
+
+#[derive(Parser)]
+#[command(name = "shots", about = "Organize screenshots into dated folders")]
+struct Args {
+    /// Directory to sweep (default: ~/Desktop)
+    #[arg(long)]
+    from: Option<PathBuf>,
+
+    /// Destination root (default: ~/Pictures/Screenshots)
+    #[arg(long)]
+    to: Option<PathBuf>,
+
+    /// Derive a content slug via OCR (tesseract) and the LLM
+    #[arg(short = 's', long)]
+    slug: bool,
+
+    /// Apply without the interactive confirmation
+    #[arg(short = 'y', long)]
+    yes: bool,
+
+    /// Keep running and sweep every few seconds
+    #[arg(short = 'w', long)]
+    watch: bool,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+
+    let home = dirs::home_dir().context("no home directory")?;
+    let from = args.from.clone().unwrap_or_else(|| home.join("Desktop"));
+    let to = args
+        .to
+        .clone()
+        .unwrap_or_else(|| home.join("Pictures").join("Screenshots"));
+
+    if !args.watch {
+        return sweep(&from, &to, args.slug, args.yes);
+    }
+    logger::info("watching for screenshots (Ctrl+C to stop)");
+    loop {
+        // Watch mode implies non-interactive.
+        if let Err(err) = sweep(&from, &to, args.slug, true) {
+            logger::warn(format!("{err:#}"));
+        }
+        std::thread::sleep(Duration::from_secs(5));
+    }
+}
+
+fn is_screenshot(name: &str) -> bool {
+    (name.starts_with("Screenshot") || name.starts_with("Screen Shot"))
+        && (name.ends_with(".png") || name.ends_with(".jpg"))
+}
+
+fn sweep(from: &Path, to: &Path, slug: bool, yes: bool) -> Result<()> {
+    let mut moves: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for entry in std::fs::read_dir(from)
+        .with_context(|| format!("reading {}", from.display()))?
+    {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if !path.is_file() || !is_screenshot(&name) {
+            continue;
+        }
+        let taken: DateTime<Local> = path
+            .metadata()
+            .and_then(|m| m.modified())
+            .map(DateTime::from)
+            .unwrap_or_else(|_| Local::now());
+        let ext = path
+            .extension()
+            .map(|e| e.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "png".into());
+        let mut stem = taken.format("%Y-%m-%d-%H.%M.%S").to_string();
+        if slug {
+            if let Some(content_slug) = derive_slug(&path) {
+                stem.push('-');
+                stem.push_str(&content_slug);
+            }
+        }
+        let dir = to.join(taken.format("%Y").to_string()).join(taken.format("%m").to_string());
+        let mut dest = dir.join(format!("{stem}.{ext}"));
+        let mut counter = 1;
+        while dest.exists() {
+            dest = dir.join(format!("{stem}-{counter}.{ext}"));
+            counter += 1;
+        }
+        moves.push((path, dest));
+    }
+
+    if moves.is_empty() {
+        if !yes {
+            logger::info("no screenshots found");
+        }
+        return Ok(());
+    }
+
+    let arrow = glyphs::pick("→", "->");
+    for (src, dest) in &moves {
+        println!(
+            "  {} {arrow} {}",
+            src.file_name().unwrap_or_default().to_string_lossy(),
+            dest.strip_prefix(to).unwrap_or(dest).display()
+        );
+    }
+    if !yes && !confirm(moves.len())? {
+        logger::info("aborted");
+        return Ok(());
+    }
+    for (src, dest) in &moves {
+        if let Some(dir) = dest.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::rename(src, dest)
+            .with_context(|| format!("moving {}", src.display()))?;
+    }
+    logger::success(format!("organized {} screenshots", moves.len()));
+    Ok(())
+}
+
+/// OCR the image and boil the text down to a short slug. Both steps
+/// are best-effort: no tesseract or no LLM config just means no slug.
+fn derive_slug(path: &Path) -> Option<String> {
+    let out = Command::new("tesseract")
+        .arg(path)
+        .arg("stdout")
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&out.stdout);
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let client = LLMClient::from_config().ok()?;
+    let reply = client
+        .complete(&[
+            ChatMessage::system(
+                "Reply with a 2-4 word lowercase-dash slug describing this \
+                 screenshot text. Only the slug.",
+            ),
+            ChatMessage::user(text.chars().take(1000).collect::<String>()),
+        ])
+        .ok()?;
+    let slug: String = reply
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let slug = slug.trim_matches('-').to_string();
+    (!slug.is_empty()).then_some(slug)
+}
+
+fn confirm(count: usize) -> Result<bool> {
+    print!("move {count} files? [y/N] ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
@@ -0,0 +1,44 @@
+//! Prints copy-pasteable example invocations for the other tools
+//! (see `zsh_utils::examples`).
+
+use anyhow::Result;
+use clap::Parser;
+
+use zsh_utils::{examples, glyphs};
+
+#[derive(Parser)]
+#[command(
+    name = "show-examples",
+    about = "Show example invocations for a command"
+)]
+struct Args {
+    /// Command to show; omit to list the commands that have examples
+    command: Option<String>,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+    let Some(command) = &args.command else {
+        println!("commands with examples:");
+        for name in examples::commands() {
+            println!("  {name}");
+        }
+        return Ok(());
+    };
+    match examples::lookup(command) {
+        Some(block) => println!("{block}"),
+        None => anyhow::bail!(
+            "no examples for {command:?}; run `show-examples` to list commands"
+        ),
+    }
+    Ok(())
+}
@@ -0,0 +1,161 @@
+//! Collects your recent commits across repos and optionally turns them
+//! into a three-bullet standup summary.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use serde::Deserialize;
+
+use zsh_utils::llm::{ChatMessage, LLMClient};
+use zsh_utils::{clipboard, glyphs, logger};
+
+#[derive(Parser)]
+#[command(name = "standup", about = "Summarize your recent commits across repos")]
+struct Args {
+    /// How many days back to look
+    #[arg(short = 'd', long, default_value_t = 1)]
+    days: u32,
+
+    /// Ask the LLM for a three-bullet summary instead of the raw list
+    #[arg(short = 's', long)]
+    summarize: bool,
+
+    /// Copy the output to the clipboard as well
+    #[arg(short = 'c', long)]
+    copy: bool,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+/// `$ZSH_CONFIG/standup.toml`:
+///
+/// ```toml
+/// repos = ["~/code/zshrc", "~/code/work-api"]
+/// author = "fameoflight@gmail.com"  # optional, defaults to git config
+/// ```
+#[derive(Deserialize)]
+struct StandupConfig {
+    repos: Vec<String>,
+    author: Option<String>,
+}
+
+impl StandupConfig {
+    fn load() -> Result<Self> {
+        let config_dir = std::env::var("ZSH_CONFIG")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| dirs::config_dir().unwrap_or_default().join("zsh"));
+        let path = config_dir.join("standup.toml");
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        toml::from_str(&raw).context("parsing standup.toml")
+    }
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+
+    let config = StandupConfig::load()?;
+    let author = match config.author {
+        Some(author) => author,
+        None => git_config_email()?,
+    };
+
+    let mut sections = Vec::new();
+    for repo in &config.repos {
+        let path = expand_tilde(repo);
+        if !path.join(".git").exists() {
+            logger::warn(format!("skipping {repo}: not a git repo"));
+            continue;
+        }
+        let commits = recent_commits(&path, &author, args.days)?;
+        if !commits.is_empty() {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| repo.clone());
+            sections.push((name, commits));
+        }
+    }
+
+    if sections.is_empty() {
+        logger::info(format!("no commits in the last {} day(s)", args.days));
+        return Ok(());
+    }
+
+    let raw = sections
+        .iter()
+        .map(|(name, commits)| format!("{name}:\n{}", commits.join("\n")))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let output = if args.summarize {
+        let client = LLMClient::from_config()?;
+        logger::step(format!("summarizing with {}", client.model()));
+        client.complete(&[
+            ChatMessage::system(
+                "Summarize these commits as exactly three short standup bullets \
+                 starting with '- '. Group related work, no commit hashes.",
+            ),
+            ChatMessage::user(raw),
+        ])?
+    } else {
+        raw
+    };
+
+    println!("{output}");
+    if args.copy {
+        clipboard::copy(&output)?;
+        logger::success("copied to clipboard");
+    }
+    Ok(())
+}
+
+fn git_config_email() -> Result<String> {
+    let out = Command::new("git")
+        .args(["config", "user.email"])
+        .output()
+        .context("running git config")?;
+    let email = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    if email.is_empty() {
+        anyhow::bail!("no author configured and git config user.email is empty");
+    }
+    Ok(email)
+}
+
+fn recent_commits(repo: &PathBuf, author: &str, days: u32) -> Result<Vec<String>> {
+    let out = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args([
+            "log",
+            "--no-merges",
+            "--pretty=format:- %s",
+            &format!("--author={author}"),
+            &format!("--since={days} days ago"),
+        ])
+        .output()
+        .with_context(|| format!("running git log in {}", repo.display()))?;
+    Ok(String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+fn expand_tilde(path: &str) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest);
+        }
+    }
+    PathBuf::from(path)
+}
+
@@ -0,0 +1,212 @@
+//! Minimal time tracking keyed by project, where "project" is the git
+//! repo (or directory) you are standing in.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, Utc};
+use clap::{Parser, Subcommand};
+use serde::{Deserialize, Serialize};
+
+use zsh_utils::{glyphs, logger};
+
+#[derive(Parser)]
+#[command(name = "track", about = "Track time per project from the shell")]
+struct Args {
+    #[command(subcommand)]
+    command: Cmd,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+    /// Start tracking (project inferred from the git repo / cwd)
+    Start {
+        /// Override the inferred project name
+        project: Option<String>,
+        /// Stay silent; used by the shell hook
+        #[arg(short = 'q', long)]
+        quiet: bool,
+    },
+    /// Stop the running interval
+    Stop,
+    /// Show what is currently being tracked
+    Status,
+    /// Per-project totals for the last N weeks
+    Report {
+        #[arg(short = 'w', long, default_value_t = 1)]
+        weeks: i64,
+        /// Emit CSV instead of an aligned table
+        #[arg(long)]
+        csv: bool,
+    },
+    /// Print the zsh hook snippet that auto-switches tracking
+    Hook,
+}
+
+/// One tracked interval; `end` is None while it is running.
+#[derive(Serialize, Deserialize)]
+struct Interval {
+    project: String,
+    start: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+    match args.command {
+        Cmd::Start { project, quiet } => start(project, quiet),
+        Cmd::Stop => stop(),
+        Cmd::Status => status(),
+        Cmd::Report { weeks, csv } => report(weeks, csv),
+        Cmd::Hook => hook(),
+    }
+}
+
+fn data_file() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_default()
+        .join("zsh-utils")
+        .join("track.jsonl")
+}
+
+fn load() -> Result<Vec<Interval>> {
+    let path = data_file();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = std::fs::read_to_string(&path)?;
+    Ok(raw
+        .lines()
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect())
+}
+
+fn save(intervals: &[Interval]) -> Result<()> {
+    let path = data_file();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut out = String::new();
+    for interval in intervals {
+        out.push_str(&serde_json::to_string(interval)?);
+        out.push('\n');
+    }
+    std::fs::write(&path, out).with_context(|| format!("writing {}", path.display()))
+}
+
+/// Project name: git toplevel directory name, else cwd directory name.
+fn infer_project() -> String {
+    let toplevel = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+    let dir = toplevel
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default();
+    dir.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+fn start(project: Option<String>, quiet: bool) -> Result<()> {
+    let project = project.unwrap_or_else(infer_project);
+    let mut intervals = load()?;
+    if let Some(open) = intervals.iter_mut().find(|i| i.end.is_none()) {
+        if open.project == project {
+            // The shell hook fires on every directory change; staying in
+            // the same project is a no-op, not a new interval.
+            return Ok(());
+        }
+        open.end = Some(Utc::now());
+    }
+    intervals.push(Interval { project: project.clone(), start: Utc::now(), end: None });
+    save(&intervals)?;
+    if !quiet {
+        logger::success(format!("tracking {project}"));
+    }
+    Ok(())
+}
+
+fn stop() -> Result<()> {
+    let mut intervals = load()?;
+    let Some(open) = intervals.iter_mut().find(|i| i.end.is_none()) else {
+        logger::info("nothing is being tracked");
+        return Ok(());
+    };
+    open.end = Some(Utc::now());
+    let minutes = (open.end.unwrap() - open.start).num_minutes();
+    let project = open.project.clone();
+    save(&intervals)?;
+    logger::success(format!("stopped {project} after {minutes}m"));
+    Ok(())
+}
+
+fn status() -> Result<()> {
+    let intervals = load()?;
+    match intervals.iter().find(|i| i.end.is_none()) {
+        Some(open) => {
+            let minutes = (Utc::now() - open.start).num_minutes();
+            logger::info(format!("{} for {minutes}m", open.project));
+        }
+        None => logger::info("nothing is being tracked"),
+    }
+    Ok(())
+}
+
+fn report(weeks: i64, csv: bool) -> Result<()> {
+    let since = Utc::now() - Duration::weeks(weeks);
+    let intervals = load()?;
+    let mut totals: std::collections::BTreeMap<String, i64> = Default::default();
+    for interval in &intervals {
+        let end = interval.end.unwrap_or_else(Utc::now);
+        if end < since {
+            continue;
+        }
+        let start = interval.start.max(since);
+        *totals.entry(interval.project.clone()).or_default() +=
+            (end - start).num_minutes();
+    }
+    if totals.is_empty() {
+        logger::info("no tracked time in range");
+        return Ok(());
+    }
+    if csv {
+        println!("project,minutes");
+        for (project, minutes) in &totals {
+            println!("{project},{minutes}");
+        }
+        return Ok(());
+    }
+    let width = totals.keys().map(String::len).max().unwrap_or(0);
+    println!(
+        "since {}",
+        since.with_timezone(&Local).format("%Y-%m-%d")
+    );
+    for (project, minutes) in &totals {
+        println!("  {project:width$}  {:>3}h {:02}m", minutes / 60, minutes % 60);
+    }
+    Ok(())
+}
+
+fn hook() -> Result<()> {
+    println!(
+        "# add to your zshrc: auto-switch tracking on directory change\n\
+         _track_chpwd() {{ track start --quiet }}\n\
+         autoload -U add-zsh-hook\n\
+         add-zsh-hook chpwd _track_chpwd"
+    );
+    Ok(())
+}
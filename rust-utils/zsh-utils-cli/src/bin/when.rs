@@ -0,0 +1,211 @@
+//! Countdown to named dates: releases, renewals, birthdays.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, Local, NaiveDate};
+use clap::{Parser, Subcommand};
+use serde::{Deserialize, Serialize};
+
+use zsh_utils::{glyphs, logger};
+
+#[derive(Parser)]
+#[command(name = "when", about = "Track named dates and days remaining")]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Cmd>,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+    /// Add a date: `when add "rust release" 2026-09-18`
+    Add {
+        name: String,
+        /// YYYY-MM-DD
+        date: NaiveDate,
+        /// Recurs every year (birthdays, anniversaries)
+        #[arg(short = 'a', long)]
+        annual: bool,
+    },
+    /// Remove a date by name
+    Remove { name: String },
+    /// List everything (default)
+    List,
+    /// Only dates within the window, e.g. `--within 30d`; made for the
+    /// shell greeting
+    Upcoming {
+        #[arg(short = 'w', long, default_value = "30d")]
+        within: String,
+    },
+    /// Import events from an ICS calendar file
+    Import { file: PathBuf },
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    name: String,
+    date: NaiveDate,
+    #[serde(default)]
+    annual: bool,
+}
+
+impl Entry {
+    /// Days until the (next) occurrence; negative for past one-shots.
+    fn days_left(&self, today: NaiveDate) -> i64 {
+        if !self.annual {
+            return (self.date - today).num_days();
+        }
+        let mut next = self.date.with_year(today.year()).unwrap_or(self.date);
+        if next < today {
+            next = next.with_year(today.year() + 1).unwrap_or(next);
+        }
+        (next - today).num_days()
+    }
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+    match args.command.unwrap_or(Cmd::List) {
+        Cmd::Add { name, date, annual } => add(name, date, annual),
+        Cmd::Remove { name } => remove(&name),
+        Cmd::List => list(None),
+        Cmd::Upcoming { within } => list(Some(parse_window(&within)?)),
+        Cmd::Import { file } => import(&file),
+    }
+}
+
+fn data_file() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_default()
+        .join("zsh-utils")
+        .join("when.json")
+}
+
+fn load() -> Result<Vec<Entry>> {
+    let path = data_file();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = std::fs::read_to_string(&path)?;
+    serde_json::from_str(&raw).context("parsing when.json")
+}
+
+fn save(entries: &[Entry]) -> Result<()> {
+    let path = data_file();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(entries)?)
+        .with_context(|| format!("writing {}", path.display()))
+}
+
+fn add(name: String, date: NaiveDate, annual: bool) -> Result<()> {
+    let mut entries = load()?;
+    if entries.iter().any(|e| e.name == name) {
+        bail!("{name:?} already exists (remove it first)");
+    }
+    entries.push(Entry { name: name.clone(), date, annual });
+    save(&entries)?;
+    logger::success(format!("added {name}"));
+    Ok(())
+}
+
+fn remove(name: &str) -> Result<()> {
+    let mut entries = load()?;
+    let before = entries.len();
+    entries.retain(|e| e.name != name);
+    if entries.len() == before {
+        bail!("no entry named {name:?}");
+    }
+    save(&entries)?;
+    logger::success(format!("removed {name}"));
+    Ok(())
+}
+
+fn list(within_days: Option<i64>) -> Result<()> {
+    let mut entries = load()?;
+    if entries.is_empty() {
+        logger::info("no dates stored; try `when add`");
+        return Ok(());
+    }
+    let today = Local::now().date_naive();
+    entries.sort_by_key(|e| e.days_left(today));
+    let width = entries.iter().map(|e| e.name.len()).max().unwrap_or(0);
+    for entry in &entries {
+        let days = entry.days_left(today);
+        if let Some(limit) = within_days {
+            if days < 0 || days > limit {
+                continue;
+            }
+        }
+        let mark = if entry.annual { glyphs::pick("🎂", "(annual)") } else { "" };
+        let status = match days {
+            0 => "today".to_string(),
+            d if d < 0 => format!("{} days ago", -d),
+            d => format!("in {d} days"),
+        };
+        println!("  {:width$}  {status:>12}  {mark}", entry.name);
+    }
+    Ok(())
+}
+
+/// `30d`, `6w`, or a bare number of days.
+fn parse_window(within: &str) -> Result<i64> {
+    let (digits, unit) = within.split_at(
+        within
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(within.len()),
+    );
+    let n: i64 = digits.parse().with_context(|| format!("bad window {within:?}"))?;
+    Ok(match unit {
+        "" | "d" => n,
+        "w" => n * 7,
+        "m" => n * 30,
+        other => bail!("unknown window unit {other:?}"),
+    })
+}
+
+/// Minimal VEVENT import: SUMMARY plus DTSTART, yearly RRULEs marked
+/// annual. Enough for the birthday calendars people actually export.
+fn import(file: &PathBuf) -> Result<()> {
+    let raw = std::fs::read_to_string(file)
+        .with_context(|| format!("reading {}", file.display()))?;
+    let mut entries = load()?;
+    let mut name: Option<String> = None;
+    let mut date: Option<NaiveDate> = None;
+    let mut annual = false;
+    let mut imported = 0;
+    for line in raw.lines() {
+        let line = line.trim_end();
+        if line == "BEGIN:VEVENT" {
+            (name, date, annual) = (None, None, false);
+        } else if let Some(value) = line.strip_prefix("SUMMARY:") {
+            name = Some(value.trim().to_string());
+        } else if line.starts_with("DTSTART") {
+            if let Some((_, value)) = line.split_once(':') {
+                date = NaiveDate::parse_from_str(&value[..8.min(value.len())], "%Y%m%d").ok();
+            }
+        } else if line.starts_with("RRULE") && line.contains("FREQ=YEARLY") {
+            annual = true;
+        } else if line == "END:VEVENT" {
+            if let (Some(name), Some(date)) = (name.take(), date.take()) {
+                if !entries.iter().any(|e| e.name == name) {
+                    entries.push(Entry { name, date, annual });
+                    imported += 1;
+                }
+            }
+        }
+    }
+    save(&entries)?;
+    logger::success(format!("imported {imported} events"));
+    Ok(())
+}
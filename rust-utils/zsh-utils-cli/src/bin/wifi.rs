@@ -0,0 +1,187 @@
+//! macOS Wi-Fi control: a sane interface over airport, networksetup,
+//! and the keychain.
+
+use std::io::Write;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+
+use zsh_utils::{glyphs, logger};
+
+const AIRPORT: &str =
+    "/System/Library/PrivateFrameworks/Apple80211.framework/Versions/Current/Resources/airport";
+
+#[derive(Parser)]
+#[command(name = "wifi", about = "Wi-Fi switcher for macOS")]
+struct Args {
+    #[command(subcommand)]
+    command: Cmd,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+#[derive(Subcommand)]
+enum Cmd {
+    /// Scan and list visible networks with signal strength
+    List,
+    /// Show the currently joined network
+    Current,
+    /// Join a network (password looked up in the keychain if omitted)
+    Join {
+        ssid: String,
+        password: Option<String>,
+    },
+    /// Turn the Wi-Fi interface on
+    On,
+    /// Turn the Wi-Fi interface off
+    Off,
+    /// Print the current network's password from the keychain
+    Password {
+        /// Skip the confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+    if !cfg!(target_os = "macos") {
+        bail!("wifi only knows the macOS tools");
+    }
+    match args.command {
+        Cmd::List => list(),
+        Cmd::Current => current().map(|ssid| println!("{ssid}")),
+        Cmd::Join { ssid, password } => join(&ssid, password),
+        Cmd::On => toggle(true),
+        Cmd::Off => toggle(false),
+        Cmd::Password { yes } => password(yes),
+    }
+}
+
+/// Wi-Fi device name (usually en0), from networksetup.
+fn interface() -> Result<String> {
+    let out = sh("networksetup", &["-listallhardwareports"])?;
+    let mut lines = out.lines();
+    while let Some(line) = lines.next() {
+        if line.contains("Wi-Fi") || line.contains("AirPort") {
+            if let Some(device) = lines.next().and_then(|l| l.strip_prefix("Device: ")) {
+                return Ok(device.trim().to_string());
+            }
+        }
+    }
+    bail!("no Wi-Fi interface found");
+}
+
+fn list() -> Result<()> {
+    let out = sh(AIRPORT, &["-s"])?;
+    let current = current().unwrap_or_default();
+    // airport -s columns: SSID BSSID RSSI CHANNEL ...
+    for line in out.lines().skip(1) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 3 {
+            continue;
+        }
+        // SSIDs can contain spaces; RSSI is the first field parsing as
+        // a negative number, the SSID is everything before the BSSID.
+        let Some(rssi_idx) = fields.iter().position(|f| f.parse::<i32>().map(|v| v < 0).unwrap_or(false))
+        else {
+            continue;
+        };
+        let ssid = fields[..rssi_idx.saturating_sub(1)].join(" ");
+        let rssi: i32 = fields[rssi_idx].parse().unwrap_or(-100);
+        let marker = if ssid == current {
+            glyphs::pick("✳️ ", "* ")
+        } else {
+            "  "
+        };
+        println!("{marker}{ssid:<32} {} {rssi} dBm", bars(rssi));
+    }
+    Ok(())
+}
+
+/// Rough RSSI-to-bars mapping.
+fn bars(rssi: i32) -> &'static str {
+    match rssi {
+        r if r >= -55 => glyphs::pick("▂▄▆█", "####"),
+        r if r >= -67 => glyphs::pick("▂▄▆ ", "### "),
+        r if r >= -75 => glyphs::pick("▂▄  ", "##  "),
+        _ => glyphs::pick("▂   ", "#   "),
+    }
+}
+
+fn current() -> Result<String> {
+    let out = sh(AIRPORT, &["-I"])?;
+    out.lines()
+        .find_map(|l| l.trim().strip_prefix("SSID: "))
+        .map(str::to_string)
+        .context("not connected to Wi-Fi")
+}
+
+fn join(ssid: &str, password: Option<String>) -> Result<()> {
+    let device = interface()?;
+    let password = match password {
+        Some(p) => p,
+        None => keychain_password(ssid)
+            .context("no password given and none found in the keychain")?,
+    };
+    sh("networksetup", &["-setairportnetwork", &device, ssid, &password])?;
+    logger::success(format!("joined {ssid}"));
+    Ok(())
+}
+
+fn toggle(on: bool) -> Result<()> {
+    let device = interface()?;
+    let state = if on { "on" } else { "off" };
+    sh("networksetup", &["-setairportpower", &device, state])?;
+    logger::success(format!("Wi-Fi {state}"));
+    Ok(())
+}
+
+fn keychain_password(ssid: &str) -> Result<String> {
+    let out = Command::new("security")
+        .args(["find-generic-password", "-D", "AirPort network password", "-wa", ssid])
+        .output()
+        .context("running security")?;
+    if !out.status.success() {
+        bail!("keychain lookup failed (denied, or no saved password)");
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+fn password(yes: bool) -> Result<()> {
+    let ssid = current()?;
+    if !yes {
+        print!("print the password for {ssid:?}? [y/N] ");
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !matches!(answer.trim(), "y" | "Y" | "yes") {
+            logger::info("aborted");
+            return Ok(());
+        }
+    }
+    println!("{}", keychain_password(&ssid)?);
+    Ok(())
+}
+
+fn sh(program: &str, args: &[&str]) -> Result<String> {
+    let out = Command::new(program)
+        .args(args)
+        .output()
+        .with_context(|| format!("running {program}"))?;
+    if !out.status.success() {
+        bail!(
+            "{program} failed: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+}
@@ -0,0 +1,135 @@
+//! Runs a command with variables from a dotenv file, validating the
+//! file against `.env.example` first so missing keys fail loudly
+//! before the command starts.
+
+use std::collections::BTreeMap;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+
+use zsh_utils::{glyphs, logger};
+
+#[derive(Parser)]
+#[command(
+    name = "withenv",
+    about = "Load a dotenv file and exec a command with it",
+    trailing_var_arg = true
+)]
+struct Args {
+    /// Dotenv file to load
+    envfile: PathBuf,
+
+    /// Example file to validate against (default: .env.example next to
+    /// the envfile, when present)
+    #[arg(long)]
+    example: Option<PathBuf>,
+
+    /// Command to run (after `--`)
+    #[arg(required = true, last = true)]
+    command: Vec<String>,
+
+    /// Force plain-ASCII output (also auto-detected from TERM/locale)
+    #[arg(long, global = true)]
+    ascii: bool,
+}
+
+fn main() {
+    zsh_utils::errors::exit_on_error(run());
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+    glyphs::init(args.ascii);
+
+    let vars = parse_dotenv(&args.envfile)?;
+
+    let example = args.example.clone().or_else(|| {
+        let candidate = args
+            .envfile
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(".env.example");
+        candidate.exists().then_some(candidate)
+    });
+    if let Some(example) = example {
+        validate(&vars, &example)?;
+    }
+
+    let (program, rest) = args
+        .command
+        .split_first()
+        .context("no command given after --")?;
+    let err = Command::new(program).args(rest).envs(&vars).exec();
+    // exec only returns on failure.
+    Err(err).with_context(|| format!("executing {program}"))
+}
+
+/// Parses a dotenv file: comments, `export` prefixes, single/double
+/// quotes, and `${VAR}` interpolation against earlier keys and the
+/// existing environment.
+fn parse_dotenv(path: &Path) -> Result<BTreeMap<String, String>> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("reading {}", path.display()))?;
+    let mut vars = BTreeMap::new();
+    for (lineno, line) in raw.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            bail!("{}:{}: not a KEY=VALUE line", path.display(), lineno + 1);
+        };
+        let key = key.trim().to_string();
+        let mut value = value.trim().to_string();
+        let quoted_single = value.starts_with('\'') && value.ends_with('\'') && value.len() >= 2;
+        if (value.starts_with('"') && value.ends_with('"') && value.len() >= 2) || quoted_single {
+            value = value[1..value.len() - 1].to_string();
+        }
+        if !quoted_single {
+            value = interpolate(&value, &vars);
+        }
+        vars.insert(key, value);
+    }
+    Ok(vars)
+}
+
+fn interpolate(value: &str, vars: &BTreeMap<String, String>) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let name = &rest[start + 2..start + end];
+        if let Some(v) = vars.get(name).cloned().or_else(|| std::env::var(name).ok()) {
+            out.push_str(&v);
+        }
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Every key present in the example must be provided by the envfile or
+/// the current environment; report all missing keys at once.
+fn validate(vars: &BTreeMap<String, String>, example: &Path) -> Result<()> {
+    let required = parse_dotenv(example)
+        .with_context(|| format!("parsing example file {}", example.display()))?;
+    let missing: Vec<&String> = required
+        .keys()
+        .filter(|k| !vars.contains_key(*k) && std::env::var(k).is_err())
+        .collect();
+    if !missing.is_empty() {
+        for key in &missing {
+            logger::error(format!("missing required key {key}"));
+        }
+        bail!("{} required key(s) missing (see {})", missing.len(), example.display());
+    }
+    Ok(())
+}
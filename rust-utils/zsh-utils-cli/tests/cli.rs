@@ -0,0 +1,72 @@
+//! End-to-end CLI tests: run the real binaries against a synthetic
+//! Claude home assembled from the fixtures.
+
+use std::path::Path;
+
+use assert_cmd::Command;
+
+fn fixture(name: &str) -> std::path::PathBuf {
+    // The fixtures live with the library crate next door.
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../zsh-utils/tests/fixtures")
+        .join(name)
+}
+
+/// Builds `<tmp>/projects/-tmp-demo/basic.jsonl` like Claude Code would.
+fn synthetic_home(tmp: &Path) {
+    let project = tmp.join("projects").join("-tmp-demo");
+    std::fs::create_dir_all(&project).expect("create project dir");
+    std::fs::copy(fixture("basic.jsonl"), project.join("basic.jsonl"))
+        .expect("copy fixture");
+}
+
+#[test]
+fn claude_export_writes_markdown() {
+    let home = tempfile::tempdir().expect("tempdir");
+    let out = tempfile::tempdir().expect("tempdir");
+    synthetic_home(home.path());
+
+    Command::cargo_bin("claude-export")
+        .expect("binary exists")
+        .env("CLAUDE_HOME", home.path())
+        .env("CLAUDE_EXPORT_DIR", out.path())
+        .arg("basic")
+        .assert()
+        .success();
+
+    let exported = out.path().join("demo").join("basic.md");
+    assert!(exported.exists(), "expected {} to exist", exported.display());
+}
+
+#[test]
+fn claude_export_json_format() {
+    let home = tempfile::tempdir().expect("tempdir");
+    let out = tempfile::tempdir().expect("tempdir");
+    synthetic_home(home.path());
+
+    Command::cargo_bin("claude-export")
+        .expect("binary exists")
+        .env("CLAUDE_HOME", home.path())
+        .env("CLAUDE_EXPORT_DIR", out.path())
+        .args(["basic", "--format", "json"])
+        .assert()
+        .success();
+
+    let raw = std::fs::read_to_string(out.path().join("demo").join("basic.json"))
+        .expect("json written");
+    let value: serde_json::Value = serde_json::from_str(&raw).expect("valid json");
+    assert_eq!(value["schema_version"], 1);
+}
+
+#[test]
+fn claude_export_unknown_session_fails() {
+    let home = tempfile::tempdir().expect("tempdir");
+    synthetic_home(home.path());
+
+    Command::cargo_bin("claude-export")
+        .expect("binary exists")
+        .env("CLAUDE_HOME", home.path())
+        .arg("does-not-exist")
+        .assert()
+        .failure();
+}
@@ -0,0 +1,85 @@
+//! Benchmarks for the transcript hot path: parsing a large session and
+//! rendering it out. Run with `cargo bench` before and after touching
+//! the parser or exporters, so performance work is measured rather than
+//! guessed.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+use zsh_utils::claude::export::{build_json, render_markdown, RenderOptions};
+use zsh_utils::claude::parser;
+use zsh_utils::claude::pricing::Pricing;
+use zsh_utils::claude::sessions::{Project, Session};
+
+/// A synthetic session of `turns` user/assistant exchanges, shaped like
+/// real transcripts: text blocks, a tool use with a result, and usage.
+fn synthetic_transcript(turns: usize) -> String {
+    let mut out = String::new();
+    for i in 0..turns {
+        out.push_str(&format!(
+            concat!(
+                r#"{{"type":"user","uuid":"u{i}","timestamp":"2025-01-02T03:04:05Z","#,
+                r#""message":{{"role":"user","content":"Please look at item {i} "#,
+                r#"and tell me whether the frobnicator needs adjusting."}}}}"#,
+                "\n"
+            ),
+            i = 2 * i
+        ));
+        out.push_str(&format!(
+            concat!(
+                r#"{{"type":"assistant","uuid":"u{i}","parentUuid":"u{p}","#,
+                r#""timestamp":"2025-01-02T03:04:06Z","message":{{"role":"assistant","#,
+                r#""model":"claude-3-opus-20240229","content":[{{"type":"text","#,
+                r#""text":"Checking item {p} now."}},{{"type":"tool_use","id":"t{i}","#,
+                r#""name":"Read","input":{{"file_path":"/tmp/demo/item-{p}.rs"}}}}],"#,
+                r#""usage":{{"input_tokens":120,"output_tokens":40}}}}}}"#,
+                "\n"
+            ),
+            i = 2 * i + 1,
+            p = 2 * i
+        ));
+    }
+    out
+}
+
+fn bench_session(raw: &str) -> Session {
+    let project = Project {
+        encoded_name: "-tmp-demo".to_string(),
+        path: std::env::temp_dir(),
+    };
+    let path = std::env::temp_dir().join("zsh-utils-bench.jsonl");
+    std::fs::write(&path, raw).expect("writing bench transcript");
+    Session { id: "bench".to_string(), path, project }
+}
+
+fn benches(c: &mut Criterion) {
+    let raw = synthetic_transcript(500);
+    let session = bench_session(&raw);
+    let transcript = parser::parse_str(&raw);
+    let pricing = Pricing::builtin();
+
+    let mut group = c.benchmark_group("transcripts");
+    group.throughput(Throughput::Bytes(raw.len() as u64));
+    group.bench_function("parse_str", |b| {
+        b.iter(|| parser::parse_str(std::hint::black_box(&raw)))
+    });
+    group.bench_function("render_markdown", |b| {
+        b.iter(|| {
+            render_markdown(
+                &session,
+                std::hint::black_box(&transcript),
+                &pricing,
+                &RenderOptions::default(),
+                &[],
+            )
+        })
+    });
+    group.bench_function("build_json", |b| {
+        b.iter(|| {
+            build_json(&session, std::hint::black_box(&transcript), &pricing, Vec::new())
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(transcripts, benches);
+criterion_main!(transcripts);
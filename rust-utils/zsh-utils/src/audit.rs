@@ -0,0 +1,93 @@
+//! Opt-in audit log of every command the toolbox executes on the
+//! user's behalf (hooks, git queries, converters), so agentic features
+//! stay accountable.
+//!
+//! Disabled unless `$ZSH_AUDIT_LOG` is set — to a log file path, or to
+//! `1` for the default `$ZSH_CONFIG/audit.jsonl`. Recording must never
+//! break the command it records, so failures only warn.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::logger;
+
+#[derive(Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// RFC 3339, UTC.
+    pub timestamp: String,
+    pub command: String,
+    pub cwd: String,
+    /// `None` when the command never ran or was killed by a signal.
+    pub exit_code: Option<i32>,
+}
+
+/// Where the log goes, or `None` when auditing is off.
+pub fn log_path() -> Option<PathBuf> {
+    let raw = std::env::var("ZSH_AUDIT_LOG").ok()?;
+    match raw.as_str() {
+        "" | "0" => None,
+        "1" => Some(crate::llm::config_dir().join("audit.jsonl")),
+        path => Some(PathBuf::from(path)),
+    }
+}
+
+/// Appends one entry (no-op when auditing is off).
+pub fn record(command: &str, cwd: &Path, exit_code: Option<i32>) {
+    let Some(path) = log_path() else { return };
+    let entry = AuditEntry {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        command: command.to_string(),
+        cwd: cwd.display().to_string(),
+        exit_code,
+    };
+    if let Err(err) = append(&path, &entry) {
+        logger::warn(format!("audit log write failed: {err:#}"));
+    }
+}
+
+fn append(path: &Path, entry: &AuditEntry) -> Result<()> {
+    use std::io::Write;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("creating {}", dir.display()))?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("opening {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+        .with_context(|| format!("appending to {}", path.display()))
+}
+
+/// Every recorded entry, oldest first. Lines that fail to decode are
+/// skipped — a half-written trailing line must not hide the rest.
+pub fn read() -> Result<Vec<AuditEntry>> {
+    let Some(path) = log_path() else {
+        anyhow::bail!("auditing is off — set ZSH_AUDIT_LOG to enable it");
+    };
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let raw = std::fs::read_to_string(&path)
+        .with_context(|| format!("reading {}", path.display()))?;
+    Ok(raw
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Truncates the log.
+pub fn clear() -> Result<()> {
+    let Some(path) = log_path() else {
+        anyhow::bail!("auditing is off — set ZSH_AUDIT_LOG to enable it");
+    };
+    if path.is_file() {
+        std::fs::remove_file(&path)
+            .with_context(|| format!("removing {}", path.display()))?;
+    }
+    Ok(())
+}
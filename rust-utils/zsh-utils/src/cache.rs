@@ -0,0 +1,182 @@
+//! Content-addressed cache for LLM-derived artifacts (summaries,
+//! digests, blog posts), so re-running an export never pays for the
+//! same completion twice.
+//!
+//! Entries live as one file per completion under `~/.cache/utils/llm`,
+//! keyed by a hash over the full prompt, the model name, and a prompt
+//! version that callers bump when they change what they do with the
+//! reply. Inspect or empty it with the `cache` tool.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::llm::{ChatMessage, ChatProvider, LLMClient};
+use crate::logger;
+
+/// Root of the tool cache, `$XDG_CACHE_HOME/utils` or `~/.cache/utils`.
+pub fn cache_dir() -> PathBuf {
+    std::env::var("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| dirs::home_dir().unwrap_or_default().join(".cache"))
+        .join("utils")
+}
+
+pub struct LlmCache {
+    dir: PathBuf,
+}
+
+pub struct CacheStats {
+    pub entries: usize,
+    pub bytes: u64,
+}
+
+impl LlmCache {
+    pub fn open() -> Self {
+        Self {
+            dir: cache_dir().join("llm"),
+        }
+    }
+
+    /// Runs the completion through the cache: a hit costs one file
+    /// read, a miss calls the model and stores the reply for next
+    /// time. `prompt_version` is bumped by the caller whenever the
+    /// meaning of the prompt changes without its text doing so.
+    pub fn complete_cached(
+        &self,
+        client: &LLMClient,
+        messages: &[ChatMessage],
+        prompt_version: &str,
+    ) -> Result<String> {
+        let key = key(messages, client.model(), prompt_version);
+        if let Some(hit) = self.get(&key, None) {
+            return Ok(hit);
+        }
+        let reply = client.complete(messages)?;
+        self.put(&key, &reply);
+        Ok(reply)
+    }
+
+    /// A `ttl` makes entries expire: anything written longer ago than
+    /// that reads as a miss (and gets overwritten on the refetch).
+    fn get(&self, key: &str, ttl: Option<std::time::Duration>) -> Option<String> {
+        let path = self.dir.join(key);
+        if let Some(ttl) = ttl {
+            let modified = std::fs::metadata(&path).ok()?.modified().ok()?;
+            if modified.elapsed().unwrap_or_default() > ttl {
+                return None;
+            }
+        }
+        std::fs::read_to_string(path).ok()
+    }
+
+    /// A failed write only warns — losing a cache entry must never
+    /// fail the export that just paid for the completion.
+    fn put(&self, key: &str, value: &str) {
+        let store = || -> Result<()> {
+            std::fs::create_dir_all(&self.dir)
+                .with_context(|| format!("creating {}", self.dir.display()))?;
+            std::fs::write(self.dir.join(key), value)
+                .with_context(|| format!("writing cache entry {key}"))
+        };
+        if let Err(err) = store() {
+            logger::warn(format!("llm cache write failed: {err:#}"));
+        }
+    }
+
+    pub fn stats(&self) -> Result<CacheStats> {
+        let mut stats = CacheStats { entries: 0, bytes: 0 };
+        if !self.dir.is_dir() {
+            return Ok(stats);
+        }
+        for entry in std::fs::read_dir(&self.dir)
+            .with_context(|| format!("reading {}", self.dir.display()))?
+        {
+            let entry = entry?;
+            if entry.path().is_file() {
+                stats.entries += 1;
+                stats.bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Removes every entry; returns how many went.
+    pub fn clear(&self) -> Result<usize> {
+        if !self.dir.is_dir() {
+            return Ok(0);
+        }
+        let mut removed = 0;
+        for entry in std::fs::read_dir(&self.dir)
+            .with_context(|| format!("reading {}", self.dir.display()))?
+        {
+            let path = entry?.path();
+            if path.is_file() {
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("removing {}", path.display()))?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// A [`ChatProvider`] that answers identical conversations from the
+/// cache instead of billing again — regenerating without changes,
+/// batch retries, and tests all hit the same key. Opt-in: enabled by
+/// `cache_ttl_minutes` in llm.toml, switched off per run with
+/// `--no-cache`.
+pub struct CachedProvider<'a> {
+    inner: &'a dyn ChatProvider,
+    cache: LlmCache,
+    ttl: Option<std::time::Duration>,
+}
+
+impl<'a> CachedProvider<'a> {
+    /// `ttl` of `None` means entries never expire.
+    pub fn new(inner: &'a dyn ChatProvider, ttl: Option<std::time::Duration>) -> Self {
+        Self {
+            inner,
+            cache: LlmCache::open(),
+            ttl,
+        }
+    }
+}
+
+impl ChatProvider for CachedProvider<'_> {
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    fn complete(&self, messages: &[ChatMessage]) -> Result<String> {
+        let cache_key = key(messages, self.inner.model(), "chat-v1");
+        if let Some(hit) = self.cache.get(&cache_key, self.ttl) {
+            return Ok(hit);
+        }
+        let reply = self.inner.complete(messages)?;
+        self.cache.put(&cache_key, &reply);
+        Ok(reply)
+    }
+}
+
+/// Hex SHA-256 over the length-prefixed parts, so no concatenation of
+/// prompt, model, and version can collide with another.
+fn key(messages: &[ChatMessage], model: &str, prompt_version: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    for message in messages {
+        hasher.update(message.role.len().to_le_bytes());
+        hasher.update(message.role.as_bytes());
+        hasher.update(message.content.len().to_le_bytes());
+        hasher.update(message.content.as_bytes());
+    }
+    hasher.update(model.len().to_le_bytes());
+    hasher.update(model.as_bytes());
+    hasher.update(prompt_version.len().to_le_bytes());
+    hasher.update(prompt_version.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
@@ -0,0 +1,473 @@
+//! Full-screen chat TUI backing the `llm-chat` binary.
+
+pub mod ui;
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+
+use crate::glyphs;
+use crate::llm::{ChatMessage, ChatProvider};
+
+/// Everything the draw code needs to render a frame.
+pub struct ChatApp {
+    pub messages: Vec<ChatMessage>,
+    pub input: String,
+    pub scroll: u16,
+    /// Transient one-line note shown in the status bar (e.g. "thinking…").
+    pub status: Option<String>,
+    pub should_quit: bool,
+    /// Pasted blocks, in paste order. The input line only carries their
+    /// collapsed previews; [`ChatApp::take_input`] splices the real
+    /// text back in on send.
+    pastes: Vec<String>,
+    undo: Vec<EditorState>,
+    redo: Vec<EditorState>,
+    /// What the last mutation was, so runs of typing or deleting
+    /// coalesce into one undo step instead of one per keystroke.
+    last_edit: EditKind,
+    pub mode: Mode,
+    /// Message under the selection cursor (Selecting mode only).
+    pub cursor: usize,
+    /// Other end of a range selection, set with Space.
+    pub anchor: Option<usize>,
+    /// Memoized Markdown rendering, interior-mutable because drawing
+    /// only gets `&ChatApp`.
+    pub render_cache: std::cell::RefCell<crate::markdown::RenderCache>,
+    /// Standing system instruction (`--lang`); sent with every request
+    /// but kept out of the visible history.
+    system: Option<ChatMessage>,
+    /// Side pane with a translation (`/translate`); Esc closes it.
+    pub pane: Option<SidePane>,
+}
+
+/// Content shown beside the conversation instead of inside it, so a
+/// translation can sit next to the original rather than replacing it.
+pub struct SidePane {
+    /// Pane border title, e.g. the target language.
+    pub title: String,
+    /// Markdown body, rendered like an assistant reply.
+    pub content: String,
+}
+
+/// Whether keystrokes edit the draft or select messages for deletion.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Typing,
+    Selecting,
+}
+
+/// Everything undo has to restore: the draft and the conversation
+/// (clear and message deletion are undoable too).
+#[derive(Clone)]
+struct EditorState {
+    input: String,
+    pastes: Vec<String>,
+    messages: Vec<ChatMessage>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    Insert,
+    Delete,
+    Other,
+}
+
+/// Undo steps kept before the oldest is dropped.
+const HISTORY_LIMIT: usize = 100;
+
+impl ChatApp {
+    pub fn new() -> Self {
+        Self {
+            messages: Vec::new(),
+            input: String::new(),
+            scroll: 0,
+            status: None,
+            should_quit: false,
+            pastes: Vec::new(),
+            undo: Vec::new(),
+            redo: Vec::new(),
+            last_edit: EditKind::Other,
+            mode: Mode::Typing,
+            cursor: 0,
+            anchor: None,
+            render_cache: std::cell::RefCell::new(crate::markdown::RenderCache::new()),
+            system: None,
+            pane: None,
+        }
+    }
+
+    /// Adds a standing system instruction, e.g. "Respond in Hindi.".
+    pub fn with_system(mut self, message: Option<ChatMessage>) -> Self {
+        self.system = message;
+        self
+    }
+
+    /// The messages a request should carry: the standing system
+    /// instruction (if any) ahead of the visible conversation.
+    fn request(&self) -> Vec<ChatMessage> {
+        self.system
+            .iter()
+            .chain(self.messages.iter())
+            .cloned()
+            .collect()
+    }
+
+    /// Enters selection mode on the newest message — the usual target
+    /// when excising a tangent that just happened.
+    pub fn enter_select(&mut self) {
+        if self.messages.is_empty() {
+            return;
+        }
+        self.mode = Mode::Selecting;
+        self.cursor = self.messages.len() - 1;
+        self.anchor = None;
+    }
+
+    pub fn exit_select(&mut self) {
+        self.mode = Mode::Typing;
+        self.anchor = None;
+    }
+
+    pub fn select_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn select_down(&mut self) {
+        if self.cursor + 1 < self.messages.len() {
+            self.cursor += 1;
+        }
+    }
+
+    pub fn toggle_anchor(&mut self) {
+        self.anchor = match self.anchor {
+            Some(_) => None,
+            None => Some(self.cursor),
+        };
+    }
+
+    /// The selected message range, normalized to ascending order.
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        if self.mode != Mode::Selecting {
+            return None;
+        }
+        let anchor = self.anchor.unwrap_or(self.cursor);
+        Some((anchor.min(self.cursor), anchor.max(self.cursor)))
+    }
+
+    /// Deletes the selected message(s) from the conversation
+    /// (undoable), so the next request goes out without them.
+    pub fn delete_selection(&mut self) {
+        let Some((from, to)) = self.selection() else { return };
+        self.checkpoint(EditKind::Other);
+        self.messages.drain(from..=to);
+        self.anchor = None;
+        if self.messages.is_empty() {
+            self.exit_select();
+        } else {
+            self.cursor = from.min(self.messages.len() - 1);
+        }
+    }
+
+    fn state(&self) -> EditorState {
+        EditorState {
+            input: self.input.clone(),
+            pastes: self.pastes.clone(),
+            messages: self.messages.clone(),
+        }
+    }
+
+    fn restore(&mut self, state: EditorState) {
+        self.input = state.input;
+        self.pastes = state.pastes;
+        self.messages = state.messages;
+    }
+
+    /// Records an undo point unless it would just extend the current
+    /// run of the same edit kind.
+    fn checkpoint(&mut self, kind: EditKind) {
+        if kind == EditKind::Other || kind != self.last_edit {
+            self.undo.push(self.state());
+            if self.undo.len() > HISTORY_LIMIT {
+                self.undo.remove(0);
+            }
+            self.redo.clear();
+        }
+        self.last_edit = kind;
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        // A word boundary starts a fresh undo step, so undo rewinds
+        // word by word instead of nuking the whole draft.
+        let kind = if c.is_whitespace() { EditKind::Other } else { EditKind::Insert };
+        self.checkpoint(kind);
+        self.input.push(c);
+    }
+
+    pub fn delete_char(&mut self) {
+        self.checkpoint(EditKind::Delete);
+        self.input.pop();
+    }
+
+    /// Clears the conversation and the draft (undoable).
+    pub fn clear(&mut self) {
+        self.checkpoint(EditKind::Other);
+        self.input.clear();
+        self.pastes.clear();
+        self.messages.clear();
+        self.scroll = 0;
+    }
+
+    pub fn undo(&mut self) {
+        if let Some(state) = self.undo.pop() {
+            self.redo.push(self.state());
+            self.restore(state);
+            self.last_edit = EditKind::Other;
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(state) = self.redo.pop() {
+            self.undo.push(self.state());
+            self.restore(state);
+            self.last_edit = EditKind::Other;
+        }
+    }
+
+    /// Handles one bracketed-paste event. Short single-line pastes go
+    /// straight into the input; anything bigger collapses to a
+    /// "(pasted N lines)" preview so a 500-line paste neither floods
+    /// the input box nor sends itself on an embedded newline.
+    pub fn paste(&mut self, text: &str) {
+        self.checkpoint(EditKind::Other);
+        if !text.contains('\n') && text.chars().count() <= 200 {
+            self.input.push_str(text);
+            return;
+        }
+        self.pastes.push(text.to_string());
+        self.input.push_str(&paste_marker(
+            self.pastes.len(),
+            text.lines().count(),
+        ));
+    }
+
+    /// Takes the pending input, with collapsed paste previews expanded
+    /// back to their full text.
+    pub fn take_input(&mut self) -> String {
+        let mut text = std::mem::take(&mut self.input);
+        for (i, paste) in self.pastes.drain(..).enumerate() {
+            let marker = paste_marker(i + 1, paste.lines().count());
+            if let Some(at) = text.find(&marker) {
+                text.replace_range(at..at + marker.len(), &paste);
+            }
+        }
+        text
+    }
+}
+
+fn paste_marker(number: usize, lines: usize) -> String {
+    format!("(pasted #{number}: {lines} lines)")
+}
+
+/// Builds the standing system instruction from a project preset's
+/// system text (see [`crate::llm::preset`]) and `--lang`, in that
+/// order — the language request comes last so it wins on conflict.
+pub fn standing_system(preset: Option<String>, lang: Option<&str>) -> Option<ChatMessage> {
+    let mut parts: Vec<String> = preset.into_iter().collect();
+    parts.extend(lang.map(|lang| format!("Respond in {lang}.")));
+    (!parts.is_empty()).then(|| ChatMessage::system(parts.join("\n\n")))
+}
+
+impl Default for ChatApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs the chat loop until the user quits. `system` is the standing
+/// instruction (see [`standing_system`]) sent ahead of every request.
+/// Terminal setup/teardown is handled here so callers only deal with
+/// errors.
+pub fn run(client: &dyn ChatProvider, system: Option<ChatMessage>) -> Result<()> {
+    crossterm::terminal::enable_raw_mode()?;
+    let mut stdout = std::io::stdout();
+    crossterm::execute!(
+        stdout,
+        crossterm::terminal::EnterAlternateScreen,
+        event::EnableBracketedPaste
+    )?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let mut app = ChatApp::new().with_system(system);
+    let result = event_loop(&mut terminal, &mut app, client);
+
+    crossterm::terminal::disable_raw_mode()?;
+    crossterm::execute!(
+        terminal.backend_mut(),
+        event::DisableBracketedPaste,
+        crossterm::terminal::LeaveAlternateScreen
+    )?;
+    result
+}
+
+/// Line-oriented fallback for screen readers and dumb terminals: no
+/// alternate screen, no raw mode, no boxes or colors — just a
+/// prompt/response loop over stdin/stdout against the same provider.
+/// Quits on EOF (Ctrl+D) or an empty line.
+pub fn run_plain(client: &dyn ChatProvider, system: Option<ChatMessage>) -> Result<()> {
+    use std::io::{BufRead, Write};
+
+    let stdin = std::io::stdin();
+    let mut messages: Vec<ChatMessage> = system.into_iter().collect();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("you: ");
+        std::io::stdout().flush()?;
+        let Some(line) = lines.next() else { break };
+        let text = line?.trim().to_string();
+        if text.is_empty() {
+            break;
+        }
+        messages.push(ChatMessage::user(text));
+        match client.complete(&messages) {
+            Ok(reply) => {
+                println!("llm: {reply}");
+                println!();
+                messages.push(ChatMessage::assistant(reply));
+            }
+            Err(err) => eprintln!("error: {err:#}"),
+        }
+    }
+    Ok(())
+}
+
+fn event_loop(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    app: &mut ChatApp,
+    client: &dyn ChatProvider,
+) -> Result<()> {
+    loop {
+        terminal.draw(|frame| ui::draw(frame, app))?;
+        if app.should_quit {
+            return Ok(());
+        }
+        match event::read()? {
+            // Bracketed paste delivers the whole block as one event, so
+            // pasted newlines never fire the Enter arm below.
+            Event::Paste(text) => app.paste(&text),
+            Event::Key(key) if app.mode == Mode::Selecting => {
+                match (key.code, key.modifiers) {
+                    (KeyCode::Char('c'), KeyModifiers::CONTROL) => app.should_quit = true,
+                    (KeyCode::Esc | KeyCode::Char('q'), _) => app.exit_select(),
+                    (KeyCode::Up | KeyCode::Char('k'), _) => app.select_up(),
+                    (KeyCode::Down | KeyCode::Char('j'), _) => app.select_down(),
+                    (KeyCode::Char(' '), _) => app.toggle_anchor(),
+                    (KeyCode::Char('d') | KeyCode::Delete, _) => app.delete_selection(),
+                    _ => {}
+                }
+            }
+            Event::Key(key) => match (key.code, key.modifiers) {
+                (KeyCode::Char('c'), KeyModifiers::CONTROL) => app.should_quit = true,
+                (KeyCode::Char('z'), KeyModifiers::CONTROL) => app.undo(),
+                (KeyCode::Char('y'), KeyModifiers::CONTROL) => app.redo(),
+                (KeyCode::Char('l'), KeyModifiers::CONTROL) => app.clear(),
+                // Esc closes the side pane first; selecting comes next.
+                (KeyCode::Esc, _) if app.pane.is_some() => app.pane = None,
+                (KeyCode::Esc, _) => app.enter_select(),
+                (KeyCode::Enter, _) => submit(terminal, app, client)?,
+                (KeyCode::Backspace, _) => app.delete_char(),
+                (KeyCode::PageUp, _) => app.scroll = app.scroll.saturating_add(5),
+                (KeyCode::PageDown, _) => app.scroll = app.scroll.saturating_sub(5),
+                (KeyCode::Char(c), _) => app.insert_char(c),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+fn submit(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    app: &mut ChatApp,
+    client: &dyn ChatProvider,
+) -> Result<()> {
+    if app.input.trim().is_empty() {
+        return Ok(());
+    }
+    app.checkpoint(EditKind::Other);
+    let text = app.take_input().trim().to_string();
+    if let Some(spec) = text.strip_prefix("/translate") {
+        return translate(terminal, app, client, spec.trim());
+    }
+    app.messages.push(ChatMessage::user(text));
+    app.status = Some(glyphs::pick("thinking…", "thinking...").to_string());
+    terminal.draw(|frame| ui::draw(frame, app))?;
+
+    match client.complete(&app.request()) {
+        Ok(reply) => app.messages.push(ChatMessage::assistant(reply)),
+        Err(err) => app.status = Some(format!("error: {err:#}")),
+    }
+    if app.status.as_deref() == Some(glyphs::pick("thinking…", "thinking...")) {
+        app.status = None;
+    }
+    app.scroll = 0;
+    Ok(())
+}
+
+const TRANSLATE_PROMPT: &str = "You translate conversations. Translate the \
+text you are given into the requested language. Preserve the Markdown \
+formatting and any code blocks verbatim. Output only the translation.";
+
+/// `/translate <lang>` re-renders the last reply into `lang` in the
+/// side pane; `/translate all <lang>` does the whole conversation. The
+/// conversation itself is left untouched — the translation is a view,
+/// not a message.
+fn translate(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    app: &mut ChatApp,
+    client: &dyn ChatProvider,
+    spec: &str,
+) -> Result<()> {
+    let (whole, lang) = match spec.split_once(char::is_whitespace) {
+        Some(("all", lang)) => (true, lang.trim()),
+        _ => (false, spec),
+    };
+    if lang.is_empty() {
+        app.status = Some("usage: /translate [all] <language>".to_string());
+        return Ok(());
+    }
+    let source = if whole {
+        app.messages
+            .iter()
+            .map(|m| format!("{}: {}", m.role, m.content))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    } else {
+        match app.messages.iter().rev().find(|m| m.role == "assistant") {
+            Some(message) => message.content.clone(),
+            None => {
+                app.status = Some("nothing to translate yet".to_string());
+                return Ok(());
+            }
+        }
+    };
+    app.status = Some(glyphs::pick("translating…", "translating...").to_string());
+    terminal.draw(|frame| ui::draw(frame, app))?;
+
+    let request = [
+        ChatMessage::system(TRANSLATE_PROMPT),
+        ChatMessage::user(format!("Language: {lang}\n\n{source}")),
+    ];
+    match client.complete(&request) {
+        Ok(translated) => {
+            app.pane = Some(SidePane {
+                title: format!(" translation: {} ", lang.to_lowercase()),
+                content: translated,
+            });
+            app.status = Some("Esc closes the translation pane".to_string());
+        }
+        Err(err) => app.status = Some(format!("error: {err:#}")),
+    }
+    Ok(())
+}
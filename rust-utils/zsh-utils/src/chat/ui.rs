@@ -0,0 +1,182 @@
+//! Frame layout and widgets for the chat TUI.
+//!
+//! The layout adapts to the terminal width so the UI stays usable over
+//! ssh sessions and tmux splits instead of wrapping into garbage:
+//!
+//! * `Full`    — title with keybinding hints plus a status bar
+//! * `Compact` — short title, no status bar
+//! * `TooSmall` — a single centered notice asking for a bigger window
+
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Paragraph, Wrap};
+use ratatui::Frame;
+
+use super::ChatApp;
+use crate::glyphs;
+use crate::term::{self, bordered};
+
+/// Below this width we refuse to lay anything out at all.
+pub const MIN_COLS: u16 = 40;
+pub const MIN_ROWS: u16 = 8;
+/// Below this width the title loses its keybinding hints and the
+/// status bar is dropped to save a row.
+pub const COMPACT_COLS: u16 = 60;
+
+const TITLE_FULL: &str = " llm-chat — Enter send · PgUp/PgDn scroll · Ctrl+C quit ";
+const TITLE_FULL_ASCII: &str = " llm-chat - Enter send / PgUp/PgDn scroll / Ctrl+C quit ";
+const TITLE_COMPACT: &str = " llm-chat ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutMode {
+    Full,
+    Compact,
+    TooSmall,
+}
+
+impl LayoutMode {
+    pub fn for_size(width: u16, height: u16) -> Self {
+        if width < MIN_COLS || height < MIN_ROWS {
+            LayoutMode::TooSmall
+        } else if width < COMPACT_COLS {
+            LayoutMode::Compact
+        } else {
+            LayoutMode::Full
+        }
+    }
+}
+
+pub fn draw(frame: &mut Frame, app: &ChatApp) {
+    let area = frame.size();
+    match LayoutMode::for_size(area.width, area.height) {
+        LayoutMode::TooSmall => draw_too_small(frame, area),
+        mode => draw_chat(frame, area, app, mode),
+    }
+}
+
+fn draw_too_small(frame: &mut Frame, area: Rect) {
+    let notice = Paragraph::new(vec![
+        Line::from("Terminal too small"),
+        Line::from(format!("need at least {MIN_COLS}×{MIN_ROWS}")),
+    ])
+    .alignment(Alignment::Center)
+    .style(term::themed(Style::default().fg(Color::Yellow)));
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ])
+        .split(area);
+    frame.render_widget(notice, vertical[1]);
+}
+
+fn draw_chat(frame: &mut Frame, area: Rect, app: &ChatApp, mode: LayoutMode) {
+    let status_rows = if mode == LayoutMode::Full { 1 } else { 0 };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(3),
+            Constraint::Length(status_rows),
+        ])
+        .split(area);
+
+    let title = match mode {
+        LayoutMode::Full => glyphs::pick(TITLE_FULL, TITLE_FULL_ASCII),
+        _ => TITLE_COMPACT,
+    };
+    // The side pane (translations) takes the right half of the history
+    // area; below the compact threshold two panes would both be
+    // useless, so the pane waits for a wider window.
+    let (history_area, pane_area) = match &app.pane {
+        Some(_) if area.width >= COMPACT_COLS => {
+            let cols = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(chunks[0]);
+            (cols[0], Some(cols[1]))
+        }
+        _ => (chunks[0], None),
+    };
+    let text_width = history_area.width.saturating_sub(2) as usize;
+    let history = Paragraph::new(message_lines(app, text_width))
+        .block(bordered(title))
+        .wrap(Wrap { trim: false })
+        .scroll((app.scroll, 0));
+    frame.render_widget(history, history_area);
+
+    if let (Some(pane), Some(pane_area)) = (&app.pane, pane_area) {
+        let pane_width = pane_area.width.saturating_sub(2) as usize;
+        let body = app
+            .render_cache
+            .borrow_mut()
+            .render(&pane.content, pane_width)
+            .to_vec();
+        let widget = Paragraph::new(body)
+            .block(bordered(pane.title.as_str()))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(widget, pane_area);
+    }
+
+    let input = Paragraph::new(app.input.as_str()).block(bordered(" message "));
+    frame.render_widget(input, chunks[1]);
+
+    if mode == LayoutMode::Full {
+        let status = if app.mode == super::Mode::Selecting {
+            SELECT_HINT
+        } else {
+            app.status.as_deref().unwrap_or("")
+        };
+        let bar = Paragraph::new(status)
+            .style(term::themed(Style::default().fg(Color::DarkGray)));
+        frame.render_widget(bar, chunks[2]);
+    }
+}
+
+const SELECT_HINT: &str =
+    " select: Up/Down move · Space range · d delete · Esc done";
+
+fn message_lines(app: &ChatApp, width: usize) -> Vec<Line<'static>> {
+    let selection = app.selection();
+    let mut lines = Vec::new();
+    for (i, msg) in app.messages.iter().enumerate() {
+        let (label, color) = match msg.role.as_str() {
+            "user" => ("you", Color::Cyan),
+            "assistant" => ("llm", Color::Green),
+            other => (other, Color::Gray),
+        };
+        let selected = selection.is_some_and(|(from, to)| i >= from && i <= to);
+        let mut style = Style::default().fg(color).add_modifier(Modifier::BOLD);
+        if selected {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+        let cursor = if selection.is_some() && i == app.cursor {
+            glyphs::pick("▶ ", "> ")
+        } else {
+            ""
+        };
+        lines.push(Line::from(Span::styled(
+            format!("{cursor}{label}:"),
+            term::themed(style),
+        )));
+        if msg.role == "assistant" {
+            // Assistant replies are Markdown; render them styled,
+            // memoized so redraws don't re-parse the whole history.
+            lines.extend(
+                app.render_cache
+                    .borrow_mut()
+                    .render(&msg.content, width)
+                    .to_vec(),
+            );
+        } else {
+            for text_line in msg.content.lines() {
+                lines.push(Line::from(text_line.to_string()));
+            }
+        }
+        lines.push(Line::from(""));
+    }
+    lines
+}
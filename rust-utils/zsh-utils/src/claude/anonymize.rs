@@ -0,0 +1,108 @@
+//! Scrubbing machine-identifying details from exports meant for
+//! public sharing.
+//!
+//! `--anonymize` rewrites the home directory, username, hostname, and
+//! email addresses to generic placeholders in everything the exporter
+//! writes. The goal is publishing an interesting session without
+//! leaking who ran it or where — not cryptographic deniability; text
+//! the session itself typed about its author still gets through.
+
+use std::process::Command;
+
+/// Literal find-and-replace pairs plus the email scrubber.
+pub struct Anonymizer {
+    /// Longest patterns first, so the home path wins over the bare
+    /// username it contains.
+    replacements: Vec<(String, String)>,
+}
+
+impl Anonymizer {
+    /// Builds the replacement table from this machine's identity.
+    pub fn from_environment() -> Self {
+        let mut replacements = Vec::new();
+        if let Some(home) = dirs::home_dir() {
+            replacements.push((home.display().to_string(), "~".to_string()));
+        }
+        if let Ok(user) = std::env::var("USER") {
+            if !user.is_empty() {
+                replacements.push((user, "user".to_string()));
+            }
+        }
+        if let Some(host) = hostname() {
+            // Strip the local-network suffix too; "mac.local" leaking
+            // as "host.local" would still be odd.
+            let short = host.trim_end_matches(".local").to_string();
+            replacements.push((host.clone(), "host".to_string()));
+            if short != host && !short.is_empty() {
+                replacements.push((short, "host".to_string()));
+            }
+        }
+        replacements.sort_by_key(|(from, _)| std::cmp::Reverse(from.len()));
+        Self { replacements }
+    }
+
+    pub fn scrub(&self, text: &str) -> String {
+        let mut scrubbed = text.to_string();
+        for (from, to) in &self.replacements {
+            scrubbed = scrubbed.replace(from, to);
+        }
+        scrub_emails(&scrubbed)
+    }
+}
+
+fn hostname() -> Option<String> {
+    if let Ok(host) = std::env::var("HOSTNAME") {
+        if !host.is_empty() {
+            return Some(host);
+        }
+    }
+    let output = Command::new("hostname").output().ok()?;
+    let host = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!host.is_empty()).then_some(host)
+}
+
+/// Replaces anything shaped like an email address with
+/// `user@example.com`. A hand-rolled scan — the crate deliberately
+/// carries no regex dependency for this one pattern.
+fn scrub_emails(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut last_emitted = 0;
+    for (at, c) in chars.iter().enumerate() {
+        if *c != '@' || at < last_emitted {
+            continue;
+        }
+        let mut start = at;
+        while start > last_emitted && is_local_char(chars[start - 1]) {
+            start -= 1;
+        }
+        let mut end = at + 1;
+        while end < chars.len() && is_domain_char(chars[end]) {
+            end += 1;
+        }
+        // Trim a trailing dot — sentences end next to addresses.
+        while end > at + 1 && chars[end - 1] == '.' {
+            end -= 1;
+        }
+        let domain: String = chars[at + 1..end].iter().collect();
+        let has_tld = domain
+            .rsplit_once('.')
+            .is_some_and(|(_, tld)| tld.len() >= 2 && tld.chars().all(char::is_alphabetic));
+        if start == at || !has_tld {
+            continue;
+        }
+        out.extend(&chars[last_emitted..start]);
+        out.push_str("user@example.com");
+        last_emitted = end;
+    }
+    out.extend(&chars[last_emitted..]);
+    out
+}
+
+fn is_local_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '%' | '+' | '-')
+}
+
+fn is_domain_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '-')
+}
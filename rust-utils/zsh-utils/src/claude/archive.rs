@@ -0,0 +1,127 @@
+//! Bundling a project's export directory into one compressed archive
+//! with a manifest — the offsite-backup companion to the exporters.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+#[derive(Clone, Copy)]
+pub enum ArchiveFormat {
+    Zip,
+    TarGz,
+}
+
+/// Archives everything under `dir` (a per-project export directory)
+/// into a sibling `<project>.zip` / `<project>.tar.gz`, prepending a
+/// `manifest.json` that lists the contents with sizes. Returns the
+/// archive path.
+pub fn archive_project(dir: &Path, format: ArchiveFormat) -> Result<PathBuf> {
+    let files = collect_files(dir)?;
+    anyhow::ensure!(!files.is_empty(), "nothing to archive in {}", dir.display());
+    let manifest = manifest(dir, &files)?;
+
+    let name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .context("archive directory has no name")?;
+    let parent = dir.parent().unwrap_or(Path::new("."));
+    match format {
+        ArchiveFormat::Zip => {
+            let out = parent.join(format!("{name}.zip"));
+            write_zip(&out, &manifest, &files)?;
+            Ok(out)
+        }
+        ArchiveFormat::TarGz => {
+            let out = parent.join(format!("{name}.tar.gz"));
+            write_tar_gz(&out, &manifest, &files)?;
+            Ok(out)
+        }
+    }
+}
+
+/// Every file under `dir`, as (absolute, archive-relative) pairs in
+/// stable order. Also used by [`super::bundle`] to stage `.claudepack`
+/// contents.
+pub(crate) fn collect_files(dir: &Path) -> Result<Vec<(PathBuf, String)>> {
+    let mut files = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(current) = stack.pop() {
+        for entry in std::fs::read_dir(&current)
+            .with_context(|| format!("reading {}", current.display()))?
+        {
+            let path = entry?.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else {
+                let rel = path
+                    .strip_prefix(dir)
+                    .expect("walk stays under dir")
+                    .to_string_lossy()
+                    .into_owned();
+                files.push((path, rel));
+            }
+        }
+    }
+    files.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(files)
+}
+
+fn manifest(dir: &Path, files: &[(PathBuf, String)]) -> Result<String> {
+    let mut listed = Vec::new();
+    for (path, rel) in files {
+        let bytes = path
+            .metadata()
+            .with_context(|| format!("reading size of {}", path.display()))?
+            .len();
+        listed.push(serde_json::json!({ "path": rel, "bytes": bytes }));
+    }
+    let manifest = serde_json::json!({
+        "project": dir.file_name().map(|n| n.to_string_lossy()),
+        "created": chrono::Utc::now().to_rfc3339(),
+        "files": listed,
+    });
+    Ok(serde_json::to_string_pretty(&manifest)?)
+}
+
+fn write_zip(out: &Path, manifest: &str, files: &[(PathBuf, String)]) -> Result<()> {
+    use std::io::Write;
+
+    let file = std::fs::File::create(out)
+        .with_context(|| format!("creating {}", out.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file("manifest.json", options)?;
+    zip.write_all(manifest.as_bytes())?;
+    for (path, rel) in files {
+        zip.start_file(rel, options)?;
+        let contents = std::fs::read(path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        zip.write_all(&contents)?;
+    }
+    zip.finish().context("finalizing archive")?;
+    Ok(())
+}
+
+fn write_tar_gz(out: &Path, manifest: &str, files: &[(PathBuf, String)]) -> Result<()> {
+    let file = std::fs::File::create(out)
+        .with_context(|| format!("creating {}", out.display()))?;
+    let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    let mut tar = tar::Builder::new(encoder);
+
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    tar.append_data(&mut header, "manifest.json", manifest.as_bytes())?;
+    for (path, rel) in files {
+        tar.append_path_with_name(path, rel)
+            .with_context(|| format!("archiving {}", path.display()))?;
+    }
+    tar.into_inner()
+        .context("finalizing archive")?
+        .finish()
+        .context("flushing gzip stream")?;
+    Ok(())
+}
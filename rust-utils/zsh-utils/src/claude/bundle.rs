@@ -0,0 +1,181 @@
+//! The `.claudepack` session bundle — a portable interchange format
+//! for moving one session between machines.
+//!
+//! A bundle is a zip holding the raw transcript, the rendered Markdown
+//! and HTML (with snapshots and images), the stable JSON export, and a
+//! `manifest.json` describing all of it. `claude-export --bundle`
+//! writes one; `claude-import` unpacks it into another machine's
+//! transcript and export directories.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::export::{self, Exporter};
+use super::{archive, parser, sessions, site};
+
+/// Bumped only when an old importer could misread a new bundle.
+const VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+pub struct Manifest {
+    /// Always `"claudepack"`.
+    pub format: String,
+    pub version: u32,
+    pub session_id: String,
+    /// Project directory name as Claude Code encodes it, so the
+    /// transcript lands where the export tools will find it.
+    pub encoded_project: String,
+    /// Friendly project name, for humans and the export directory.
+    pub project: String,
+    pub created: String,
+    /// Rendered artifacts stored under `export/` in the zip.
+    pub files: Vec<String>,
+}
+
+/// Writes `session` as a self-contained bundle at `out`. The exporter
+/// should be rooted at a scratch directory — its rendered artifacts are
+/// staged there, then copied into the zip.
+pub fn export_bundle(
+    exporter: &Exporter,
+    session: &sessions::Session,
+    out: &Path,
+) -> Result<PathBuf> {
+    let md_path = exporter.export_markdown(session)?;
+    exporter.export_json(session)?;
+    let markdown = std::fs::read_to_string(&md_path)
+        .with_context(|| format!("reading {}", md_path.display()))?;
+    let title = markdown
+        .lines()
+        .next()
+        .map(|l| l.trim_start_matches("# "))
+        .unwrap_or(&session.id);
+    let html = site::page(title, &site::markdown_to_html(&markdown));
+    std::fs::write(
+        md_path.with_extension("html"),
+        html,
+    )
+    .context("writing bundled HTML")?;
+
+    let staged = exporter.session_dir(session);
+    let files = archive::collect_files(&staged)?;
+    let manifest = Manifest {
+        format: "claudepack".to_string(),
+        version: VERSION,
+        session_id: session.id.clone(),
+        encoded_project: session.project.encoded_name.clone(),
+        project: session.project.friendly_name(),
+        created: chrono::Utc::now().to_rfc3339(),
+        files: files.iter().map(|(_, rel)| rel.clone()).collect(),
+    };
+
+    let file = std::fs::File::create(out)
+        .with_context(|| format!("creating {}", out.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    zip.start_file("manifest.json", options)?;
+    zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+    zip.start_file("transcript.jsonl", options)?;
+    zip.write_all(
+        &std::fs::read(&session.path)
+            .with_context(|| format!("reading {}", session.path.display()))?,
+    )?;
+    for (path, rel) in &files {
+        zip.start_file(format!("export/{rel}"), options)?;
+        zip.write_all(
+            &std::fs::read(path)
+                .with_context(|| format!("reading {}", path.display()))?,
+        )?;
+    }
+    zip.finish().context("finalizing bundle")?;
+    Ok(out.to_path_buf())
+}
+
+/// Unpacks a bundle: the transcript goes into this machine's
+/// `~/.claude/projects` tree (refusing to clobber an existing session
+/// unless `force`), the rendered artifacts into the export directory.
+/// Rendered files are overwritten freely — they are derived data.
+pub fn import_bundle(path: &Path, force: bool) -> Result<Manifest> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("opening {}", path.display()))?;
+    let mut zip = zip::ZipArchive::new(file)
+        .with_context(|| format!("reading {} as a zip", path.display()))?;
+
+    let manifest: Manifest = {
+        let mut raw = String::new();
+        zip.by_name("manifest.json")
+            .context("bundle has no manifest.json")?
+            .read_to_string(&mut raw)?;
+        serde_json::from_str(&raw).context("parsing bundle manifest")?
+    };
+    anyhow::ensure!(
+        manifest.format == "claudepack",
+        "{} is not a claudepack bundle",
+        path.display()
+    );
+    anyhow::ensure!(
+        manifest.version <= VERSION,
+        "bundle version {} is newer than this tool understands",
+        manifest.version
+    );
+    // Transcripts and exports land in path components; a hostile
+    // manifest must not write outside them.
+    anyhow::ensure!(
+        !manifest.session_id.contains(['/', '\\'])
+            && !manifest.encoded_project.contains(['/', '\\'])
+            && !manifest.project.contains(['/', '\\']),
+        "bundle manifest contains path separators"
+    );
+
+    let transcript_path = sessions::claude_home()
+        .join("projects")
+        .join(&manifest.encoded_project)
+        .join(format!("{}.jsonl", manifest.session_id));
+    if transcript_path.is_file() && !force {
+        anyhow::bail!(
+            "session {} already exists at {} (use --force to overwrite)",
+            manifest.session_id,
+            transcript_path.display()
+        );
+    }
+    let mut transcript = Vec::new();
+    zip.by_name("transcript.jsonl")
+        .context("bundle has no transcript.jsonl")?
+        .read_to_end(&mut transcript)?;
+    // Catch corrupted bundles before anything is written.
+    let raw = std::str::from_utf8(&transcript)
+        .context("bundle transcript is not UTF-8")?;
+    anyhow::ensure!(
+        !parser::parse_str(raw).entries.is_empty(),
+        "bundle transcript holds no entries"
+    );
+    if let Some(dir) = transcript_path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("creating {}", dir.display()))?;
+    }
+    std::fs::write(&transcript_path, &transcript)
+        .with_context(|| format!("writing {}", transcript_path.display()))?;
+
+    let export_dir = export::export_root().join(&manifest.project);
+    for rel in &manifest.files {
+        anyhow::ensure!(
+            !rel.split(['/', '\\']).any(|part| part == ".."),
+            "bundle entry {rel:?} escapes the export directory"
+        );
+        let mut contents = Vec::new();
+        zip.by_name(&format!("export/{rel}"))
+            .with_context(|| format!("bundle is missing listed file {rel:?}"))?
+            .read_to_end(&mut contents)?;
+        let dest = export_dir.join(rel);
+        if let Some(dir) = dest.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("creating {}", dir.display()))?;
+        }
+        std::fs::write(&dest, contents)
+            .with_context(|| format!("writing {}", dest.display()))?;
+    }
+    Ok(manifest)
+}
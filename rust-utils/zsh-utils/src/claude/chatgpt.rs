@@ -0,0 +1,200 @@
+//! Importing ChatGPT export archives.
+//!
+//! OpenAI's data export is a zip holding `conversations.json`: an
+//! array of conversations whose messages form a tree (`mapping`,
+//! `current_node`) rather than a flat list. Like [`super::desktop`],
+//! the importer converts each conversation into transcript-shaped
+//! JSONL in a staging directory, so the regular exporters handle them
+//! without learning a third schema.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::sessions::{Project, Session};
+use crate::logger;
+
+#[derive(Deserialize)]
+struct Conversation {
+    id: Option<String>,
+    conversation_id: Option<String>,
+    title: Option<String>,
+    #[serde(default)]
+    mapping: HashMap<String, Node>,
+    current_node: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Node {
+    message: Option<ChatMessage>,
+    parent: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ChatMessage {
+    id: Option<String>,
+    author: Author,
+    content: Option<ChatContent>,
+    /// Unix seconds, fractional.
+    create_time: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct Author {
+    role: String,
+}
+
+#[derive(Deserialize)]
+struct ChatContent {
+    #[serde(default)]
+    parts: Vec<serde_json::Value>,
+}
+
+/// Converts every conversation in `archive` (the export zip, or a bare
+/// `conversations.json`) into staged sessions of a pseudo-project
+/// named `chatgpt`.
+pub fn stage(archive: &Path) -> Result<Vec<Session>> {
+    let raw = read_conversations(archive)?;
+    let conversations: Vec<Conversation> = serde_json::from_str(&raw)
+        .context("parsing conversations.json")?;
+
+    let dir = std::env::temp_dir().join("chatgpt-staging");
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("creating {}", dir.display()))?;
+    let project = Project {
+        encoded_name: "chatgpt".to_string(),
+        path: dir.clone(),
+    };
+
+    let mut sessions = Vec::new();
+    for conversation in conversations {
+        let Some(id) = conversation
+            .id
+            .clone()
+            .or_else(|| conversation.conversation_id.clone())
+        else {
+            logger::warn("skipping a conversation with no id");
+            continue;
+        };
+        let out = dir.join(format!("{id}.jsonl"));
+        std::fs::write(&out, to_jsonl(&id, &conversation))
+            .with_context(|| format!("writing {}", out.display()))?;
+        sessions.push(Session {
+            id,
+            path: out,
+            project: project.clone(),
+        });
+    }
+    Ok(sessions)
+}
+
+/// Pulls `conversations.json` out of the zip, or reads it directly
+/// when handed the file (or a directory it was unpacked into).
+fn read_conversations(archive: &Path) -> Result<String> {
+    if archive.is_dir() {
+        let inner = archive.join("conversations.json");
+        return std::fs::read_to_string(&inner)
+            .with_context(|| format!("reading {}", inner.display()));
+    }
+    if archive.extension().is_some_and(|e| e == "zip") {
+        let file = std::fs::File::open(archive)
+            .with_context(|| format!("opening {}", archive.display()))?;
+        let mut zip = zip::ZipArchive::new(file)
+            .with_context(|| format!("reading {} as a zip", archive.display()))?;
+        let mut raw = String::new();
+        zip.by_name("conversations.json")
+            .context("archive has no conversations.json")?
+            .read_to_string(&mut raw)?;
+        return Ok(raw);
+    }
+    std::fs::read_to_string(archive)
+        .with_context(|| format!("reading {}", archive.display()))
+}
+
+/// The active branch of the message tree, root first: walk the parent
+/// links up from `current_node`. Abandoned edit branches are exactly
+/// what the official UI hides, so they stay out of the export too.
+fn active_branch(conversation: &Conversation) -> Vec<&ChatMessage> {
+    let mut branch = Vec::new();
+    let mut cursor = conversation.current_node.as_deref();
+    while let Some(node_id) = cursor {
+        let Some(node) = conversation.mapping.get(node_id) else { break };
+        if let Some(message) = &node.message {
+            branch.push(message);
+        }
+        cursor = node.parent.as_deref();
+    }
+    branch.reverse();
+    branch
+}
+
+fn to_jsonl(id: &str, conversation: &Conversation) -> String {
+    let mut lines = String::new();
+    let branch = active_branch(conversation);
+    if let Some(title) = &conversation.title {
+        let leaf = branch.last().and_then(|m| m.id.clone());
+        lines.push_str(
+            &serde_json::json!({
+                "type": "summary",
+                "summary": title,
+                "leafUuid": leaf,
+            })
+            .to_string(),
+        );
+        lines.push('\n');
+    }
+    let mut parent: Option<String> = None;
+    for message in branch {
+        let kind = match message.author.role.as_str() {
+            "user" => "user",
+            "assistant" => "assistant",
+            // Tool plumbing and hidden system notes have no place in a
+            // readable archive.
+            _ => continue,
+        };
+        let text = message
+            .content
+            .as_ref()
+            .map(|content| {
+                content
+                    .parts
+                    .iter()
+                    .filter_map(|part| part.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .unwrap_or_default();
+        if text.is_empty() {
+            continue;
+        }
+        let timestamp = message.create_time.and_then(|seconds| {
+            chrono::DateTime::from_timestamp(
+                seconds as i64,
+                ((seconds.fract()) * 1e9) as u32,
+            )
+            .map(|t| t.to_rfc3339())
+        });
+        lines.push_str(
+            &serde_json::json!({
+                "type": kind,
+                "uuid": message.id,
+                "parentUuid": parent,
+                "sessionId": id,
+                "timestamp": timestamp,
+                "message": {
+                    "role": kind,
+                    "content": [{ "type": "text", "text": text }],
+                },
+            })
+            .to_string(),
+        );
+        lines.push('\n');
+        if message.id.is_some() {
+            parent = message.id.clone();
+        }
+    }
+    lines
+}
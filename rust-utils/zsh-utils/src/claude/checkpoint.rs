@@ -0,0 +1,119 @@
+//! Resume support for interrupted bulk exports.
+//!
+//! A big `--all` run can take minutes; a Ctrl-C halfway through used
+//! to mean starting over. The checkpoint lives at the export root and
+//! records, per project, which sessions this run already finished; a
+//! re-run skips those and picks up where the last one stopped. A run
+//! that completes removes the file, so a missing checkpoint means
+//! "nothing to resume".
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+const CHECKPOINT_FILE: &str = ".export-checkpoint.json";
+
+#[derive(Default, Serialize, Deserialize)]
+struct State {
+    /// Output format the interrupted run used. A resume in a different
+    /// format would skip sessions it never actually produced, so a
+    /// mismatch discards the checkpoint instead.
+    format: String,
+    /// Finished session ids, keyed by encoded project name.
+    done: BTreeMap<String, BTreeSet<String>>,
+}
+
+pub struct Checkpoint {
+    path: PathBuf,
+    state: State,
+    /// Whether a checkpoint for this format was already on disk.
+    resumed: bool,
+}
+
+impl Checkpoint {
+    /// Loads the checkpoint at the export root. Missing, corrupt, or
+    /// differently-formatted checkpoints start empty.
+    pub fn load(root: &Path, format: &str) -> Self {
+        let path = root.join(CHECKPOINT_FILE);
+        let state: Option<State> = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .filter(|state: &State| state.format == format);
+        let resumed = state.is_some();
+        Self {
+            path,
+            state: state.unwrap_or_else(|| State {
+                format: format.to_string(),
+                done: BTreeMap::new(),
+            }),
+            resumed,
+        }
+    }
+
+    /// Whether an interrupted run left work behind to resume.
+    pub fn resuming(&self) -> bool {
+        self.resumed
+    }
+
+    /// Sessions the interrupted run already finished.
+    pub fn done(&self) -> usize {
+        self.state.done.values().map(BTreeSet::len).sum()
+    }
+
+    pub fn is_done(&self, project: &str, session: &str) -> bool {
+        self.state
+            .done
+            .get(project)
+            .is_some_and(|ids| ids.contains(session))
+    }
+
+    /// Records a finished session and persists immediately — the whole
+    /// point is surviving an abort at an arbitrary moment.
+    pub fn mark_done(&mut self, project: &str, session: &str) -> Result<()> {
+        self.state
+            .done
+            .entry(project.to_string())
+            .or_default()
+            .insert(session.to_string());
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("creating {}", dir.display()))?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(&self.state)?)
+            .with_context(|| format!("writing {}", self.path.display()))
+    }
+
+    /// Marks the run complete: the next run starts fresh.
+    pub fn finish(self) -> Result<()> {
+        if self.path.is_file() {
+            std::fs::remove_file(&self.path)
+                .with_context(|| format!("removing {}", self.path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Removes a directory without ever leaving it half-deleted in place:
+/// it is atomically renamed aside first, then deleted under the
+/// throwaway name. A crash mid-delete leaves either the intact
+/// original or a `.deleting` leftover that the next call sweeps up —
+/// never a live output directory missing a random subset of files.
+pub fn clean_dir(dir: &Path) -> Result<()> {
+    let Some(name) = dir.file_name() else {
+        anyhow::bail!("refusing to clean {}", dir.display());
+    };
+    let trash = dir.with_file_name(format!("{}.deleting", name.to_string_lossy()));
+    if trash.is_dir() {
+        std::fs::remove_dir_all(&trash)
+            .with_context(|| format!("removing {}", trash.display()))?;
+    }
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    std::fs::rename(dir, &trash)
+        .with_context(|| format!("moving {} aside", dir.display()))?;
+    std::fs::remove_dir_all(&trash)
+        .with_context(|| format!("removing {}", trash.display()))
+}
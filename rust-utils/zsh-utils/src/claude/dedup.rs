@@ -0,0 +1,59 @@
+//! Content-hash dedup across export runs.
+//!
+//! Re-running an export into a fresh directory must not duplicate
+//! megabytes of identical transcripts under slightly different names.
+//! The index lives at the export root and maps a hash of each source
+//! transcript (plus the output format) to the artifact already written
+//! for it; exporters consult it and skip instead of re-rendering.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+
+const INDEX_FILE: &str = ".export-hashes.json";
+
+pub struct DedupIndex {
+    path: PathBuf,
+    /// `<format>:<sha256>` → artifact path relative to the export root.
+    entries: BTreeMap<String, String>,
+}
+
+impl DedupIndex {
+    /// Loads the index at the export root. Missing or corrupt index
+    /// files start empty rather than blocking the export.
+    pub fn load(root: &Path) -> Self {
+        let path = root.join(INDEX_FILE);
+        let entries = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default();
+        Self { path, entries }
+    }
+
+    pub fn lookup(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    /// Records an exported artifact and persists immediately — batch
+    /// exports can abort halfway, and a stale index re-duplicates.
+    pub fn record(&mut self, key: String, artifact: String) -> Result<()> {
+        self.entries.insert(key, artifact);
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("creating {}", dir.display()))?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(&self.entries)?)
+            .with_context(|| format!("writing {}", self.path.display()))
+    }
+}
+
+/// The index key for one (transcript, format) pair. Hashing the raw
+/// source file catches identical sessions regardless of file name or
+/// timestamps.
+pub fn key(transcript: &Path, format: &str) -> Result<String> {
+    let raw = std::fs::read(transcript)
+        .with_context(|| format!("reading {}", transcript.display()))?;
+    Ok(format!("{format}:{:x}", Sha256::digest(&raw)))
+}
@@ -0,0 +1,149 @@
+//! Reading Claude Desktop's local conversation store.
+//!
+//! Desktop keeps chats in a different place and shape than Claude
+//! Code: one JSON document per conversation, with a `chat_messages`
+//! array instead of a JSONL transcript. Rather than teach every
+//! exporter a second schema, conversations are converted into
+//! transcript-shaped JSONL in a staging directory and flow through the
+//! same pipeline as any other [`Session`].
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::sessions::{Project, Session};
+use crate::logger;
+
+/// Root of the Claude Desktop data directory, `$CLAUDE_DESKTOP_HOME`
+/// or the platform application-support directory.
+pub fn desktop_home() -> PathBuf {
+    std::env::var("CLAUDE_DESKTOP_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| dirs::config_dir().unwrap_or_default().join("Claude"))
+}
+
+/// One conversation document as Desktop writes it.
+#[derive(Deserialize)]
+struct Conversation {
+    uuid: String,
+    name: Option<String>,
+    #[serde(default)]
+    chat_messages: Vec<ChatMessage>,
+}
+
+#[derive(Deserialize)]
+struct ChatMessage {
+    uuid: Option<String>,
+    /// `"human"` or `"assistant"`.
+    sender: String,
+    #[serde(default)]
+    text: String,
+    created_at: Option<String>,
+}
+
+/// Whether this machine has a Desktop conversation store at all.
+pub fn available() -> bool {
+    desktop_home().join("conversations").is_dir()
+}
+
+/// Converts every Desktop conversation into transcript-shaped JSONL
+/// under a staging directory and returns them as sessions of a
+/// pseudo-project named `desktop`. The staging directory lives under
+/// the system temp dir and is simply rewritten on each run.
+pub fn stage() -> Result<Vec<Session>> {
+    let source = desktop_home().join("conversations");
+    anyhow::ensure!(
+        source.is_dir(),
+        "no Claude Desktop conversations at {}",
+        source.display()
+    );
+    let dir = std::env::temp_dir().join("claude-desktop-staging");
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("creating {}", dir.display()))?;
+    let project = Project {
+        encoded_name: "desktop".to_string(),
+        path: dir.clone(),
+    };
+
+    let mut sessions = Vec::new();
+    for path in std::fs::read_dir(&source)
+        .with_context(|| format!("reading {}", source.display()))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "json"))
+    {
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        let conversation: Conversation = match serde_json::from_str(&raw) {
+            Ok(conversation) => conversation,
+            // One odd document must not hide the rest of the store.
+            Err(err) => {
+                logger::warn(format!(
+                    "skipping {}: not a Desktop conversation ({err})",
+                    path.display()
+                ));
+                continue;
+            }
+        };
+        let out = dir.join(format!("{}.jsonl", conversation.uuid));
+        std::fs::write(&out, to_jsonl(&conversation))
+            .with_context(|| format!("writing {}", out.display()))?;
+        sessions.push(Session {
+            id: conversation.uuid,
+            path: out,
+            project: project.clone(),
+        });
+    }
+    Ok(sessions)
+}
+
+/// Rewrites one conversation in the Claude Code transcript shape
+/// ([`super::models`]): an optional summary line carrying the chat
+/// name, then one entry per message with the uuids chained through
+/// `parentUuid`.
+fn to_jsonl(conversation: &Conversation) -> String {
+    let mut lines = String::new();
+    if let Some(name) = &conversation.name {
+        let leaf = conversation
+            .chat_messages
+            .last()
+            .and_then(|m| m.uuid.clone());
+        lines.push_str(
+            &serde_json::json!({
+                "type": "summary",
+                "summary": name,
+                "leafUuid": leaf,
+            })
+            .to_string(),
+        );
+        lines.push('\n');
+    }
+    let mut parent: Option<String> = None;
+    for message in &conversation.chat_messages {
+        let kind = if message.sender == "assistant" {
+            "assistant"
+        } else {
+            "user"
+        };
+        lines.push_str(
+            &serde_json::json!({
+                "type": kind,
+                "uuid": message.uuid,
+                "parentUuid": parent,
+                "sessionId": conversation.uuid,
+                "timestamp": message.created_at,
+                "message": {
+                    "role": kind,
+                    "content": [{ "type": "text", "text": message.text }],
+                },
+            })
+            .to_string(),
+        );
+        lines.push('\n');
+        if message.uuid.is_some() {
+            parent = message.uuid.clone();
+        }
+    }
+    lines
+}
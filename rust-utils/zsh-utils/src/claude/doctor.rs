@@ -0,0 +1,153 @@
+//! Health check over the transcript store for `claude-doctor`.
+//!
+//! The parser is deliberately forgiving: malformed lines are skipped
+//! and unknown entry types collapse into `Unknown`, so a damaged store
+//! exports quietly minus whatever was damaged. This module takes the
+//! opposite stance — walk every transcript and say exactly what the
+//! tolerant path is papering over, with counts and locations.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::BufRead;
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use super::sessions;
+
+/// Entry types [`super::models::TranscriptEntry`] decodes into a real
+/// variant. Anything else lands in `Unknown` and vanishes from exports.
+const MODELED_TYPES: &[&str] = &["summary", "user", "assistant", "system"];
+
+/// File-history snapshots are unmodeled on purpose (exports rebuild
+/// histories from tool uses instead), so they are checked for
+/// referential integrity rather than reported as unknown.
+const SNAPSHOT_TYPE: &str = "file_history_snapshot";
+
+/// Everything the check found wrong with one transcript file.
+pub struct FileReport {
+    pub path: PathBuf,
+    pub project: String,
+    /// 1-based numbers of lines that are not valid JSON or carry no
+    /// `type` — the lines `parse_str` counts as skipped.
+    pub malformed: Vec<usize>,
+    /// Entry types outside the modeled set, with occurrence counts.
+    pub unknown_types: BTreeMap<String, usize>,
+    /// `messageId`s of snapshot entries that reference no entry `uuid`
+    /// anywhere in the file.
+    pub orphaned_snapshots: Vec<String>,
+    /// Set when the file has entries but none carries a timestamp, so
+    /// [`sessions::Session::start_time`] comes back empty and every
+    /// date filter waves the session through.
+    pub missing_timestamps: bool,
+}
+
+impl FileReport {
+    pub fn healthy(&self) -> bool {
+        self.malformed.is_empty()
+            && self.unknown_types.is_empty()
+            && self.orphaned_snapshots.is_empty()
+            && !self.missing_timestamps
+    }
+}
+
+/// Findings across the whole store. `files` holds only the transcripts
+/// with something wrong; healthy ones just bump the counters.
+pub struct Report {
+    pub files: Vec<FileReport>,
+    pub sessions_checked: usize,
+    pub projects_checked: usize,
+}
+
+impl Report {
+    pub fn healthy(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Walks every project under the Claude home and checks each
+/// transcript. An unreadable file shows up as one malformed "line"
+/// rather than aborting the walk — the whole point is a complete
+/// picture of a possibly damaged store.
+pub fn check() -> Result<Report> {
+    let mut report = Report {
+        files: Vec::new(),
+        sessions_checked: 0,
+        projects_checked: 0,
+    };
+    for project in sessions::projects()? {
+        report.projects_checked += 1;
+        let name = project.friendly_name();
+        for session in project.sessions()? {
+            report.sessions_checked += 1;
+            let file = check_file(session.path, name.clone());
+            if !file.healthy() {
+                report.files.push(file);
+            }
+        }
+    }
+    Ok(report)
+}
+
+fn check_file(path: PathBuf, project: String) -> FileReport {
+    let mut report = FileReport {
+        path,
+        project,
+        malformed: Vec::new(),
+        unknown_types: BTreeMap::new(),
+        orphaned_snapshots: Vec::new(),
+        missing_timestamps: false,
+    };
+    let file = match std::fs::File::open(&report.path) {
+        Ok(file) => file,
+        Err(_) => {
+            report.malformed.push(1);
+            return report;
+        }
+    };
+
+    let mut uuids = BTreeSet::new();
+    let mut snapshots: Vec<String> = Vec::new();
+    let mut entries = 0usize;
+    let mut saw_timestamp = false;
+    for (number, line) in std::io::BufReader::new(file).lines().enumerate() {
+        let number = number + 1;
+        let Ok(line) = line else {
+            report.malformed.push(number);
+            continue;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(&line) else {
+            report.malformed.push(number);
+            continue;
+        };
+        let Some(kind) = value.get("type").and_then(|t| t.as_str()) else {
+            // No type tag: serde can't dispatch it, so the parser
+            // skips it just like invalid JSON.
+            report.malformed.push(number);
+            continue;
+        };
+        entries += 1;
+        if let Some(uuid) = value.get("uuid").and_then(|u| u.as_str()) {
+            uuids.insert(uuid.to_string());
+        }
+        if value.get("timestamp").and_then(|t| t.as_str()).is_some() {
+            saw_timestamp = true;
+        }
+        if kind == SNAPSHOT_TYPE {
+            if let Some(id) = value.get("messageId").and_then(|m| m.as_str()) {
+                snapshots.push(id.to_string());
+            }
+        } else if !MODELED_TYPES.contains(&kind) {
+            *report.unknown_types.entry(kind.to_string()).or_default() += 1;
+        }
+    }
+
+    report.orphaned_snapshots = snapshots
+        .into_iter()
+        .filter(|id| !uuids.contains(id))
+        .collect();
+    report.missing_timestamps = entries > 0 && !saw_timestamp;
+    report
+}
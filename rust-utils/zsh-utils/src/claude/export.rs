@@ -0,0 +1,1470 @@
+//! Rendering sessions into the export directory as Markdown.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use super::anonymize::Anonymizer;
+use super::models::{ContentBlock, MessageContent, TranscriptEntry};
+use super::parser::{self, Transcript};
+use super::pricing::Pricing;
+use super::sessions::{Project, Session};
+use super::snapshots::SnapshotPolicy;
+use super::tags::Tags;
+use crate::llm::{ChatMessage, LLMClient};
+
+/// Root of the export tree, `$CLAUDE_EXPORT_DIR` or `~/claude-exports`.
+pub fn export_root() -> PathBuf {
+    std::env::var("CLAUDE_EXPORT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| dirs::home_dir().unwrap_or_default().join("claude-exports"))
+}
+
+/// Whether a path lives inside a cloud-synced directory (iCloud or
+/// Dropbox), where full-speed writes make the sync client fall behind.
+pub fn looks_synced(path: &std::path::Path) -> bool {
+    path.components().any(|c| {
+        let part = c.as_os_str().to_string_lossy();
+        part == "Dropbox" || part == "Mobile Documents" || part == "com~apple~CloudDocs"
+    })
+}
+
+pub struct Exporter {
+    out_root: PathBuf,
+    pricing: Pricing,
+    summarizer: Option<LLMClient>,
+    /// Encoded project name → display name, from [`sessions::unique_names`].
+    project_names: BTreeMap<String, String>,
+    snapshots: bool,
+    snapshot_policy: SnapshotPolicy,
+    synced: bool,
+    /// Pause inserted between artifact writes, for outputs whose sync
+    /// client cannot keep up with full-speed exports.
+    io_throttle: Option<std::time::Duration>,
+    render_options: RenderOptions,
+    timeline: bool,
+    git_log: bool,
+    /// Tera template source replacing the built-in Markdown layout.
+    template: Option<String>,
+    /// Scrubs machine-identifying details from everything written.
+    anonymizer: Option<Anonymizer>,
+    /// Session tags folded into every format's metadata (see
+    /// [`super::tags`]).
+    tags: Tags,
+}
+
+impl Exporter {
+    pub fn new() -> Self {
+        Self {
+            out_root: export_root(),
+            pricing: Pricing::builtin(),
+            summarizer: None,
+            project_names: BTreeMap::new(),
+            snapshots: false,
+            snapshot_policy: SnapshotPolicy::default(),
+            synced: false,
+            io_throttle: None,
+            render_options: RenderOptions::default(),
+            timeline: false,
+            git_log: false,
+            template: None,
+            anonymizer: None,
+            tags: Tags::load(),
+        }
+    }
+
+    pub fn with_root(out_root: PathBuf) -> Self {
+        Self {
+            out_root,
+            pricing: Pricing::builtin(),
+            summarizer: None,
+            project_names: BTreeMap::new(),
+            snapshots: false,
+            snapshot_policy: SnapshotPolicy::default(),
+            synced: false,
+            io_throttle: None,
+            render_options: RenderOptions::default(),
+            timeline: false,
+            git_log: false,
+            template: None,
+            anonymizer: None,
+            tags: Tags::load(),
+        }
+    }
+
+    /// Replaces the built-in pricing table (e.g. from `--pricing-file`).
+    pub fn with_pricing(mut self, pricing: Pricing) -> Self {
+        self.pricing = pricing;
+        self
+    }
+
+    /// Enables LLM-written summary sections in Markdown exports.
+    pub fn with_summarizer(mut self, client: LLMClient) -> Self {
+        self.summarizer = Some(client);
+        self
+    }
+
+    /// Collision-free project display names (see
+    /// [`super::sessions::unique_names`]); unmapped projects fall back
+    /// to their friendly name.
+    pub fn with_project_names(mut self, names: BTreeMap<String, String>) -> Self {
+        self.project_names = names;
+        self
+    }
+
+    /// Enables diff-based file snapshots next to Markdown exports.
+    pub fn with_snapshots(mut self, enabled: bool) -> Self {
+        self.snapshots = enabled;
+        self
+    }
+
+    /// Retention policy applied to snapshots (version caps, size
+    /// limits, binary detection).
+    pub fn with_snapshot_policy(mut self, policy: SnapshotPolicy) -> Self {
+        self.snapshot_policy = policy;
+        self
+    }
+
+    /// Output strategy for synced directories (iCloud, Dropbox):
+    /// snapshots go into one zip per session instead of many small
+    /// files, and text artifacts are written atomically so sync
+    /// clients never pick up half a file.
+    pub fn with_synced(mut self, enabled: bool) -> Self {
+        self.synced = enabled;
+        self
+    }
+
+    /// Inserts a pause between artifact writes so iCloud/Dropbox sync
+    /// clients keep up instead of pegging a core.
+    pub fn with_io_throttle(mut self, delay: Option<std::time::Duration>) -> Self {
+        self.io_throttle = delay;
+        self
+    }
+
+    /// Controls which tool calls and tool results appear in rendered
+    /// Markdown, and how much of each result is kept.
+    pub fn with_render_options(mut self, options: RenderOptions) -> Self {
+        self.render_options = options;
+        self
+    }
+
+    /// Embeds a Mermaid gantt timeline of turns and tool invocations in
+    /// Markdown exports (see [`super::timeline`]).
+    pub fn with_timeline(mut self, enabled: bool) -> Self {
+        self.timeline = enabled;
+        self
+    }
+
+    /// Adds a "Commits during session" section from `git log` over the
+    /// session's time window in its recorded cwd (see
+    /// [`super::gitlog`]).
+    pub fn with_git_log(mut self, enabled: bool) -> Self {
+        self.git_log = enabled;
+        self
+    }
+
+    /// Replaces the built-in Markdown layout with a user-supplied Tera
+    /// template (see [`render_template`] for the context it receives).
+    /// The summary/timeline sections only apply to the built-in layout;
+    /// a template controls its own sections.
+    pub fn with_template(mut self, source: String) -> Self {
+        self.template = Some(source);
+        self
+    }
+
+    /// Scrub the home directory, username, hostname, and email
+    /// addresses from every written artifact (see [`super::anonymize`]).
+    pub fn with_anonymize(mut self, enabled: bool) -> Self {
+        self.anonymizer = enabled.then(Anonymizer::from_environment);
+        self
+    }
+
+    fn pace(&self) {
+        if let Some(delay) = self.io_throttle {
+            std::thread::sleep(delay);
+        }
+    }
+
+    /// Directory a given session's artifacts land in (one per project).
+    pub fn session_dir(&self, session: &Session) -> PathBuf {
+        self.project_dir(&session.project)
+    }
+
+    /// Directory a project's exports land in.
+    pub fn project_dir(&self, project: &Project) -> PathBuf {
+        let name = self
+            .project_names
+            .get(&project.encoded_name)
+            .cloned()
+            .unwrap_or_else(|| project.friendly_name());
+        self.out_root.join(name)
+    }
+
+    /// Renders the session to `<project>/<session-id>.md` and returns
+    /// the written path.
+    pub fn export_markdown(&self, session: &Session) -> Result<PathBuf> {
+        let rendered = self.assemble_markdown(session)?;
+        let out = self.session_dir(session).join(format!("{}.md", session.id));
+        self.write_text(&out, &rendered)?;
+        Ok(out)
+    }
+
+    /// Renders a chain of resumed sessions (see [`super::merge`]) into
+    /// one document under the first session's name, each continuation
+    /// separated by a "Resumed" heading.
+    pub fn export_merged(&self, chain: &[Session]) -> Result<PathBuf> {
+        let first = chain.first().context("empty session chain")?;
+        let mut rendered = self.assemble_markdown(first)?;
+        for session in &chain[1..] {
+            let doc = self.assemble_markdown(session)?;
+            // Keep only the conversation onwards; the continuation's
+            // header would just repeat the chain's context.
+            let body = doc
+                .find("## Conversation")
+                .map_or(doc.as_str(), |at| &doc[at..]);
+            let when = session
+                .start_time()
+                .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+                .unwrap_or_else(|| "later".to_string());
+            rendered.push_str(&format!(
+                "\n---\n\n## Resumed {when} *(session {})*\n\n",
+                session.id
+            ));
+            rendered.push_str(body);
+        }
+        let out = self.session_dir(first).join(format!("{}.md", first.id));
+        self.write_text(&out, &rendered)?;
+        Ok(out)
+    }
+
+    /// The full Markdown document for one session, with every side
+    /// artifact (snapshots, images) already written next to it.
+    fn assemble_markdown(&self, session: &Session) -> Result<String> {
+        let transcript = parser::parse_file(&session.path)?;
+        let tags = self.tags.for_session(&session.id);
+        let mut rendered = match &self.template {
+            Some(template) => render_template(
+                template,
+                session,
+                &transcript,
+                &self.pricing,
+                &self.render_options,
+                &tags,
+            )?,
+            None => render_markdown(
+                session,
+                &transcript,
+                &self.pricing,
+                &self.render_options,
+                &tags,
+            ),
+        };
+        if self.template.is_none() {
+            if let Some(client) = &self.summarizer {
+                let sections = summary_sections(client, &transcript)?;
+                let at = rendered
+                    .find("## Conversation")
+                    .unwrap_or(rendered.len());
+                rendered.insert_str(at, &sections);
+            }
+            if self.timeline {
+                if let Some(section) = super::timeline::render_section(&transcript) {
+                    let at = rendered
+                        .find("## Conversation")
+                        .unwrap_or(rendered.len());
+                    rendered.insert_str(at, &section);
+                }
+            }
+            if self.git_log {
+                if let Some(section) = super::gitlog::render_section(&transcript) {
+                    let at = rendered
+                        .find("## Conversation")
+                        .unwrap_or(rendered.len());
+                    rendered.insert_str(at, &section);
+                }
+            }
+        }
+        let dir = self.session_dir(session);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("creating {}", dir.display()))?;
+        let want_files = self.snapshots && self.render_options.sections.files;
+        if want_files && self.synced {
+            let zip_name = format!("{}-files.zip", session.id);
+            let index = super::snapshots::export_snapshots_zip(
+                &transcript,
+                &dir.join(&zip_name),
+                &self.snapshot_policy,
+            )?;
+            if !index.is_empty() {
+                rendered.push_str(&super::snapshots::render_zip_section(&index, &zip_name));
+            }
+            self.pace();
+        } else if want_files {
+            let files_dir = dir.join(format!("{}-files", session.id));
+            let snapshots = super::snapshots::export_snapshots(
+                &transcript,
+                &files_dir,
+                &self.snapshot_policy,
+            )?;
+            if !snapshots.is_empty() {
+                rendered.push_str(&super::snapshots::render_section(&snapshots, &dir));
+            }
+            self.pace();
+        }
+        // Image links are emitted unconditionally, so the files must
+        // exist whether or not --snapshots is on.
+        let files_dir = dir.join(format!("{}-files", session.id));
+        if export_images(&transcript, &files_dir)? > 0 {
+            self.pace();
+        }
+        if export_tool_results(
+            &transcript,
+            &files_dir,
+            self.render_options.max_tool_result,
+        )? > 0
+        {
+            self.pace();
+        }
+        Ok(rendered)
+    }
+
+    /// Atomic (write-then-rename) in synced mode, plain write otherwise.
+    /// Both flavors respect the io throttle, and `--anonymize` scrubs
+    /// here so every text format goes through one funnel.
+    fn write_text(&self, path: &std::path::Path, contents: &str) -> Result<()> {
+        self.pace();
+        let scrubbed = self.anonymizer.as_ref().map(|a| a.scrub(contents));
+        let contents = scrubbed.as_deref().unwrap_or(contents);
+        if !self.synced {
+            return std::fs::write(path, contents)
+                .with_context(|| format!("writing {}", path.display()));
+        }
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, contents)
+            .with_context(|| format!("writing {}", tmp.display()))?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("renaming into {}", path.display()))
+    }
+
+    /// Serializes the session to `<project>/<session-id>.json` using
+    /// the stable schema in [`JsonExport`].
+    pub fn export_json(&self, session: &Session) -> Result<PathBuf> {
+        let transcript = parser::parse_file(&session.path)?;
+        let export = build_json(
+            session,
+            &transcript,
+            &self.pricing,
+            self.tags.for_session(&session.id),
+        );
+        let dir = self.session_dir(session);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("creating {}", dir.display()))?;
+        let out = dir.join(format!("{}.json", session.id));
+        self.write_text(&out, &serde_json::to_string_pretty(&export)?)?;
+        Ok(out)
+    }
+
+    /// Typesets the session to `<project>/<session-id>.pdf` through an
+    /// external HTML-to-PDF converter (see [`super::pdf`]).
+    pub fn export_pdf(&self, session: &Session) -> Result<PathBuf> {
+        let mut rendered = self.assemble_markdown(session)?;
+        // PDFs bypass `write_text`, so scrub here.
+        if let Some(anonymizer) = &self.anonymizer {
+            rendered = anonymizer.scrub(&rendered);
+        }
+        let title = rendered
+            .lines()
+            .next()
+            .map(|l| l.trim_start_matches("# "))
+            .unwrap_or(&session.id);
+        let out = self.session_dir(session).join(format!("{}.pdf", session.id));
+        super::pdf::export_pdf(title, &rendered, &out)?;
+        self.pace();
+        Ok(out)
+    }
+
+    /// Renders the session to `<project>/<session-id>.org` in the
+    /// Emacs org-mode layout (see [`super::org`]).
+    pub fn export_org(&self, session: &Session) -> Result<PathBuf> {
+        let transcript = parser::parse_file(&session.path)?;
+        let rendered = super::org::render_org(
+            session,
+            &transcript,
+            &self.pricing,
+            &self.render_options,
+            &self.tags.for_session(&session.id),
+        );
+        let dir = self.session_dir(session);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("creating {}", dir.display()))?;
+        let out = dir.join(format!("{}.org", session.id));
+        self.write_text(&out, &rendered)?;
+        Ok(out)
+    }
+
+    /// Writes one normalized [`JsonlRecord`] per message to
+    /// `<project>/<session-id>.jsonl`.
+    pub fn export_jsonl(&self, session: &Session) -> Result<PathBuf> {
+        let transcript = parser::parse_file(&session.path)?;
+        let mut lines = String::new();
+        for record in build_jsonl(session, &transcript) {
+            lines.push_str(&serde_json::to_string(&record)?);
+            lines.push('\n');
+        }
+        let dir = self.session_dir(session);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("creating {}", dir.display()))?;
+        let out = dir.join(format!("{}.jsonl", session.id));
+        self.write_text(&out, &lines)?;
+        Ok(out)
+    }
+}
+
+// --- JSON export ---------------------------------------------------------
+//
+// The JSON schema is a contract for downstream scripting: fields are
+// only ever added, never renamed or removed.
+//
+// The model borrows from the parsed transcript: large text — thinking
+// blocks, tool inputs, message bodies — is serialized straight out of
+// the entries instead of being copied into the export model first, so
+// a huge session doesn't briefly exist in memory twice.
+
+#[derive(Serialize)]
+pub struct JsonExport<'a> {
+    pub schema_version: u32,
+    pub session_id: &'a str,
+    pub project: String,
+    /// Tags attached with `claude-export <session> --tag ...` (see
+    /// [`super::tags`]); empty for untagged sessions.
+    pub tags: Vec<String>,
+    pub entry_count: usize,
+    pub model: Option<&'a str>,
+    pub token_stats: TokenStats,
+    /// Estimated USD cost, priced per message model; unpriced models
+    /// contribute nothing and are listed separately.
+    pub estimated_cost_usd: f64,
+    pub cost_by_model: BTreeMap<String, f64>,
+    pub messages: Vec<JsonMessage<'a>>,
+    /// Files touched by tools, mapped to how often they came up.
+    pub files: BTreeMap<String, u32>,
+}
+
+#[derive(Serialize, Default)]
+pub struct TokenStats {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_input_tokens: u64,
+    pub cache_read_input_tokens: u64,
+}
+
+#[derive(Serialize)]
+pub struct JsonMessage<'a> {
+    pub role: &'a str,
+    pub timestamp: Option<&'a str>,
+    pub text: std::borrow::Cow<'a, str>,
+    pub tool_uses: Vec<JsonToolUse<'a>>,
+}
+
+#[derive(Serialize)]
+pub struct JsonToolUse<'a> {
+    pub name: &'a str,
+    pub input: &'a serde_json::Value,
+}
+
+pub fn build_json<'a>(
+    session: &'a Session,
+    transcript: &'a Transcript,
+    pricing: &Pricing,
+    tags: Vec<String>,
+) -> JsonExport<'a> {
+    let estimate = pricing.estimate(transcript);
+    let mut token_stats = TokenStats::default();
+    let mut messages = Vec::new();
+    let mut files: BTreeMap<String, u32> = BTreeMap::new();
+    let mut model = None;
+
+    for entry in &transcript.entries {
+        let role = match entry {
+            TranscriptEntry::User { .. } => "user",
+            TranscriptEntry::Assistant { .. } => "assistant",
+            _ => continue,
+        };
+        let message = entry.message().expect("user/assistant have messages");
+        if model.is_none() {
+            model = message.model.as_deref();
+        }
+        if let Some(usage) = &message.usage {
+            token_stats.input_tokens += usage.input_tokens.unwrap_or(0);
+            token_stats.output_tokens += usage.output_tokens.unwrap_or(0);
+            token_stats.cache_creation_input_tokens +=
+                usage.cache_creation_input_tokens.unwrap_or(0);
+            token_stats.cache_read_input_tokens +=
+                usage.cache_read_input_tokens.unwrap_or(0);
+        }
+
+        let mut tool_uses = Vec::new();
+        if let MessageContent::Blocks(blocks) = &message.content {
+            for block in blocks {
+                if let ContentBlock::ToolUse { name, input, .. } = block {
+                    if let Some(path) = input.get("file_path").and_then(|p| p.as_str()) {
+                        *files.entry(path.to_string()).or_default() += 1;
+                    }
+                    tool_uses.push(JsonToolUse { name, input });
+                }
+            }
+        }
+        messages.push(JsonMessage {
+            role,
+            timestamp: entry.meta().and_then(|m| m.timestamp.as_deref()),
+            text: message.content.plain_text(),
+            tool_uses,
+        });
+    }
+
+    JsonExport {
+        schema_version: 1,
+        session_id: &session.id,
+        project: session.project.friendly_name(),
+        tags,
+        entry_count: transcript.entries.len(),
+        model,
+        token_stats,
+        estimated_cost_usd: estimate.total_usd,
+        cost_by_model: estimate.by_model,
+        messages,
+        files,
+    }
+}
+
+/// One flat record per message for `--format jsonl` — the shape DuckDB
+/// and pandas ingest directly, no nested blocks to unpack. Same
+/// stability contract as [`JsonExport`]: fields only get added.
+#[derive(Serialize)]
+pub struct JsonlRecord<'a> {
+    pub session_id: &'a str,
+    pub project: String,
+    pub role: &'a str,
+    pub timestamp: Option<&'a str>,
+    pub model: Option<&'a str>,
+    pub text: std::borrow::Cow<'a, str>,
+    /// Names of the tools this message invoked, in order.
+    pub tools: Vec<&'a str>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+pub fn build_jsonl<'a>(
+    session: &'a Session,
+    transcript: &'a Transcript,
+) -> Vec<JsonlRecord<'a>> {
+    let project = session.project.friendly_name();
+    let mut records = Vec::new();
+    for entry in &transcript.entries {
+        let role = match entry {
+            TranscriptEntry::User { .. } => "user",
+            TranscriptEntry::Assistant { .. } => "assistant",
+            _ => continue,
+        };
+        let message = entry.message().expect("user/assistant have messages");
+        let mut tools = Vec::new();
+        if let MessageContent::Blocks(blocks) = &message.content {
+            for block in blocks {
+                if let ContentBlock::ToolUse { name, .. } = block {
+                    tools.push(name.as_str());
+                }
+            }
+        }
+        records.push(JsonlRecord {
+            session_id: &session.id,
+            project: project.clone(),
+            role,
+            timestamp: entry.meta().and_then(|m| m.timestamp.as_deref()),
+            model: message.model.as_deref(),
+            text: message.content.plain_text(),
+            tools,
+            input_tokens: message
+                .usage
+                .as_ref()
+                .and_then(|u| u.input_tokens)
+                .unwrap_or(0),
+            output_tokens: message
+                .usage
+                .as_ref()
+                .and_then(|u| u.output_tokens)
+                .unwrap_or(0),
+        });
+    }
+    records
+}
+
+impl Default for Exporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Renders a user-supplied Tera template instead of the built-in
+/// layout. The context carries:
+///
+/// * `session`  — the full [`JsonExport`] model (id, project, token
+///   stats, cost, messages with their tool uses, touched files)
+/// * `markdown` — the built-in rendering, for templates that only wrap
+///   it in frontmatter
+pub fn render_template(
+    template: &str,
+    session: &Session,
+    transcript: &Transcript,
+    pricing: &Pricing,
+    options: &RenderOptions,
+    tags: &[String],
+) -> Result<String> {
+    let mut tera = tera::Tera::default();
+    tera.add_raw_template("export", template)
+        .context("parsing export template")?;
+    let mut context = tera::Context::new();
+    context.insert(
+        "session",
+        &build_json(session, transcript, pricing, tags.to_vec()),
+    );
+    context.insert(
+        "markdown",
+        &render_markdown(session, transcript, pricing, options, tags),
+    );
+    tera.render("export", &context)
+        .context("rendering export template")
+}
+
+pub fn render_markdown(
+    session: &Session,
+    transcript: &Transcript,
+    pricing: &Pricing,
+    options: &RenderOptions,
+    tags: &[String],
+) -> String {
+    let mut out = String::new();
+    let title = session_title(transcript)
+        .unwrap_or_else(|| format!("Session {}", session.id));
+    out.push_str(&format!("# {title}\n\n"));
+
+    if options.sections.context {
+        out.push_str("## Context\n\n");
+        out.push_str(&format!("- Project: {}\n", session.project.friendly_name()));
+        if !tags.is_empty() {
+            out.push_str(&format!("- Tags: {}\n", tags.join(", ")));
+        }
+        out.push_str(&format!("- Entries: {}\n", transcript.entries.len()));
+        if let Some(model) = transcript
+            .entries
+            .iter()
+            .filter_map(|e| e.message())
+            .find_map(|m| m.model.as_deref())
+        {
+            out.push_str(&format!("- Model: {model}\n"));
+        }
+        let times = entry_times(transcript);
+        if let (Some(first), Some(last)) = (times.first(), times.last()) {
+            let mut line = format!("- Duration: {}", format_duration(*last - *first));
+            if let Some(gap) = largest_gap(&times) {
+                if gap > chrono::Duration::minutes(10) {
+                    line.push_str(&format!(" (largest gap {})", format_duration(gap)));
+                }
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+        if options.sections.tokens {
+            let estimate = pricing.estimate(transcript);
+            if !estimate.by_model.is_empty() {
+                out.push_str(&format!("- Estimated cost: ${:.4}\n", estimate.total_usd));
+            }
+        }
+        if let Some((timeline, bucket)) = activity_timeline(&times) {
+            out.push_str(&format!(
+                "- Activity: `{timeline}` (messages per {})\n",
+                format_duration(bucket)
+            ));
+        }
+        out.push('\n');
+    }
+
+    let summaries = summary_entries(transcript);
+    if options.sections.summary && !summaries.is_empty() {
+        out.push_str("## Session Summary\n\n");
+        for (summary, leaf_uuid) in &summaries {
+            out.push_str(summary);
+            // leaf_uuid names the last message the summary covers;
+            // resolve it so readers know how much it spans.
+            if let Some(n) = leaf_uuid.and_then(|uuid| message_position(transcript, uuid))
+            {
+                out.push_str(&format!(" *(through message {n})*"));
+            }
+            out.push_str("\n\n");
+        }
+    }
+
+    if options.sections.transcript {
+        out.push_str("## Conversation\n\n");
+        let entries = &transcript.entries;
+        let main_branch = main_branch_uuids(transcript);
+        let off_branch = |entry: &TranscriptEntry| {
+            let Some(main) = &main_branch else { return false };
+            entry
+                .meta()
+                .and_then(|m| m.uuid.as_deref())
+                .is_some_and(|uuid| !main.contains(uuid))
+        };
+        let mut ctx = RenderCtx::new(session, transcript, options);
+        let mut i = 0;
+        while i < entries.len() {
+            if is_sidechain(&entries[i]) {
+                // A run of sidechain entries is one sub-agent conversation;
+                // fold it so the main thread stays readable.
+                i = fold_run(
+                    entries,
+                    i,
+                    "Sub-agent conversation",
+                    is_sidechain,
+                    &mut ctx,
+                    &mut out,
+                );
+            } else if off_branch(&entries[i]) {
+                // Entries off the main parent_uuid chain are abandoned
+                // branches (retries, edited prompts); fold those too.
+                i = fold_run(entries, i, "Alternate branch", off_branch, &mut ctx, &mut out);
+            } else {
+                render_entry(&entries[i], &mut ctx, &mut out);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Knobs for what lands in the rendered transcript — mainly for taming
+/// huge Bash/Read tool outputs.
+pub struct RenderOptions {
+    /// Render tool result blocks at all.
+    pub tool_results: bool,
+    /// Cap each tool result at this many lines.
+    pub max_tool_output: Option<usize>,
+    /// Cap each tool result at this many bytes; the full output is
+    /// dumped to a sidecar file the document links to.
+    pub max_tool_result: Option<u64>,
+    /// Only show these tools' calls (and their results).
+    pub tools: Option<std::collections::BTreeSet<String>>,
+    /// Tools whose calls collapse to a one-line count and whose results
+    /// are dropped (see [`ExportConfig`]).
+    pub ignored: std::collections::BTreeSet<String>,
+    /// Which top-level sections land in the document (`--sections`).
+    pub sections: Sections,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            tool_results: true,
+            max_tool_output: None,
+            max_tool_result: None,
+            tools: None,
+            ignored: std::collections::BTreeSet::new(),
+            sections: Sections::default(),
+        }
+    }
+}
+
+/// Per-section enable flags for the built-in Markdown layout. `tokens`
+/// governs the cost bullet inside Context, so it only shows when
+/// `context` is on too; `files` governs the snapshot sections.
+#[derive(Clone)]
+pub struct Sections {
+    pub context: bool,
+    pub summary: bool,
+    pub transcript: bool,
+    pub files: bool,
+    pub tokens: bool,
+}
+
+impl Default for Sections {
+    fn default() -> Self {
+        Self {
+            context: true,
+            summary: true,
+            transcript: true,
+            files: true,
+            tokens: true,
+        }
+    }
+}
+
+impl Sections {
+    /// Parses a `--sections` list into flags, everything not named
+    /// off. Unknown names error so a typo cannot silently drop a
+    /// section.
+    pub fn parse(names: &[String]) -> Result<Self> {
+        let mut sections = Self {
+            context: false,
+            summary: false,
+            transcript: false,
+            files: false,
+            tokens: false,
+        };
+        for name in names {
+            match name.as_str() {
+                "context" => sections.context = true,
+                "summary" => sections.summary = true,
+                "transcript" => sections.transcript = true,
+                "files" => sections.files = true,
+                "tokens" => sections.tokens = true,
+                other => anyhow::bail!(
+                    "unknown section {other:?} (context, summary, transcript, \
+                     files, tokens)"
+                ),
+            }
+        }
+        Ok(sections)
+    }
+}
+
+#[derive(serde::Deserialize, Default)]
+struct ExportFile {
+    #[serde(default)]
+    export: ExportConfig,
+}
+
+/// The `[export]` table of `$ZSH_CONFIG/claude-export.toml`:
+///
+/// ```toml
+/// [export]
+/// ignore_tools = ["TodoWrite", "WebSearch"]
+/// ```
+#[derive(serde::Deserialize, Default)]
+pub struct ExportConfig {
+    /// Tools too noisy to transcribe; their invocations become a
+    /// one-line count instead of full JSON blocks.
+    #[serde(default)]
+    pub ignore_tools: Vec<String>,
+}
+
+impl ExportConfig {
+    /// Loads the `[export]` table; a missing file means defaults.
+    pub fn from_config() -> Result<Self> {
+        let path = crate::llm::config_dir().join("claude-export.toml");
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        let file: ExportFile = toml::from_str(&raw)
+            .with_context(|| format!("parsing {}", path.display()))?;
+        Ok(file.export)
+    }
+}
+
+/// State threaded through transcript rendering: the image counter and
+/// the tool filters with the `tool_use_id → name` map they need.
+struct RenderCtx<'a> {
+    images: ImageLinks,
+    raws: RawLinks,
+    options: &'a RenderOptions,
+    tool_names: std::collections::HashMap<&'a str, &'a str>,
+}
+
+impl<'a> RenderCtx<'a> {
+    fn new(
+        session: &Session,
+        transcript: &'a Transcript,
+        options: &'a RenderOptions,
+    ) -> Self {
+        let mut tool_names = std::collections::HashMap::new();
+        for message in transcript.entries.iter().filter_map(|e| e.message()) {
+            if let MessageContent::Blocks(blocks) = &message.content {
+                for block in blocks {
+                    if let ContentBlock::ToolUse { id: Some(id), name, .. } = block {
+                        tool_names.insert(id.as_str(), name.as_str());
+                    }
+                }
+            }
+        }
+        Self {
+            images: ImageLinks::for_session(session),
+            raws: RawLinks::for_session(session, options.max_tool_result),
+            options,
+            tool_names,
+        }
+    }
+
+    fn tool_allowed(&self, name: &str) -> bool {
+        self.options.tools.as_ref().is_none_or(|allow| allow.contains(name))
+    }
+
+    fn ignored(&self, name: &str) -> bool {
+        self.options.ignored.contains(name)
+    }
+
+    /// Results are tied to their tool via `tool_use_id`; results whose
+    /// origin is unknown stay visible rather than being guessed away.
+    fn result_allowed(&self, tool_use_id: Option<&str>) -> bool {
+        if !self.options.tool_results {
+            return false;
+        }
+        let name = tool_use_id.and_then(|id| self.tool_names.get(id));
+        if name.is_some_and(|name| self.ignored(name)) {
+            return false;
+        }
+        match (&self.options.tools, name) {
+            (Some(allow), Some(name)) => allow.contains(*name),
+            _ => true,
+        }
+    }
+}
+
+/// Renders the run of entries starting at `start` that match `pred`
+/// inside one `<details>` block; returns the index past the run.
+fn fold_run(
+    entries: &[TranscriptEntry],
+    start: usize,
+    label: &str,
+    pred: impl Fn(&TranscriptEntry) -> bool,
+    ctx: &mut RenderCtx,
+    out: &mut String,
+) -> usize {
+    let mut i = start;
+    while i < entries.len() && pred(&entries[i]) {
+        i += 1;
+    }
+    let run = &entries[start..i];
+    let messages = run.iter().filter(|e| e.message().is_some()).count();
+    out.push_str(&format!(
+        "<details>\n<summary>{label} ({messages} messages)</summary>\n\n"
+    ));
+    for entry in run {
+        render_entry(entry, ctx, out);
+    }
+    out.push_str("</details>\n\n");
+    i
+}
+
+/// Running image counter plus the relative directory the Markdown
+/// links point at. The counter keeps the links and the files
+/// [`export_images`] writes in lockstep.
+struct ImageLinks {
+    dir: String,
+    count: usize,
+}
+
+impl ImageLinks {
+    fn for_session(session: &Session) -> Self {
+        Self { dir: format!("{}-files", session.id), count: 0 }
+    }
+
+    fn next(&mut self, media_type: Option<&str>) -> String {
+        self.count += 1;
+        format!("{}/image-{:03}.{}", self.dir, self.count, image_ext(media_type))
+    }
+}
+
+/// Same lockstep scheme for oversized tool results: every result over
+/// the byte cap gets a number whether or not the filters render it, so
+/// the links here and the files [`export_tool_results`] writes agree.
+struct RawLinks {
+    dir: String,
+    max: Option<u64>,
+    count: usize,
+}
+
+impl RawLinks {
+    fn for_session(session: &Session, max: Option<u64>) -> Self {
+        Self { dir: format!("{}-files", session.id), max, count: 0 }
+    }
+
+    /// The sidecar link for `text`, or `None` when it fits the cap.
+    fn next(&mut self, text: &str) -> Option<String> {
+        if text.len() as u64 <= self.max? {
+            return None;
+        }
+        self.count += 1;
+        Some(format!("{}/tool-result-{:03}.txt", self.dir, self.count))
+    }
+}
+
+/// The longest prefix of `text` within `max` bytes, cut on a char
+/// boundary.
+pub(crate) fn truncate_bytes(text: &str, max: u64) -> &str {
+    let mut end = (max as usize).min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+fn image_ext(media_type: Option<&str>) -> &'static str {
+    match media_type {
+        Some("image/png") => "png",
+        Some("image/jpeg") => "jpg",
+        Some("image/gif") => "gif",
+        Some("image/webp") => "webp",
+        _ => "bin",
+    }
+}
+
+/// Decodes base64 image blocks to `<files_dir>/image-NNN.<ext>`,
+/// numbered in transcript order so the links [`render_markdown`] emits
+/// resolve. Returns how many images were written.
+pub fn export_images(
+    transcript: &Transcript,
+    files_dir: &std::path::Path,
+) -> Result<usize> {
+    use base64::Engine;
+    let mut count = 0;
+    let mut written = 0;
+    for entry in &transcript.entries {
+        let Some(message) = entry.message() else { continue };
+        let MessageContent::Blocks(blocks) = &message.content else { continue };
+        for block in blocks {
+            let ContentBlock::Image { source } = block else { continue };
+            let Some(data) = &source.data else { continue };
+            count += 1;
+            let bytes = match base64::engine::general_purpose::STANDARD
+                .decode(data.trim())
+            {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    crate::logger::warn(format!("image {count} not decodable: {err}"));
+                    continue;
+                }
+            };
+            std::fs::create_dir_all(files_dir)
+                .with_context(|| format!("creating {}", files_dir.display()))?;
+            let out = files_dir.join(format!(
+                "image-{count:03}.{}",
+                image_ext(source.media_type.as_deref())
+            ));
+            std::fs::write(&out, bytes)
+                .with_context(|| format!("writing {}", out.display()))?;
+            written += 1;
+        }
+    }
+    Ok(written)
+}
+
+/// Dumps every tool result over `max` bytes whole to
+/// `<files_dir>/tool-result-NNN.txt`, numbered in transcript order so
+/// the links [`render_markdown`] emits resolve. Returns how many files
+/// were written.
+pub fn export_tool_results(
+    transcript: &Transcript,
+    files_dir: &std::path::Path,
+    max: Option<u64>,
+) -> Result<usize> {
+    let Some(max) = max else { return Ok(0) };
+    let mut written = 0;
+    for entry in &transcript.entries {
+        let Some(message) = entry.message() else { continue };
+        let MessageContent::Blocks(blocks) = &message.content else { continue };
+        for block in blocks {
+            let ContentBlock::ToolResult { content, .. } = block else { continue };
+            let text = tool_result_text(content);
+            if text.len() as u64 <= max {
+                continue;
+            }
+            written += 1;
+            std::fs::create_dir_all(files_dir)
+                .with_context(|| format!("creating {}", files_dir.display()))?;
+            let out = files_dir.join(format!("tool-result-{written:03}.txt"));
+            std::fs::write(&out, text.as_ref())
+                .with_context(|| format!("writing {}", out.display()))?;
+        }
+    }
+    Ok(written)
+}
+
+/// The uuids on the path from the root to the newest leaf. `None` when
+/// the transcript never forks — then there is nothing to fold. Retries
+/// and edited prompts fork the tree; the branch holding the last entry
+/// is the one the session actually continued from.
+fn main_branch_uuids(
+    transcript: &Transcript,
+) -> Option<std::collections::HashSet<String>> {
+    let mut parents: BTreeMap<&str, Option<&str>> = BTreeMap::new();
+    let mut children: BTreeMap<&str, u32> = BTreeMap::new();
+    let mut last = None;
+    for meta in transcript.entries.iter().filter_map(|e| e.meta()) {
+        let Some(uuid) = meta.uuid.as_deref() else { continue };
+        parents.insert(uuid, meta.parent_uuid.as_deref());
+        if let Some(parent) = meta.parent_uuid.as_deref() {
+            *children.entry(parent).or_default() += 1;
+        }
+        last = Some(uuid);
+    }
+    if !children.values().any(|&count| count > 1) {
+        return None;
+    }
+    let mut branch = std::collections::HashSet::new();
+    let mut cursor = last;
+    while let Some(uuid) = cursor {
+        if !branch.insert(uuid.to_string()) {
+            break; // cycle guard: malformed transcripts must not hang
+        }
+        cursor = parents.get(uuid).copied().flatten();
+    }
+    Some(branch)
+}
+
+/// Document title: the transcript's own rolling summary when present,
+/// else the opening line of the first user message.
+pub(crate) fn session_title(transcript: &Transcript) -> Option<String> {
+    if let Some((summary, _)) = summary_entries(transcript).into_iter().last() {
+        return Some(summary.to_string());
+    }
+    // First user message whose opening line survives sanitization;
+    // sessions that open with a paste skip ahead to the first typed
+    // message instead of using 80 chars of stack trace as a header.
+    transcript.entries.iter().find_map(|e| match e {
+        TranscriptEntry::User { message, .. } => {
+            let text = message.content.plain_text();
+            let line = text.lines().find(|l| !l.trim().is_empty())?;
+            sanitize_title(line)
+        }
+        _ => None,
+    })
+}
+
+/// Cleans an opening line into a usable header: markdown syntax and
+/// backticks stripped, whitespace collapsed, truncated at a word
+/// boundary. Returns `None` when the line is clearly pasted material.
+fn sanitize_title(line: &str) -> Option<String> {
+    let line = line.trim();
+    if looks_pasted(line) {
+        return None;
+    }
+    let stripped = line
+        .trim_start_matches(['#', '>', '-', '*', ' '])
+        .replace('`', "");
+    let collapsed = stripped.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        return None;
+    }
+    Some(truncate_title(&collapsed))
+}
+
+/// Heuristics for "this was pasted, not typed": code fences, stack
+/// traces, very long lines, or text that is mostly symbols.
+fn looks_pasted(line: &str) -> bool {
+    if line.starts_with("```")
+        || line.chars().count() > 200
+        || line.contains("Traceback (most recent call last)")
+        || line.contains("panicked at")
+    {
+        return true;
+    }
+    let symbols = line
+        .chars()
+        .filter(|c| !c.is_alphanumeric() && !c.is_whitespace())
+        .count();
+    let total = line.chars().count();
+    total >= 20 && symbols * 10 > total * 4
+}
+
+fn truncate_title(text: &str) -> String {
+    const MAX: usize = 80;
+    if text.chars().count() <= MAX {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(MAX - 1).collect();
+    // Break at a word boundary unless that would lose half the title.
+    let cut = match cut.rfind(' ') {
+        Some(at) if at > MAX / 2 => &cut[..at],
+        _ => cut.as_str(),
+    };
+    format!("{}…", cut.trim_end_matches([' ', ',', ';', ':', '.']))
+}
+
+fn summary_entries(transcript: &Transcript) -> Vec<(&str, Option<&str>)> {
+    transcript
+        .entries
+        .iter()
+        .filter_map(|e| match e {
+            TranscriptEntry::Summary { summary, leaf_uuid } => {
+                Some((summary.as_str(), leaf_uuid.as_deref()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// 1-based position of `uuid` among the entries that carry one.
+fn message_position(transcript: &Transcript, uuid: &str) -> Option<usize> {
+    transcript
+        .entries
+        .iter()
+        .filter_map(|e| e.meta())
+        .filter_map(|m| m.uuid.as_deref())
+        .position(|u| u == uuid)
+        .map(|i| i + 1)
+}
+
+fn is_sidechain(entry: &TranscriptEntry) -> bool {
+    entry.meta().is_some_and(|m| m.is_sidechain)
+}
+
+fn render_entry(entry: &TranscriptEntry, ctx: &mut RenderCtx, out: &mut String) {
+    match entry {
+        TranscriptEntry::User { message, .. } => {
+            out.push_str("### 👤 User\n\n");
+            render_content(&message.content, ctx, out);
+        }
+        TranscriptEntry::Assistant { message, .. } => {
+            out.push_str("### 🤖 Assistant\n\n");
+            render_content(&message.content, ctx, out);
+        }
+        _ => {}
+    }
+}
+
+fn render_content(content: &MessageContent, ctx: &mut RenderCtx, out: &mut String) {
+    match content {
+        MessageContent::Text(text) => {
+            if !text.trim().is_empty() {
+                out.push_str(text.trim_end());
+                out.push_str("\n\n");
+            }
+        }
+        MessageContent::Blocks(blocks) => {
+            let mut ignored: BTreeMap<&str, usize> = BTreeMap::new();
+            for block in blocks {
+                match block {
+                    ContentBlock::Text { text } => {
+                        if !text.trim().is_empty() {
+                            out.push_str(text.trim_end());
+                            out.push_str("\n\n");
+                        }
+                    }
+                    ContentBlock::ToolUse { name, input, .. } => {
+                        if ctx.ignored(name) {
+                            *ignored.entry(name.as_str()).or_default() += 1;
+                            continue;
+                        }
+                        if !ctx.tool_allowed(name) {
+                            continue;
+                        }
+                        out.push_str(&format!("**Tool: {name}**\n\n"));
+                        out.push_str("```json\n");
+                        out.push_str(
+                            &serde_json::to_string_pretty(input).unwrap_or_default(),
+                        );
+                        out.push_str("\n```\n\n");
+                    }
+                    ContentBlock::Image { source } => {
+                        if source.data.is_some() {
+                            let link = ctx.images.next(source.media_type.as_deref());
+                            out.push_str(&format!(
+                                "![image {}]({link})\n\n",
+                                ctx.images.count
+                            ));
+                        }
+                    }
+                    ContentBlock::ToolResult { tool_use_id, content, is_error } => {
+                        let full = tool_result_text(content);
+                        // Numbered before the filters so the sidecar
+                        // files line up even for results the filters
+                        // then drop.
+                        let raw_link = ctx.raws.next(&full);
+                        if !ctx.result_allowed(tool_use_id.as_deref()) {
+                            continue;
+                        }
+                        let label = if *is_error { "Tool error" } else { "Tool result" };
+                        let (shown, truncated_bytes) = match &raw_link {
+                            Some(_) => {
+                                let cap = ctx.options.max_tool_result
+                                    .expect("raw links only exist under a cap");
+                                let shown = truncate_bytes(&full, cap);
+                                (shown, full.len() - shown.len())
+                            }
+                            None => (full.as_ref(), 0),
+                        };
+                        let (text, omitted) =
+                            truncate_lines(shown, ctx.options.max_tool_output);
+                        if !text.trim().is_empty() {
+                            out.push_str(&format!("**{label}:**\n\n```\n"));
+                            out.push_str(text.trim_end());
+                            if omitted > 0 {
+                                out.push_str(&format!("\n... (+{omitted} more lines)"));
+                            }
+                            out.push_str("\n```\n\n");
+                        }
+                        if let Some(link) = raw_link {
+                            out.push_str(&format!(
+                                "*(truncated, {truncated_bytes} bytes omitted — \
+                                 full output in [{link}]({link}))*\n\n"
+                            ));
+                        }
+                    }
+                    ContentBlock::Thinking { .. } | ContentBlock::Other => {}
+                }
+            }
+            if !ignored.is_empty() {
+                let counts: Vec<String> = ignored
+                    .iter()
+                    .map(|(name, count)| format!("{count}× {name}"))
+                    .collect();
+                out.push_str(&format!("*(hidden: {} calls)*\n\n", counts.join(", ")));
+            }
+        }
+    }
+}
+
+const SUMMARY_PROMPT: &str = "You are given a coding-assistant session transcript. \
+Write two Markdown sections. `## What Happened`: one tight paragraph of what was \
+attempted and what the outcome was. `## Lessons Learned`: up to four bullets with \
+genuinely reusable takeaways (decisions, gotchas, fixes) — no filler. Output only \
+those two sections.";
+
+/// How much transcript the summarizer sees. Long sessions keep their
+/// head and tail — that is where the goal and the outcome live.
+const SUMMARY_BUDGET: usize = 16_000;
+
+fn summary_sections(client: &LLMClient, transcript: &Transcript) -> Result<String> {
+    let mut conversation = String::new();
+    for entry in &transcript.entries {
+        let role = match entry {
+            TranscriptEntry::User { .. } => "User",
+            TranscriptEntry::Assistant { .. } => "Assistant",
+            _ => continue,
+        };
+        let text = entry
+            .message()
+            .expect("user/assistant have messages")
+            .content
+            .plain_text();
+        if !text.trim().is_empty() {
+            conversation.push_str(&format!("{role}: {}\n", text.trim()));
+        }
+    }
+    if conversation.chars().count() > SUMMARY_BUDGET {
+        let head: String = conversation.chars().take(SUMMARY_BUDGET / 2).collect();
+        let tail: String = {
+            let chars: Vec<char> = conversation.chars().collect();
+            chars[chars.len() - SUMMARY_BUDGET / 2..].iter().collect()
+        };
+        conversation = format!("{head}\n[... transcript truncated ...]\n{tail}");
+    }
+    let reply = crate::cache::LlmCache::open()
+        .complete_cached(
+            client,
+            &[
+                ChatMessage::system(SUMMARY_PROMPT),
+                ChatMessage::user(conversation),
+            ],
+            "summary-v1",
+        )
+        .context("generating summary sections")?;
+    Ok(format!("{}\n\n", reply.trim()))
+}
+
+/// Timestamps of every entry that carries one, in transcript order.
+fn entry_times(transcript: &Transcript) -> Vec<chrono::DateTime<chrono::Utc>> {
+    transcript
+        .entries
+        .iter()
+        .filter_map(|e| e.meta())
+        .filter_map(|m| m.timestamp.as_deref())
+        .filter_map(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&chrono::Utc))
+        .collect()
+}
+
+fn format_duration(duration: chrono::Duration) -> String {
+    let secs = duration.num_seconds().max(0);
+    let (hours, minutes, seconds) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if hours > 0 && minutes == 0 {
+        format!("{hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 && seconds == 0 {
+        format!("{minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+fn largest_gap(times: &[chrono::DateTime<chrono::Utc>]) -> Option<chrono::Duration> {
+    times.windows(2).map(|w| w[1] - w[0]).max()
+}
+
+/// Sparkline of messages over the session, normally one cell per ten
+/// minutes. Sessions left open for days would produce absurdly long
+/// lines, so the bucket widens (in 10-minute steps) to keep it within
+/// 60 cells.
+fn activity_timeline(
+    times: &[chrono::DateTime<chrono::Utc>],
+) -> Option<(String, chrono::Duration)> {
+    const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let (first, last) = (times.first()?, times.last()?);
+    let span = (*last - *first).num_seconds().max(1);
+    let mut bucket_secs: i64 = 600;
+    while span / bucket_secs >= 60 {
+        bucket_secs += 600;
+    }
+    let buckets = (span / bucket_secs) as usize + 1;
+    let mut counts = vec![0u64; buckets];
+    for time in times {
+        let idx = ((*time - *first).num_seconds() / bucket_secs) as usize;
+        counts[idx] += 1;
+    }
+    let max = *counts.iter().max().expect("at least one bucket");
+    let timeline = counts
+        .iter()
+        .map(|&count| {
+            if count == 0 {
+                '·'
+            } else {
+                let level = (count * LEVELS.len() as u64).div_ceil(max) - 1;
+                LEVELS[level.min(LEVELS.len() as u64 - 1) as usize]
+            }
+        })
+        .collect();
+    Some((timeline, chrono::Duration::seconds(bucket_secs)))
+}
+
+/// Tool results are either a string or a list of `{type: text}` blocks.
+/// Borrowed whenever the result is a single string — tool output is
+/// the bulk of a transcript, so copying it here would dominate memory.
+pub fn tool_result_text(content: &serde_json::Value) -> std::borrow::Cow<'_, str> {
+    match content {
+        serde_json::Value::String(s) => std::borrow::Cow::Borrowed(s),
+        serde_json::Value::Array(items) => {
+            let texts: Vec<&str> = items
+                .iter()
+                .filter_map(|i| i.get("text").and_then(|t| t.as_str()))
+                .collect();
+            match texts.as_slice() {
+                [only] => std::borrow::Cow::Borrowed(only),
+                texts => std::borrow::Cow::Owned(texts.join("\n")),
+            }
+        }
+        _ => std::borrow::Cow::Borrowed(""),
+    }
+}
+
+/// Keeps the first `max` lines of `text`, returning the kept text and
+/// how many lines were dropped. Untruncated text is passed through
+/// without a copy.
+pub(crate) fn truncate_lines(
+    text: &str,
+    max: Option<usize>,
+) -> (std::borrow::Cow<'_, str>, usize) {
+    let Some(max) = max else {
+        return (std::borrow::Cow::Borrowed(text), 0);
+    };
+    let total = text.lines().count();
+    if total <= max {
+        return (std::borrow::Cow::Borrowed(text), 0);
+    }
+    let kept = text.lines().take(max).collect::<Vec<_>>().join("\n");
+    (std::borrow::Cow::Owned(kept), total - max)
+}
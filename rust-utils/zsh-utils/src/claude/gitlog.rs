@@ -0,0 +1,88 @@
+//! "Commits during session" — the bridge between a transcript and the
+//! code changes that actually landed. Sessions record their `cwd`, so
+//! running `git log` there over the session's time window ties the
+//! conversation to real SHAs.
+
+use std::process::Command;
+
+use chrono::{DateTime, Utc};
+
+use super::parser::Transcript;
+use crate::logger;
+
+pub struct Commit {
+    pub sha: String,
+    pub subject: String,
+}
+
+/// Commits made in the session's working tree between its first and
+/// last timestamp. `None` when the session recorded no cwd, has no
+/// usable time window, or the cwd is not a git checkout — a transcript
+/// from a non-repo directory is normal, not an error.
+pub fn commits_during(transcript: &Transcript) -> Option<Vec<Commit>> {
+    let cwd = transcript
+        .entries
+        .iter()
+        .filter_map(|e| e.meta())
+        .find_map(|m| m.cwd.clone())?;
+    let times: Vec<DateTime<Utc>> = transcript
+        .entries
+        .iter()
+        .filter_map(|e| e.meta())
+        .filter_map(|m| m.timestamp.as_deref())
+        .filter_map(|t| DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&Utc))
+        .collect();
+    let (first, last) = (times.first()?, times.last()?);
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(&cwd)
+        .arg("log")
+        .arg(format!("--since={}", first.to_rfc3339()))
+        .arg(format!("--until={}", last.to_rfc3339()))
+        .arg("--pretty=format:%h\t%s")
+        .output();
+    let output = match output {
+        Ok(output) => output,
+        Err(err) => {
+            logger::warn(format!("git log failed in {cwd}: {err}"));
+            return None;
+        }
+    };
+    crate::audit::record(
+        "git log (session window)",
+        std::path::Path::new(&cwd),
+        output.status.code(),
+    );
+    if !output.status.success() {
+        // Not a repo, or the directory is gone since the session ran.
+        return None;
+    }
+    let commits: Vec<Commit> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (sha, subject) = line.split_once('\t')?;
+            Some(Commit {
+                sha: sha.to_string(),
+                subject: subject.to_string(),
+            })
+        })
+        .collect();
+    Some(commits)
+}
+
+/// Markdown section listing the session-window commits, or `None` when
+/// there is nothing to show.
+pub fn render_section(transcript: &Transcript) -> Option<String> {
+    let commits = commits_during(transcript)?;
+    if commits.is_empty() {
+        return None;
+    }
+    let mut out = String::from("## Commits During Session\n\n");
+    for commit in commits {
+        out.push_str(&format!("- `{}` {}\n", commit.sha, commit.subject));
+    }
+    out.push('\n');
+    Some(out)
+}
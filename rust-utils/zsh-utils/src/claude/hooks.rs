@@ -0,0 +1,144 @@
+//! Post-export hooks: user-defined commands and webhooks fired after
+//! each session export, so the archive can auto-commit itself or
+//! notify downstream systems.
+//!
+//! Configured in `$ZSH_CONFIG/claude-export.toml`:
+//!
+//! ```toml
+//! [hooks]
+//! post_session = "git -C ~/Documents/Claude add -A && git commit -m export"
+//! webhook = "https://example.com/claude-exported"
+//! ```
+//!
+//! `claude-export --post-hook <cmd>` adds one-off commands on top of
+//! the configured ones. Commands get the metadata twice over: as JSON
+//! on stdin for pipelines, and as `CLAUDE_EXPORT_*` environment
+//! variables for plain shell one-liners.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::logger;
+
+#[derive(Deserialize, Default)]
+struct HooksFile {
+    #[serde(default)]
+    hooks: Hooks,
+}
+
+#[derive(Deserialize, Default)]
+pub struct Hooks {
+    /// Shell command run after each exported session; the metadata
+    /// arrives as JSON on its stdin.
+    pub post_session: Option<String>,
+    /// URL that receives the same metadata as a JSON POST.
+    pub webhook: Option<String>,
+    /// Commands added for this run only (`--post-hook`), run after the
+    /// configured one.
+    #[serde(skip)]
+    pub extra: Vec<String>,
+}
+
+/// What the hooks learn about the export that just happened.
+#[derive(Serialize)]
+pub struct HookEvent {
+    pub session_id: String,
+    pub project: String,
+    /// Path of the artifact that was written.
+    pub path: String,
+    pub format: String,
+    pub estimated_cost_usd: f64,
+}
+
+impl Hooks {
+    /// Loads the `[hooks]` table; a missing file means no hooks.
+    pub fn from_config() -> Result<Self> {
+        let path = crate::llm::config_dir().join("claude-export.toml");
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        let file: HooksFile = toml::from_str(&raw)
+            .with_context(|| format!("parsing {}", path.display()))?;
+        Ok(file.hooks)
+    }
+
+    /// Appends a command from the command line; chainable like the
+    /// exporter's builders.
+    pub fn with_command(mut self, command: String) -> Self {
+        self.extra.push(command);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.post_session.is_none() && self.webhook.is_none() && self.extra.is_empty()
+    }
+
+    /// Fires every configured hook. A failing hook warns instead of
+    /// erroring — one broken auto-commit must not abort a batch export.
+    pub fn fire(&self, event: &HookEvent) {
+        let payload = match serde_json::to_string(event) {
+            Ok(payload) => payload,
+            Err(err) => {
+                logger::warn(format!("hook payload not serializable: {err}"));
+                return;
+            }
+        };
+        for command in self.post_session.iter().chain(&self.extra) {
+            if let Err(err) = run_command(command, &payload, event) {
+                logger::warn(format!("post-export hook failed: {err:#}"));
+            }
+        }
+        if let Some(url) = &self.webhook {
+            if let Err(err) = post_webhook(url, &payload) {
+                logger::warn(format!("webhook failed: {err:#}"));
+            }
+        }
+    }
+}
+
+fn run_command(command: &str, payload: &str, event: &HookEvent) -> Result<()> {
+    let mut child = Command::new("sh")
+        .args(["-c", command])
+        .env("CLAUDE_EXPORT_PATH", &event.path)
+        .env("CLAUDE_EXPORT_SESSION_ID", &event.session_id)
+        .env("CLAUDE_EXPORT_PROJECT", &event.project)
+        .env("CLAUDE_EXPORT_FORMAT", &event.format)
+        .env(
+            "CLAUDE_EXPORT_COST_USD",
+            format!("{:.4}", event.estimated_cost_usd),
+        )
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("spawning {command:?}"))?;
+    child
+        .stdin
+        .take()
+        .context("hook has no stdin")?
+        .write_all(payload.as_bytes())
+        .context("writing hook stdin")?;
+    let status = child.wait().context("waiting for hook")?;
+    crate::audit::record(
+        command,
+        &std::env::current_dir().unwrap_or_default(),
+        status.code(),
+    );
+    anyhow::ensure!(status.success(), "hook exited with {status}");
+    Ok(())
+}
+
+fn post_webhook(url: &str, payload: &str) -> Result<()> {
+    reqwest::blocking::Client::new()
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(payload.to_string())
+        .send()
+        .context("sending webhook")?
+        .error_for_status()
+        .context("webhook endpoint rejected the event")?;
+    Ok(())
+}
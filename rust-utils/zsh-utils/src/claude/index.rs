@@ -0,0 +1,396 @@
+//! SQLite index over every transcript, backing `claude-search`.
+//!
+//! The index is derived data — it can be rebuilt from the transcripts
+//! at any time — so it lives in the cache directory and refreshes
+//! incrementally by comparing file mtimes.
+
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+
+use super::models::{ContentBlock, MessageContent, TranscriptEntry};
+use super::parser;
+use super::sessions::{self, Session};
+use crate::llm::LLMClient;
+
+/// `~/.cache/zsh-utils/claude-index.db` (or the platform equivalent).
+pub fn index_path() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_default()
+        .join("zsh-utils")
+        .join("claude-index.db")
+}
+
+pub struct Index {
+    conn: Connection,
+}
+
+/// One FTS match, carrying enough context to print a useful line.
+pub struct Hit {
+    pub session_id: String,
+    pub project: String,
+    pub start_time: Option<String>,
+    pub role: String,
+    /// Snippet with the match wrapped in `\u{1}`/`\u{2}` markers, which
+    /// callers replace with their own highlighting.
+    pub snippet: String,
+}
+
+/// Markers `snippet()` wraps matches in; chosen so no transcript text
+/// can collide with them.
+pub const HIT_START: char = '\u{1}';
+pub const HIT_END: char = '\u{2}';
+
+/// What a refresh did, for reporting.
+#[derive(Default)]
+pub struct RefreshStats {
+    pub indexed: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+}
+
+impl Index {
+    pub fn open() -> Result<Self> {
+        Self::open_at(&index_path())
+    }
+
+    pub fn open_at(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("creating {}", dir.display()))?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("opening index {}", path.display()))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS sessions (
+                 id         TEXT PRIMARY KEY,
+                 project    TEXT NOT NULL,
+                 path       TEXT NOT NULL,
+                 mtime      INTEGER NOT NULL,
+                 start_time TEXT,
+                 tokens     INTEGER NOT NULL DEFAULT 0
+             );
+             CREATE TABLE IF NOT EXISTS messages (
+                 session_id TEXT NOT NULL,
+                 idx        INTEGER NOT NULL,
+                 role       TEXT NOT NULL,
+                 timestamp  TEXT,
+                 text       TEXT NOT NULL,
+                 PRIMARY KEY (session_id, idx)
+             );
+             CREATE TABLE IF NOT EXISTS tool_uses (
+                 session_id TEXT NOT NULL,
+                 idx        INTEGER NOT NULL,
+                 name       TEXT NOT NULL,
+                 file_path  TEXT
+             );
+             CREATE VIRTUAL TABLE IF NOT EXISTS messages_fts USING fts5 (
+                 text,
+                 session_id UNINDEXED,
+                 role       UNINDEXED
+             );
+             CREATE TABLE IF NOT EXISTS embeddings (
+                 session_id TEXT NOT NULL,
+                 idx        INTEGER NOT NULL,
+                 vector     BLOB NOT NULL,
+                 PRIMARY KEY (session_id, idx)
+             );",
+        )
+        .context("initializing index schema")?;
+        Ok(Self { conn })
+    }
+
+    /// Brings the index in line with what is on disk: new or modified
+    /// sessions are (re)ingested, deleted ones are dropped.
+    pub fn refresh(&mut self) -> Result<RefreshStats> {
+        let mut stats = RefreshStats::default();
+        let mut seen = Vec::new();
+        for project in sessions::projects()? {
+            for session in project.sessions()? {
+                seen.push(session.id.clone());
+                let mtime = file_mtime(&session.path);
+                let stored: Option<i64> = self
+                    .conn
+                    .query_row(
+                        "SELECT mtime FROM sessions WHERE id = ?1",
+                        [&session.id],
+                        |row| row.get(0),
+                    )
+                    .ok();
+                if stored == Some(mtime) {
+                    stats.unchanged += 1;
+                    continue;
+                }
+                self.ingest(&session, mtime)?;
+                stats.indexed += 1;
+            }
+        }
+        stats.removed = self.remove_missing(&seen)?;
+        Ok(stats)
+    }
+
+    fn ingest(&mut self, session: &Session, mtime: i64) -> Result<()> {
+        let transcript = parser::parse_file(&session.path)?;
+        let tx = self.conn.transaction()?;
+        for table in ["messages", "tool_uses", "messages_fts", "embeddings"] {
+            tx.execute(
+                &format!("DELETE FROM {table} WHERE session_id = ?1"),
+                [&session.id],
+            )?;
+        }
+        tx.execute("DELETE FROM sessions WHERE id = ?1", [&session.id])?;
+
+        let mut tokens: u64 = 0;
+        let mut start_time: Option<String> = None;
+        for (idx, entry) in transcript.entries.iter().enumerate() {
+            let role = match entry {
+                TranscriptEntry::User { .. } => "user",
+                TranscriptEntry::Assistant { .. } => "assistant",
+                _ => continue,
+            };
+            let message = entry.message().expect("user/assistant have messages");
+            if let Some(usage) = &message.usage {
+                tokens += usage.input_tokens.unwrap_or(0)
+                    + usage.output_tokens.unwrap_or(0);
+            }
+            let timestamp = entry.meta().and_then(|m| m.timestamp.clone());
+            if start_time.is_none() {
+                start_time.clone_from(&timestamp);
+            }
+            let text = message.content.plain_text();
+            tx.execute(
+                "INSERT INTO messages (session_id, idx, role, timestamp, text)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                rusqlite::params![session.id, idx as i64, role, timestamp, text.as_ref()],
+            )?;
+            if !text.trim().is_empty() {
+                tx.execute(
+                    "INSERT INTO messages_fts (text, session_id, role)
+                     VALUES (?1, ?2, ?3)",
+                    rusqlite::params![text.as_ref(), session.id, role],
+                )?;
+            }
+            if let MessageContent::Blocks(blocks) = &message.content {
+                for block in blocks {
+                    if let ContentBlock::ToolUse { name, input, .. } = block {
+                        let file_path =
+                            input.get("file_path").and_then(|p| p.as_str());
+                        tx.execute(
+                            "INSERT INTO tool_uses (session_id, idx, name, file_path)
+                             VALUES (?1, ?2, ?3, ?4)",
+                            rusqlite::params![session.id, idx as i64, name, file_path],
+                        )?;
+                    }
+                }
+            }
+        }
+        tx.execute(
+            "INSERT INTO sessions (id, project, path, mtime, start_time, tokens)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                session.id,
+                session.project.friendly_name(),
+                session.path.to_string_lossy(),
+                mtime,
+                start_time,
+                tokens as i64,
+            ],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn remove_missing(&self, seen: &[String]) -> Result<usize> {
+        let stored: Vec<String> = self
+            .conn
+            .prepare("SELECT id FROM sessions")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        let mut removed = 0;
+        for id in stored {
+            if seen.contains(&id) {
+                continue;
+            }
+            for table in ["messages", "tool_uses", "messages_fts", "embeddings"] {
+                self.conn.execute(
+                    &format!("DELETE FROM {table} WHERE session_id = ?1"),
+                    [&id],
+                )?;
+            }
+            self.conn.execute("DELETE FROM sessions WHERE id = ?1", [&id])?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    /// Full-text search across message text. FTS5 handles phrase
+    /// queries (`"tokio panic"`) and boolean operators natively; hits
+    /// come back best match first (bm25), then newest session. The
+    /// project and date facets narrow before ranking, so they stay fast
+    /// on large indexes.
+    pub fn search(
+        &self,
+        query: &str,
+        project: Option<&str>,
+        since: Option<&str>,
+        until: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Hit>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT f.session_id, s.project, s.start_time, f.role,
+                    snippet(messages_fts, 0, ?2, ?3, '…', 12)
+             FROM messages_fts f
+             JOIN sessions s ON s.id = f.session_id
+             WHERE messages_fts MATCH ?1
+               AND (?4 IS NULL OR s.project = ?4)
+               AND (?5 IS NULL OR substr(s.start_time, 1, 10) >= ?5)
+               AND (?6 IS NULL OR substr(s.start_time, 1, 10) <= ?6)
+             ORDER BY bm25(messages_fts), s.start_time DESC
+             LIMIT ?7",
+        )?;
+        let hits = stmt
+            .query_map(
+                rusqlite::params![
+                    query,
+                    HIT_START.to_string(),
+                    HIT_END.to_string(),
+                    project,
+                    since,
+                    until,
+                    limit as i64,
+                ],
+                |row| {
+                    Ok(Hit {
+                        session_id: row.get(0)?,
+                        project: row.get(1)?,
+                        start_time: row.get(2)?,
+                        role: row.get(3)?,
+                        snippet: row.get(4)?,
+                    })
+                },
+            )?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(hits)
+    }
+}
+
+impl Index {
+    /// Embeds every indexed message that has no vector yet, batching
+    /// requests. Returns how many messages were embedded. Long messages
+    /// are truncated — embeddings lose little from the tail and the
+    /// endpoints cap input size anyway.
+    pub fn embed_missing(&mut self, client: &LLMClient) -> Result<usize> {
+        const BATCH: usize = 32;
+        const MAX_CHARS: usize = 4_000;
+        let pending: Vec<(String, i64, String)> = self
+            .conn
+            .prepare(
+                "SELECT m.session_id, m.idx, m.text
+                 FROM messages m
+                 LEFT JOIN embeddings e
+                   ON e.session_id = m.session_id AND e.idx = m.idx
+                 WHERE e.session_id IS NULL AND length(trim(m.text)) > 0",
+            )?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        let mut embedded = 0;
+        for batch in pending.chunks(BATCH) {
+            let inputs: Vec<String> = batch
+                .iter()
+                .map(|(_, _, text)| text.chars().take(MAX_CHARS).collect())
+                .collect();
+            let vectors = client.embed(&inputs)?;
+            let tx = self.conn.transaction()?;
+            for ((session_id, idx, _), vector) in batch.iter().zip(vectors) {
+                tx.execute(
+                    "INSERT OR REPLACE INTO embeddings (session_id, idx, vector)
+                     VALUES (?1, ?2, ?3)",
+                    rusqlite::params![session_id, idx, vector_to_blob(&vector)],
+                )?;
+            }
+            tx.commit()?;
+            embedded += batch.len();
+        }
+        Ok(embedded)
+    }
+
+    /// Ranks indexed messages by cosine similarity to the query. A
+    /// linear scan over the vectors is plenty for tens of thousands of
+    /// messages; no approximate-NN structure needed at this scale.
+    pub fn semantic_search(
+        &self,
+        client: &LLMClient,
+        query: &str,
+        project: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Hit>> {
+        let query_vec = client
+            .embed(&[query.to_string()])?
+            .into_iter()
+            .next()
+            .context("empty embedding for query")?;
+        let mut stmt = self.conn.prepare(
+            "SELECT e.session_id, s.project, s.start_time, m.role, m.text, e.vector
+             FROM embeddings e
+             JOIN messages m ON m.session_id = e.session_id AND m.idx = e.idx
+             JOIN sessions s ON s.id = e.session_id
+             WHERE (?1 IS NULL OR s.project = ?1)",
+        )?;
+        let mut scored: Vec<(f32, Hit)> = stmt
+            .query_map([project], |row| {
+                let text: String = row.get(4)?;
+                let blob: Vec<u8> = row.get(5)?;
+                Ok((
+                    blob,
+                    Hit {
+                        session_id: row.get(0)?,
+                        project: row.get(1)?,
+                        start_time: row.get(2)?,
+                        role: row.get(3)?,
+                        snippet: text.chars().take(120).collect(),
+                    },
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+            .into_iter()
+            .map(|(blob, hit)| (cosine(&query_vec, &blob_to_vector(&blob)), hit))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored.truncate(limit);
+        Ok(scored.into_iter().map(|(_, hit)| hit).collect())
+    }
+}
+
+fn vector_to_blob(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn blob_to_vector(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+fn file_mtime(path: &Path) -> i64 {
+    path.metadata()
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
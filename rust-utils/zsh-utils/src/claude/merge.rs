@@ -0,0 +1,73 @@
+//! Stitching resumed conversations back together.
+//!
+//! Resuming a session starts a fresh JSONL file, so one piece of work
+//! fragments across several transcripts. The new file keeps the
+//! lineage, though: its carried-over `summary` entries point at leaf
+//! UUIDs in the old file, and its first real entry's `parentUuid` does
+//! too. This module follows those references to group a project's
+//! sessions into continuation chains.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use super::models::TranscriptEntry;
+use super::parser::{self, Transcript};
+use super::sessions::Session;
+
+/// One piece of work: a session and every session that resumed it,
+/// oldest first.
+pub struct Chain {
+    pub sessions: Vec<Session>,
+}
+
+/// Groups sessions into continuation chains. Sessions that nothing
+/// resumed and that resume nothing come back as chains of one.
+pub fn chains(mut sessions: Vec<Session>) -> Result<Vec<Chain>> {
+    // Chronological order guarantees a continuation is seen after the
+    // session it continues.
+    sessions.sort_by_key(|s| s.start_time());
+
+    // Every UUID seen so far, mapped to the chain it belongs to.
+    let mut owner: HashMap<String, usize> = HashMap::new();
+    let mut chains: Vec<Vec<Session>> = Vec::new();
+    for session in sessions {
+        let transcript = parser::parse_file(&session.path)?;
+        let index = continuation_refs(&transcript)
+            .iter()
+            .find_map(|uuid| owner.get(uuid).copied())
+            .unwrap_or_else(|| {
+                chains.push(Vec::new());
+                chains.len() - 1
+            });
+        // Own UUIDs are recorded after the lookup, so a summary
+        // pointing at this file's own leaf never links it to itself.
+        for entry in &transcript.entries {
+            if let Some(uuid) = entry.meta().and_then(|m| m.uuid.clone()) {
+                owner.insert(uuid, index);
+            }
+        }
+        chains[index].push(session);
+    }
+    Ok(chains.into_iter().map(|sessions| Chain { sessions }).collect())
+}
+
+/// UUIDs this transcript claims to continue: leaf UUIDs of its summary
+/// entries plus the parent of its first real entry.
+fn continuation_refs(transcript: &Transcript) -> Vec<String> {
+    let mut refs = Vec::new();
+    for entry in &transcript.entries {
+        if let TranscriptEntry::Summary { leaf_uuid: Some(uuid), .. } = entry {
+            refs.push(uuid.clone());
+        }
+    }
+    if let Some(parent) = transcript
+        .entries
+        .iter()
+        .find_map(|e| e.meta())
+        .and_then(|m| m.parent_uuid.clone())
+    {
+        refs.push(parent);
+    }
+    refs
+}
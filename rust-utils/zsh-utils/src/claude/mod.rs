@@ -0,0 +1,40 @@
+//! Reading and exporting Claude Code session transcripts.
+//!
+//! Claude Code keeps one JSONL transcript per session under
+//! `~/.claude/projects/<encoded-project-path>/<session-uuid>.jsonl`.
+//! This module owns locating those files ([`sessions`]), decoding the
+//! entry format ([`models`], [`parser`]), serving them to the binaries
+//! through a cached data layer ([`store`]), and rendering sessions out
+//! to the export directory ([`export`]).
+
+pub mod anonymize;
+pub mod archive;
+pub mod bundle;
+pub mod chatgpt;
+pub mod checkpoint;
+pub mod dedup;
+pub mod desktop;
+pub mod doctor;
+pub mod export;
+pub mod gitlog;
+pub mod hooks;
+pub mod index;
+pub mod merge;
+pub mod models;
+pub mod notion;
+pub mod org;
+pub mod parser;
+pub mod pdf;
+pub mod picker;
+pub mod pricing;
+pub mod prune;
+pub mod schedule;
+pub mod sessions;
+pub mod site;
+pub mod snapshots;
+pub mod store;
+pub mod sync;
+pub mod tags;
+pub mod timeline;
+pub mod usage;
+pub mod webhook;
@@ -0,0 +1,163 @@
+//! Serde models for the Claude Code transcript JSONL format.
+//!
+//! The format is not documented, so these types are deliberately
+//! permissive: unknown entry kinds and unknown content blocks decode
+//! into `Unknown`/`Other` instead of failing the whole file.
+
+use serde::Deserialize;
+
+/// One line of a transcript file.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TranscriptEntry {
+    /// Rolling conversation summary written by Claude Code itself.
+    Summary {
+        summary: String,
+        #[serde(rename = "leafUuid")]
+        leaf_uuid: Option<String>,
+    },
+    User {
+        #[serde(flatten)]
+        meta: EntryMeta,
+        message: Message,
+    },
+    Assistant {
+        #[serde(flatten)]
+        meta: EntryMeta,
+        message: Message,
+    },
+    System {
+        #[serde(flatten)]
+        meta: EntryMeta,
+        content: Option<String>,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+impl TranscriptEntry {
+    pub fn meta(&self) -> Option<&EntryMeta> {
+        match self {
+            TranscriptEntry::User { meta, .. }
+            | TranscriptEntry::Assistant { meta, .. }
+            | TranscriptEntry::System { meta, .. } => Some(meta),
+            _ => None,
+        }
+    }
+
+    pub fn message(&self) -> Option<&Message> {
+        match self {
+            TranscriptEntry::User { message, .. }
+            | TranscriptEntry::Assistant { message, .. } => Some(message),
+            _ => None,
+        }
+    }
+}
+
+/// Bookkeeping fields shared by user/assistant/system entries.
+#[derive(Debug, Deserialize)]
+pub struct EntryMeta {
+    pub uuid: Option<String>,
+    #[serde(rename = "parentUuid")]
+    pub parent_uuid: Option<String>,
+    #[serde(rename = "sessionId")]
+    pub session_id: Option<String>,
+    /// RFC 3339 timestamp as written by Claude Code.
+    pub timestamp: Option<String>,
+    pub cwd: Option<String>,
+    /// Set on entries that belong to a sub-agent (Task tool) run.
+    #[serde(rename = "isSidechain", default)]
+    pub is_sidechain: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Message {
+    pub role: Option<String>,
+    #[serde(default)]
+    pub content: MessageContent,
+    pub model: Option<String>,
+    pub usage: Option<Usage>,
+}
+
+/// Message content is either a bare string (old entries) or a list of
+/// typed blocks.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum MessageContent {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+impl Default for MessageContent {
+    fn default() -> Self {
+        MessageContent::Text(String::new())
+    }
+}
+
+impl MessageContent {
+    /// Flattens the content down to the human-readable text parts.
+    /// Borrows whenever the text is already in one piece — the common
+    /// case — so callers don't copy megabyte blocks just to read them.
+    pub fn plain_text(&self) -> std::borrow::Cow<'_, str> {
+        match self {
+            MessageContent::Text(text) => std::borrow::Cow::Borrowed(text),
+            MessageContent::Blocks(blocks) => {
+                let texts: Vec<&str> = blocks
+                    .iter()
+                    .filter_map(|b| match b {
+                        ContentBlock::Text { text } => Some(text.as_str()),
+                        _ => None,
+                    })
+                    .collect();
+                match texts.as_slice() {
+                    [only] => std::borrow::Cow::Borrowed(only),
+                    texts => std::borrow::Cow::Owned(texts.join("\n")),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentBlock {
+    Text {
+        text: String,
+    },
+    Thinking {
+        thinking: String,
+    },
+    ToolUse {
+        id: Option<String>,
+        name: String,
+        input: serde_json::Value,
+    },
+    Image {
+        source: ImageSource,
+    },
+    ToolResult {
+        #[serde(rename = "tool_use_id")]
+        tool_use_id: Option<String>,
+        #[serde(default)]
+        content: serde_json::Value,
+        #[serde(default)]
+        is_error: bool,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// Pasted/screenshotted images travel as base64 payloads.
+#[derive(Debug, Deserialize)]
+pub struct ImageSource {
+    pub media_type: Option<String>,
+    pub data: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Usage {
+    pub input_tokens: Option<u64>,
+    pub output_tokens: Option<u64>,
+    pub cache_creation_input_tokens: Option<u64>,
+    pub cache_read_input_tokens: Option<u64>,
+}
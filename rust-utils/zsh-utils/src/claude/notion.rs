@@ -0,0 +1,179 @@
+//! Publishing sessions to a Notion database, one page per session.
+//!
+//! The integration token is looked up in the macOS keychain (service
+//! `notion-token`) with `$NOTION_TOKEN` as the fallback, so the secret
+//! never lands in a dotfile.
+
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+
+use super::models::TranscriptEntry;
+use super::parser::Transcript;
+use super::pricing::Pricing;
+use super::sessions::Session;
+
+const NOTION_VERSION: &str = "2022-06-28";
+/// Notion rejects rich text runs over 2000 characters and block lists
+/// over 100 entries per request.
+const MAX_TEXT: usize = 2_000;
+const MAX_BLOCKS: usize = 100;
+
+pub struct NotionClient {
+    token: String,
+    database_id: String,
+    http: reqwest::blocking::Client,
+}
+
+impl NotionClient {
+    pub fn new(database_id: String) -> Result<Self> {
+        Ok(Self {
+            token: find_token()?,
+            database_id,
+            http: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Creates the page and returns its URL. Long transcripts are
+    /// appended in follow-up batches to stay under the block limit.
+    pub fn publish_session(
+        &self,
+        session: &Session,
+        transcript: &Transcript,
+        pricing: &Pricing,
+    ) -> Result<String> {
+        let blocks = transcript_blocks(transcript);
+        let mut batches = blocks.chunks(MAX_BLOCKS);
+        let first: Vec<Value> = batches.next().unwrap_or_default().to_vec();
+
+        let page = json!({
+            "parent": { "database_id": self.database_id },
+            "properties": self.properties(session, transcript, pricing),
+            "children": first,
+        });
+        let created: Value = self
+            .http
+            .post("https://api.notion.com/v1/pages")
+            .bearer_auth(&self.token)
+            .header("Notion-Version", NOTION_VERSION)
+            .json(&page)
+            .send()
+            .context("creating Notion page")?
+            .error_for_status()
+            .context("Notion rejected the page")?
+            .json()
+            .context("decoding Notion response")?;
+        let page_id = created["id"]
+            .as_str()
+            .context("Notion response has no page id")?
+            .to_string();
+
+        for batch in batches {
+            self.http
+                .patch(format!(
+                    "https://api.notion.com/v1/blocks/{page_id}/children"
+                ))
+                .bearer_auth(&self.token)
+                .header("Notion-Version", NOTION_VERSION)
+                .json(&json!({ "children": batch }))
+                .send()
+                .context("appending transcript blocks")?
+                .error_for_status()
+                .context("Notion rejected transcript blocks")?;
+        }
+
+        Ok(created["url"].as_str().unwrap_or(&page_id).to_string())
+    }
+
+    fn properties(
+        &self,
+        session: &Session,
+        transcript: &Transcript,
+        pricing: &Pricing,
+    ) -> Value {
+        let mut tokens: u64 = 0;
+        for usage in transcript
+            .entries
+            .iter()
+            .filter_map(|e| e.message())
+            .filter_map(|m| m.usage.as_ref())
+        {
+            tokens += usage.input_tokens.unwrap_or(0) + usage.output_tokens.unwrap_or(0);
+        }
+        let mut properties = json!({
+            "Name": { "title": [text_run(&format!(
+                "{} — {}", session.project.friendly_name(), session.id
+            ))] },
+            "Project": { "rich_text": [text_run(&session.project.friendly_name())] },
+            "Tokens": { "number": tokens },
+            "Cost": { "number": pricing.estimate(transcript).total_usd },
+        });
+        if let Some(start) = session.start_time() {
+            properties["Date"] = json!({ "date": { "start": start.to_rfc3339() } });
+        }
+        properties
+    }
+}
+
+/// Keychain first, `$NOTION_TOKEN` second.
+fn find_token() -> Result<String> {
+    if let Ok(output) = Command::new("security")
+        .args(["find-generic-password", "-s", "notion-token", "-w"])
+        .output()
+    {
+        if output.status.success() {
+            let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if !token.is_empty() {
+                return Ok(token);
+            }
+        }
+    }
+    std::env::var("NOTION_TOKEN").context(
+        "no Notion token: add one to the keychain (service notion-token) \
+         or set NOTION_TOKEN",
+    )
+}
+
+fn text_run(content: &str) -> Value {
+    let content: String = content.chars().take(MAX_TEXT).collect();
+    json!({ "text": { "content": content } })
+}
+
+fn paragraphs(text: &str, blocks: &mut Vec<Value>) {
+    let chars: Vec<char> = text.trim().chars().collect();
+    for chunk in chars.chunks(MAX_TEXT) {
+        let run: String = chunk.iter().collect();
+        blocks.push(json!({
+            "object": "block",
+            "type": "paragraph",
+            "paragraph": { "rich_text": [text_run(&run)] },
+        }));
+    }
+}
+
+fn transcript_blocks(transcript: &Transcript) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    for entry in &transcript.entries {
+        let heading = match entry {
+            TranscriptEntry::User { .. } => "User",
+            TranscriptEntry::Assistant { .. } => "Assistant",
+            _ => continue,
+        };
+        let text = entry
+            .message()
+            .expect("user/assistant have messages")
+            .content
+            .plain_text();
+        if text.trim().is_empty() {
+            continue;
+        }
+        blocks.push(json!({
+            "object": "block",
+            "type": "heading_3",
+            "heading_3": { "rich_text": [text_run(heading)] },
+        }));
+        paragraphs(&text, &mut blocks);
+    }
+    blocks
+}
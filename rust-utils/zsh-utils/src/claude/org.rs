@@ -0,0 +1,235 @@
+//! Org-mode rendering for `--format org`.
+//!
+//! The same information as the Markdown layout in Emacs clothing:
+//! session metadata goes into a properties drawer on the top heading,
+//! tool inputs into `#+BEGIN_SRC json` blocks, and tool results into
+//! `:RESULTS:` drawers, which Org folds by default — exactly what a
+//! wall of Bash output wants. Sidechain entries keep their own
+//! headings but carry a `:sidechain:` tag so agenda filters can skip
+//! them.
+
+use std::collections::BTreeMap;
+
+use super::export::{self, RenderOptions};
+use super::models::{ContentBlock, MessageContent, TranscriptEntry};
+use super::parser::Transcript;
+use super::pricing::Pricing;
+use super::sessions::Session;
+
+pub fn render_org(
+    session: &Session,
+    transcript: &Transcript,
+    pricing: &Pricing,
+    options: &RenderOptions,
+    tags: &[String],
+) -> String {
+    let mut out = String::new();
+    let title = export::session_title(transcript)
+        .unwrap_or_else(|| format!("Session {}", session.id));
+    out.push_str(&format!("#+TITLE: {title}\n"));
+    out.push_str("#+STARTUP: overview\n\n");
+
+    // Session tags become Org heading tags, which sub-headings inherit,
+    // so `org-agenda` and sparse trees can filter whole sessions.
+    if tags.is_empty() {
+        out.push_str(&format!("* {title}\n"));
+    } else {
+        out.push_str(&format!("* {title} :{}:\n", tags.join(":")));
+    }
+    out.push_str(":PROPERTIES:\n");
+    out.push_str(&format!(":SESSION_ID: {}\n", session.id));
+    out.push_str(&format!(":PROJECT: {}\n", session.project.friendly_name()));
+    out.push_str(&format!(":ENTRIES: {}\n", transcript.entries.len()));
+    if let Some(model) = transcript
+        .entries
+        .iter()
+        .filter_map(|e| e.message())
+        .find_map(|m| m.model.as_deref())
+    {
+        out.push_str(&format!(":MODEL: {model}\n"));
+    }
+    if options.sections.tokens {
+        let estimate = pricing.estimate(transcript);
+        if !estimate.by_model.is_empty() {
+            out.push_str(&format!(":ESTIMATED_COST_USD: {:.4}\n", estimate.total_usd));
+        }
+    }
+    out.push_str(":END:\n\n");
+
+    if options.sections.summary {
+        let summaries: Vec<&str> = transcript
+            .entries
+            .iter()
+            .filter_map(|e| match e {
+                TranscriptEntry::Summary { summary, .. } => Some(summary.as_str()),
+                _ => None,
+            })
+            .collect();
+        if !summaries.is_empty() {
+            out.push_str("** Session Summary\n\n");
+            for summary in summaries {
+                out.push_str(&escape_text(summary));
+                out.push_str("\n\n");
+            }
+        }
+    }
+
+    if options.sections.transcript {
+        out.push_str("** Conversation\n\n");
+        // Results are tied to their tool via `tool_use_id`, mirroring
+        // the Markdown renderer's filtering.
+        let mut tool_names = std::collections::HashMap::new();
+        for message in transcript.entries.iter().filter_map(|e| e.message()) {
+            if let MessageContent::Blocks(blocks) = &message.content {
+                for block in blocks {
+                    if let ContentBlock::ToolUse { id: Some(id), name, .. } = block {
+                        tool_names.insert(id.as_str(), name.as_str());
+                    }
+                }
+            }
+        }
+        for entry in &transcript.entries {
+            render_entry(entry, &tool_names, options, &mut out);
+        }
+    }
+    out
+}
+
+fn render_entry(
+    entry: &TranscriptEntry,
+    tool_names: &std::collections::HashMap<&str, &str>,
+    options: &RenderOptions,
+    out: &mut String,
+) {
+    let speaker = match entry {
+        TranscriptEntry::User { .. } => "User",
+        TranscriptEntry::Assistant { .. } => "Assistant",
+        _ => return,
+    };
+    let tag = if entry.meta().is_some_and(|m| m.is_sidechain) {
+        " :sidechain:"
+    } else {
+        ""
+    };
+    out.push_str(&format!("*** {speaker}{tag}\n\n"));
+    let message = entry.message().expect("user/assistant have messages");
+    match &message.content {
+        MessageContent::Text(text) => {
+            if !text.trim().is_empty() {
+                out.push_str(&escape_text(text.trim_end()));
+                out.push_str("\n\n");
+            }
+        }
+        MessageContent::Blocks(blocks) => {
+            let mut ignored: BTreeMap<&str, usize> = BTreeMap::new();
+            for block in blocks {
+                render_block(block, tool_names, options, &mut ignored, out);
+            }
+            if !ignored.is_empty() {
+                let counts: Vec<String> = ignored
+                    .iter()
+                    .map(|(name, count)| format!("{count}× {name}"))
+                    .collect();
+                out.push_str(&format!("(hidden: {} calls)\n\n", counts.join(", ")));
+            }
+        }
+    }
+}
+
+fn render_block<'a>(
+    block: &'a ContentBlock,
+    tool_names: &std::collections::HashMap<&str, &str>,
+    options: &RenderOptions,
+    ignored: &mut BTreeMap<&'a str, usize>,
+    out: &mut String,
+) {
+    let tool_allowed = |name: &str| {
+        options.tools.as_ref().is_none_or(|allow| allow.contains(name))
+    };
+    match block {
+        ContentBlock::Text { text } => {
+            if !text.trim().is_empty() {
+                out.push_str(&escape_text(text.trim_end()));
+                out.push_str("\n\n");
+            }
+        }
+        ContentBlock::ToolUse { name, input, .. } => {
+            if options.ignored.contains(name) {
+                *ignored.entry(name.as_str()).or_default() += 1;
+                return;
+            }
+            if !tool_allowed(name) {
+                return;
+            }
+            out.push_str(&format!("**** Tool: {name}\n\n"));
+            out.push_str("#+BEGIN_SRC json\n");
+            out.push_str(&escape_block(
+                &serde_json::to_string_pretty(input).unwrap_or_default(),
+            ));
+            out.push_str("\n#+END_SRC\n\n");
+        }
+        ContentBlock::ToolResult { tool_use_id, content, is_error } => {
+            if !options.tool_results {
+                return;
+            }
+            let name = tool_use_id.as_deref().and_then(|id| tool_names.get(id));
+            if name.is_some_and(|name| options.ignored.contains(*name)) {
+                return;
+            }
+            if let (Some(allow), Some(name)) = (&options.tools, name) {
+                if !allow.contains(*name) {
+                    return;
+                }
+            }
+            let text = export::tool_result_text(content);
+            let (text, omitted) = export::truncate_lines(&text, options.max_tool_output);
+            if text.trim().is_empty() {
+                return;
+            }
+            let label = if *is_error { "RESULTS (error)" } else { "RESULTS" };
+            out.push_str(&format!(":{label}:\n#+BEGIN_EXAMPLE\n"));
+            out.push_str(&escape_block(text.trim_end()));
+            if omitted > 0 {
+                out.push_str(&format!("\n... (+{omitted} more lines)"));
+            }
+            out.push_str("\n#+END_EXAMPLE\n:END:\n\n");
+        }
+        ContentBlock::Thinking { .. } | ContentBlock::Image { .. } | ContentBlock::Other => {}
+    }
+}
+
+/// Body text: a line starting with `*` would become a heading and
+/// swallow everything after it, so such lines get a leading space —
+/// invisible in Org, structurally inert.
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for (i, line) in text.lines().enumerate() {
+        if i > 0 {
+            escaped.push('\n');
+        }
+        if line.starts_with('*') {
+            escaped.push(' ');
+        }
+        escaped.push_str(line);
+    }
+    escaped
+}
+
+/// Inside src/example blocks Org's own convention applies: lines
+/// starting with `*` or `#+` are comma-escaped (what
+/// `org-escape-code-in-region` does), and Org strips the comma back
+/// out on extraction.
+fn escape_block(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for (i, line) in text.lines().enumerate() {
+        if i > 0 {
+            escaped.push('\n');
+        }
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('*') || trimmed.starts_with("#+") {
+            escaped.push(',');
+        }
+        escaped.push_str(line);
+    }
+    escaped
+}
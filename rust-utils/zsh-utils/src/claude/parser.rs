@@ -0,0 +1,138 @@
+//! Line-by-line JSONL parsing with tolerance for the occasional broken
+//! line — a crashed session can leave a truncated last entry and that
+//! should never make a whole transcript unreadable.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use super::models::TranscriptEntry;
+use crate::logger;
+
+pub struct Transcript {
+    pub entries: Vec<TranscriptEntry>,
+    /// Number of lines that failed to decode and were skipped.
+    pub skipped: usize,
+}
+
+/// Above this size the file is memory-mapped and scanned in place
+/// instead of copied into a `String` first; multi-gigabyte transcript
+/// corpora otherwise spend bulk export mostly allocating.
+const MMAP_THRESHOLD: u64 = 8 * 1024 * 1024;
+
+pub fn parse_file(path: &Path) -> Result<Transcript> {
+    let size = path
+        .metadata()
+        .with_context(|| format!("reading transcript {}", path.display()))?
+        .len();
+    let transcript = if size >= MMAP_THRESHOLD {
+        parse_file_mmap(path)?
+    } else {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("reading transcript {}", path.display()))?;
+        parse_str(&raw)
+    };
+    if transcript.skipped > 0 {
+        logger::warn(format!(
+            "skipped {} malformed lines in {}",
+            transcript.skipped,
+            path.display()
+        ));
+    }
+    Ok(transcript)
+}
+
+/// The mmap path: split on newlines in the mapped bytes and hand each
+/// line to serde directly, no per-line buffer. Skipping semantics
+/// match [`parse_str`] exactly — invalid UTF-8 just shows up as one
+/// more line that fails to decode.
+fn parse_file_mmap(path: &Path) -> Result<Transcript> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("opening transcript {}", path.display()))?;
+    // Safety: transcripts are append-only session logs; nothing
+    // truncates one while an export reads it.
+    let map = unsafe { memmap2::Mmap::map(&file) }
+        .with_context(|| format!("mapping transcript {}", path.display()))?;
+    let mut entries = Vec::new();
+    let mut skipped = 0;
+    for line in map.split(|b| *b == b'\n') {
+        if line.iter().all(|b| b.is_ascii_whitespace()) {
+            continue;
+        }
+        match serde_json::from_slice::<TranscriptEntry>(line) {
+            Ok(entry) => entries.push(entry),
+            Err(_) => skipped += 1,
+        }
+    }
+    Ok(Transcript { entries, skipped })
+}
+
+/// Lazily decoded entries of one transcript file, for callers that
+/// fold rather than collect — a 100MB transcript should not cost 100MB
+/// of entries just to sum its token counts. Malformed and unreadable
+/// lines are counted, not surfaced, matching [`parse_str`]; ask
+/// [`EntryStream::skipped`] after draining.
+pub struct EntryStream {
+    lines: std::io::Lines<std::io::BufReader<std::fs::File>>,
+    skipped: usize,
+}
+
+impl EntryStream {
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+}
+
+impl Iterator for EntryStream {
+    type Item = TranscriptEntry;
+
+    fn next(&mut self) -> Option<TranscriptEntry> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(_) => {
+                    self.skipped += 1;
+                    continue;
+                }
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(entry) => return Some(entry),
+                Err(_) => self.skipped += 1,
+            }
+        }
+    }
+}
+
+/// Opens `path` for streaming instead of loading it whole; the
+/// iterator yields what [`parse_file`] would have put in `entries`.
+pub fn stream_file(path: &Path) -> Result<EntryStream> {
+    use std::io::BufRead;
+
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("opening transcript {}", path.display()))?;
+    Ok(EntryStream {
+        lines: std::io::BufReader::new(file).lines(),
+        skipped: 0,
+    })
+}
+
+/// Parses transcript content that is already in memory. Every non-blank
+/// line either becomes an entry or is counted in `skipped`; nothing is
+/// dropped silently and no input can make this fail.
+pub fn parse_str(raw: &str) -> Transcript {
+    let mut entries = Vec::new();
+    let mut skipped = 0;
+    for line in raw.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<TranscriptEntry>(line) {
+            Ok(entry) => entries.push(entry),
+            Err(_) => skipped += 1,
+        }
+    }
+    Transcript { entries, skipped }
+}
@@ -0,0 +1,107 @@
+//! PDF export, for attaching session records to design docs.
+//!
+//! Typesetting goes through the existing HTML pipeline ([`site`]'s
+//! styling, so code blocks and tables come out right) and an external
+//! HTML-to-PDF converter — wkhtmltopdf, weasyprint, or headless
+//! Chrome, whichever is installed. Bundling a PDF engine into the
+//! binary is not worth the build cost when every machine this runs on
+//! has at least one of those.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use super::site;
+
+/// Converts a session's Markdown rendering to `out` (a `.pdf` path).
+/// A table of contents built from the `##` sections goes up front.
+pub fn export_pdf(title: &str, markdown: &str, out: &Path) -> Result<()> {
+    let (toc, body) = with_toc(markdown);
+    let html = site::page(title, &format!("{toc}{}", site::markdown_to_html(&body)));
+
+    let html_path = std::env::temp_dir().join(format!(
+        "claude-export-{}.html",
+        std::process::id()
+    ));
+    std::fs::write(&html_path, html)
+        .with_context(|| format!("writing {}", html_path.display()))?;
+    let result = convert(&html_path, out);
+    let _ = std::fs::remove_file(&html_path);
+    result
+}
+
+/// Builds a linked table of contents from the `##` headings and gives
+/// each one an anchor (inline HTML passes through the Markdown
+/// renderer untouched).
+fn with_toc(markdown: &str) -> (String, String) {
+    let mut toc = String::from("<nav><h2>Contents</h2><ul>\n");
+    let mut body = String::new();
+    let mut section = 0;
+    let mut in_fence = false;
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if !in_fence && line.starts_with("## ") {
+            section += 1;
+            let heading = site::escape(line.trim_start_matches("## "));
+            toc.push_str(&format!(
+                "<li><a href=\"#sec-{section}\">{heading}</a></li>\n"
+            ));
+            body.push_str(&format!("<a id=\"sec-{section}\"></a>\n\n"));
+        }
+        body.push_str(line);
+        body.push('\n');
+    }
+    toc.push_str("</ul></nav>\n");
+    if section == 0 {
+        return (String::new(), body);
+    }
+    (toc, body)
+}
+
+/// The converters tried in order: (binary, args before input/output).
+fn convert(html: &Path, out: &Path) -> Result<()> {
+    let attempts: [(&str, Vec<String>); 3] = [
+        ("wkhtmltopdf", vec![arg(html), arg(out)]),
+        ("weasyprint", vec![arg(html), arg(out)]),
+        (
+            "chromium",
+            vec![
+                "--headless".to_string(),
+                "--disable-gpu".to_string(),
+                format!("--print-to-pdf={}", out.display()),
+                arg(html),
+            ],
+        ),
+    ];
+    for (binary, args) in &attempts {
+        match Command::new(binary).args(args).output() {
+            // Converter exists but failed: report it rather than
+            // silently trying a worse one.
+            Ok(output) if output.status.success() => {
+                crate::audit::record(
+                    &format!("{binary} {}", args.join(" ")),
+                    &std::env::current_dir().unwrap_or_default(),
+                    output.status.code(),
+                );
+                return Ok(());
+            }
+            Ok(output) => anyhow::bail!(
+                "{binary} failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            // Not installed; try the next converter.
+            Err(_) => continue,
+        }
+    }
+    anyhow::bail!(
+        "no HTML-to-PDF converter found — install wkhtmltopdf, weasyprint, \
+         or chromium"
+    )
+}
+
+fn arg(path: &Path) -> String {
+    path.display().to_string()
+}
@@ -0,0 +1,343 @@
+//! Interactive multi-select session picker for `claude-export`.
+//!
+//! Shows one row per session — project, start date, token count, and a
+//! preview of the first user message — and lets the user toggle exactly
+//! the sessions they want before exporting.
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use ratatui::Terminal;
+
+use super::models::TranscriptEntry;
+use super::parser;
+use super::sessions::{Project, ProjectMatcher, Session};
+use crate::glyphs;
+use crate::term::{self, bordered};
+
+const TITLE: &str = " sessions — Space select · a all · Enter export · q quit ";
+const TITLE_ASCII: &str = " sessions - Space select / a all / Enter export / q quit ";
+const PROJECT_TITLE: &str = " projects — type to filter · Enter choose · Esc quit ";
+const PROJECT_TITLE_ASCII: &str = " projects - type to filter / Enter choose / Esc quit ";
+
+struct Row {
+    session: Session,
+    project: String,
+    date: String,
+    tokens: u64,
+    preview: String,
+    selected: bool,
+}
+
+impl Row {
+    fn new(session: Session) -> Self {
+        let (tokens, preview) = summarize(&session);
+        let date = session
+            .start_time()
+            .map(|t| t.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "----------".to_string());
+        Self {
+            project: session.project.friendly_name(),
+            date,
+            tokens,
+            preview,
+            session,
+            selected: false,
+        }
+    }
+}
+
+/// Total tokens and a one-line preview of the first user message. A
+/// session that fails to open still gets a row, just an empty one —
+/// hiding it would make the picker lie about what exists. Entries are
+/// streamed (the picker touches every transcript at once, and some
+/// are huge) and folded into the two values as they pass.
+fn summarize(session: &Session) -> (u64, String) {
+    let Ok(entries) = parser::stream_file(&session.path) else {
+        return (0, String::new());
+    };
+    let mut tokens = 0;
+    let mut preview = String::new();
+    for entry in entries {
+        if preview.is_empty() {
+            if let TranscriptEntry::User { message, .. } = &entry {
+                let text = message.content.plain_text();
+                preview = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            }
+        }
+        if let Some(usage) = entry.message().and_then(|m| m.usage.as_ref()) {
+            tokens +=
+                usage.input_tokens.unwrap_or(0) + usage.output_tokens.unwrap_or(0);
+        }
+    }
+    (tokens, preview)
+}
+
+/// Runs the picker over `sessions` and returns the ones the user
+/// confirmed with Enter. Quitting without confirming returns an empty
+/// list.
+pub fn pick_sessions(sessions: Vec<Session>) -> Result<Vec<Session>> {
+    let mut rows: Vec<Row> = sessions.into_iter().map(Row::new).collect();
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    crossterm::terminal::enable_raw_mode()?;
+    let mut stdout = std::io::stdout();
+    crossterm::execute!(stdout, crossterm::terminal::EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let result = event_loop(&mut terminal, &mut rows);
+
+    crossterm::terminal::disable_raw_mode()?;
+    crossterm::execute!(
+        terminal.backend_mut(),
+        crossterm::terminal::LeaveAlternateScreen
+    )?;
+
+    let confirmed = result?;
+    if !confirmed {
+        return Ok(Vec::new());
+    }
+    Ok(rows
+        .into_iter()
+        .filter(|r| r.selected)
+        .map(|r| r.session)
+        .collect())
+}
+
+struct ProjectRow {
+    project: Project,
+    name: String,
+    sessions: usize,
+    last_activity: String,
+}
+
+impl ProjectRow {
+    fn new(project: Project) -> Self {
+        let sessions = project.sessions().unwrap_or_default();
+        let last_activity = sessions
+            .first()
+            .map(|s| {
+                chrono::DateTime::<chrono::Local>::from(s.modified())
+                    .format("%Y-%m-%d")
+                    .to_string()
+            })
+            .unwrap_or_else(|| "----------".to_string());
+        Self {
+            name: project.friendly_name(),
+            sessions: sessions.len(),
+            last_activity,
+            project,
+        }
+    }
+}
+
+/// Single-select project picker with type-to-filter fuzzy search.
+/// Returns `None` when the user quits without choosing.
+pub fn pick_project(projects: Vec<Project>) -> Result<Option<Project>> {
+    let rows: Vec<ProjectRow> = projects.into_iter().map(ProjectRow::new).collect();
+    if rows.is_empty() {
+        return Ok(None);
+    }
+
+    crossterm::terminal::enable_raw_mode()?;
+    let mut stdout = std::io::stdout();
+    crossterm::execute!(stdout, crossterm::terminal::EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let result = project_event_loop(&mut terminal, &rows);
+
+    crossterm::terminal::disable_raw_mode()?;
+    crossterm::execute!(
+        terminal.backend_mut(),
+        crossterm::terminal::LeaveAlternateScreen
+    )?;
+
+    result
+}
+
+fn project_event_loop(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    rows: &[ProjectRow],
+) -> Result<Option<Project>> {
+    let mut state = ListState::default();
+    state.select(Some(0));
+    let mut filter = String::new();
+    loop {
+        let visible = filtered(rows, &filter);
+        if state.selected().unwrap_or(0) >= visible.len() {
+            state.select(Some(visible.len().saturating_sub(1)));
+        }
+        terminal.draw(|frame| draw_projects(frame, &visible, &filter, &mut state))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        let cursor = state.selected().unwrap_or(0);
+        match (key.code, key.modifiers) {
+            (KeyCode::Char('c'), KeyModifiers::CONTROL) => return Ok(None),
+            (KeyCode::Esc, _) => return Ok(None),
+            (KeyCode::Enter, _) => {
+                return Ok(visible.get(cursor).map(|r| r.project.clone()));
+            }
+            (KeyCode::Backspace, _) => {
+                filter.pop();
+            }
+            (KeyCode::Up, _) => state.select(Some(cursor.saturating_sub(1))),
+            (KeyCode::Down, _) => {
+                state.select(Some((cursor + 1).min(visible.len().saturating_sub(1))));
+            }
+            (KeyCode::Char(c), _) => filter.push(c),
+            _ => {}
+        }
+    }
+}
+
+/// Rows matching the filter, best score first; an empty filter shows
+/// everything in the original order.
+fn filtered<'a>(rows: &'a [ProjectRow], filter: &str) -> Vec<&'a ProjectRow> {
+    if filter.trim().is_empty() {
+        return rows.iter().collect();
+    }
+    let matcher = ProjectMatcher::new(filter).with_min_score(0.5);
+    let mut visible: Vec<(&ProjectRow, f64)> = rows
+        .iter()
+        .map(|r| (r, matcher.score(&r.name)))
+        .filter(|(_, score)| *score >= 0.5)
+        .collect();
+    visible.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    visible.into_iter().map(|(r, _)| r).collect()
+}
+
+fn draw_projects(
+    frame: &mut ratatui::Frame,
+    visible: &[&ProjectRow],
+    filter: &str,
+    state: &mut ListState,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(frame.size());
+
+    let name_width = visible.iter().map(|r| r.name.len()).max().unwrap_or(0);
+    let items: Vec<ListItem> = visible
+        .iter()
+        .map(|row| {
+            ListItem::new(Line::from(format!(
+                " {:name_width$}  {:>3} sessions  last {}",
+                row.name, row.sessions, row.last_activity
+            )))
+        })
+        .collect();
+
+    let title = glyphs::pick(PROJECT_TITLE, PROJECT_TITLE_ASCII);
+    let list = List::new(items)
+        .block(bordered(title))
+        .highlight_style(term::themed(
+            Style::default().add_modifier(Modifier::REVERSED),
+        ));
+    frame.render_stateful_widget(list, chunks[0], state);
+
+    let bar = Paragraph::new(format!(" filter: {filter}"))
+        .style(term::themed(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(bar, chunks[1]);
+}
+
+/// Returns whether the user confirmed (Enter) rather than quit.
+fn event_loop(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    rows: &mut [Row],
+) -> Result<bool> {
+    let mut state = ListState::default();
+    state.select(Some(0));
+    loop {
+        terminal.draw(|frame| draw(frame, rows, &mut state))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        let cursor = state.selected().unwrap_or(0);
+        match (key.code, key.modifiers) {
+            (KeyCode::Char('c'), KeyModifiers::CONTROL) => return Ok(false),
+            (KeyCode::Char('q') | KeyCode::Esc, _) => return Ok(false),
+            (KeyCode::Enter, _) => return Ok(true),
+            (KeyCode::Char(' '), _) => {
+                rows[cursor].selected = !rows[cursor].selected;
+                state.select(Some((cursor + 1).min(rows.len() - 1)));
+            }
+            (KeyCode::Char('a'), _) => {
+                let all = rows.iter().all(|r| r.selected);
+                for row in rows.iter_mut() {
+                    row.selected = !all;
+                }
+            }
+            (KeyCode::Up | KeyCode::Char('k'), _) => {
+                state.select(Some(cursor.saturating_sub(1)));
+            }
+            (KeyCode::Down | KeyCode::Char('j'), _) => {
+                state.select(Some((cursor + 1).min(rows.len() - 1)));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn draw(frame: &mut ratatui::Frame, rows: &[Row], state: &mut ListState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(frame.size());
+
+    let width = chunks[0].width.saturating_sub(2) as usize;
+    let project_width = rows.iter().map(|r| r.project.len()).max().unwrap_or(0);
+    let items: Vec<ListItem> = rows
+        .iter()
+        .map(|row| ListItem::new(Line::from(render_row(row, project_width, width))))
+        .collect();
+
+    let title = glyphs::pick(TITLE, TITLE_ASCII);
+    let list = List::new(items)
+        .block(bordered(title))
+        .highlight_style(term::themed(
+            Style::default().add_modifier(Modifier::REVERSED),
+        ));
+    frame.render_stateful_widget(list, chunks[0], state);
+
+    let picked = rows.iter().filter(|r| r.selected).count();
+    let bar = Paragraph::new(format!(" {picked} of {} selected", rows.len()))
+        .style(term::themed(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(bar, chunks[1]);
+}
+
+fn render_row(row: &Row, project_width: usize, width: usize) -> String {
+    let mark = if row.selected {
+        glyphs::pick("●", "x")
+    } else {
+        glyphs::pick("○", " ")
+    };
+    let mut line = format!(
+        "[{mark}] {:project_width$}  {}  {:>7}  ",
+        row.project,
+        row.date,
+        human_tokens(row.tokens),
+    );
+    for c in row.preview.chars() {
+        if line.chars().count() >= width {
+            break;
+        }
+        line.push(c);
+    }
+    line
+}
+
+fn human_tokens(tokens: u64) -> String {
+    match tokens {
+        0..=999 => format!("{tokens} tok"),
+        1_000..=999_999 => format!("{:.1}k tok", tokens as f64 / 1_000.0),
+        _ => format!("{:.1}M tok", tokens as f64 / 1_000_000.0),
+    }
+}
@@ -0,0 +1,159 @@
+//! Per-model pricing for cost estimates on exported sessions.
+//!
+//! Rates are USD per million tokens and match models by name prefix, so
+//! dated releases (`claude-3-opus-20240229`) pick up their family rate.
+//! The built-in table can be extended or corrected without a rebuild
+//! via a TOML file passed to `--pricing-file`:
+//!
+//! ```toml
+//! [models."claude-3-opus"]
+//! input = 15.0
+//! output = 75.0
+//! cache_creation = 18.75
+//! cache_read = 1.5
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::models::TranscriptEntry;
+use super::parser::Transcript;
+
+/// USD per million tokens, by token category.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Rates {
+    pub input: f64,
+    pub output: f64,
+    #[serde(default)]
+    pub cache_creation: f64,
+    #[serde(default)]
+    pub cache_read: f64,
+}
+
+/// Built-in table, longest-prefix order does not matter — lookup picks
+/// the longest match.
+const BUILTIN: &[(&str, Rates)] = &[
+    (
+        "claude-3-opus",
+        Rates { input: 15.0, output: 75.0, cache_creation: 18.75, cache_read: 1.5 },
+    ),
+    (
+        "claude-3-5-sonnet",
+        Rates { input: 3.0, output: 15.0, cache_creation: 3.75, cache_read: 0.3 },
+    ),
+    (
+        "claude-3-5-haiku",
+        Rates { input: 0.8, output: 4.0, cache_creation: 1.0, cache_read: 0.08 },
+    ),
+    (
+        "claude-3-haiku",
+        Rates { input: 0.25, output: 1.25, cache_creation: 0.3, cache_read: 0.03 },
+    ),
+    (
+        "claude-sonnet",
+        Rates { input: 3.0, output: 15.0, cache_creation: 3.75, cache_read: 0.3 },
+    ),
+    (
+        "claude-opus",
+        Rates { input: 15.0, output: 75.0, cache_creation: 18.75, cache_read: 1.5 },
+    ),
+    (
+        "claude-haiku",
+        Rates { input: 0.8, output: 4.0, cache_creation: 1.0, cache_read: 0.08 },
+    ),
+];
+
+#[derive(Deserialize)]
+struct PricingFile {
+    #[serde(default)]
+    models: BTreeMap<String, Rates>,
+}
+
+pub struct Pricing {
+    /// Model-name prefix to rates; overrides shadow built-ins.
+    table: BTreeMap<String, Rates>,
+}
+
+/// What a transcript cost, split the way people ask about it.
+#[derive(Default)]
+pub struct CostEstimate {
+    /// Total USD, rounded to micro-dollar precision so serialized
+    /// values round-trip exactly.
+    pub total_usd: f64,
+    pub by_model: BTreeMap<String, f64>,
+    /// Models that had usage but no pricing entry; their tokens are not
+    /// in the total.
+    pub unpriced: BTreeSet<String>,
+}
+
+impl Pricing {
+    pub fn builtin() -> Self {
+        Self {
+            table: BUILTIN
+                .iter()
+                .map(|(name, rates)| (name.to_string(), *rates))
+                .collect(),
+        }
+    }
+
+    /// Built-in table plus overrides from a `--pricing-file` TOML.
+    pub fn with_overrides(path: &Path) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("reading pricing file {}", path.display()))?;
+        let file: PricingFile =
+            toml::from_str(&raw).context("parsing pricing file")?;
+        let mut pricing = Self::builtin();
+        pricing.table.extend(file.models);
+        Ok(pricing)
+    }
+
+    /// Longest matching name prefix wins, so an exact override beats a
+    /// family rate.
+    pub fn rates_for(&self, model: &str) -> Option<Rates> {
+        self.table
+            .iter()
+            .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, rates)| *rates)
+    }
+
+    /// Sums cost message by message, using each message's own model.
+    pub fn estimate(&self, transcript: &Transcript) -> CostEstimate {
+        let mut estimate = CostEstimate::default();
+        let mut total = 0.0;
+        for entry in &transcript.entries {
+            if !matches!(entry, TranscriptEntry::Assistant { .. }) {
+                continue;
+            }
+            let message = entry.message().expect("assistant entries have messages");
+            let (Some(model), Some(usage)) = (&message.model, &message.usage) else {
+                continue;
+            };
+            let Some(rates) = self.rates_for(model) else {
+                estimate.unpriced.insert(model.clone());
+                continue;
+            };
+            let cost = (usage.input_tokens.unwrap_or(0) as f64 * rates.input
+                + usage.output_tokens.unwrap_or(0) as f64 * rates.output
+                + usage.cache_creation_input_tokens.unwrap_or(0) as f64
+                    * rates.cache_creation
+                + usage.cache_read_input_tokens.unwrap_or(0) as f64
+                    * rates.cache_read)
+                / 1_000_000.0;
+            total += cost;
+            *estimate.by_model.entry(model.clone()).or_insert(0.0) += cost;
+        }
+        for cost in estimate.by_model.values_mut() {
+            *cost = round_usd(*cost);
+        }
+        estimate.total_usd = round_usd(total);
+        estimate
+    }
+}
+
+fn round_usd(usd: f64) -> f64 {
+    (usd * 1_000_000.0).round() / 1_000_000.0
+}
@@ -0,0 +1,172 @@
+//! Retention policy for the Claude home (`claude-prune`).
+//!
+//! Transcripts under `~/.claude/projects` and tool-use snapshots under
+//! `~/.claude/file-history` grow without bound; after a few months the
+//! directory runs to gigabytes. This module turns a [`Policy`] into an
+//! explicit [`Plan`] first — what gets deleted, how much comes back —
+//! so the binary can show a dry run, and only `Plan::apply` touches
+//! the filesystem. Tagged sessions (see [`super::tags`]) are spared by
+//! default: somebody labeled them on purpose.
+
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+
+use super::sessions;
+use super::tags::Tags;
+
+pub struct Policy {
+    /// Delete entries untouched for more than this many days.
+    pub max_age_days: Option<u64>,
+    /// Keep the newest entries up to this many bytes; whatever is
+    /// older than the budget allows goes.
+    pub max_total_bytes: Option<u64>,
+    /// Spare transcripts that carry tags, whatever their age or size.
+    pub keep_tagged: bool,
+}
+
+/// One file the plan will delete.
+pub struct Candidate {
+    pub path: PathBuf,
+    pub bytes: u64,
+    pub age_days: u64,
+    pub reason: &'static str,
+}
+
+pub struct Plan {
+    pub remove: Vec<Candidate>,
+    /// Files surviving the policy, and their total size.
+    pub kept: usize,
+    pub kept_bytes: u64,
+    /// Transcripts the policy would have deleted but their tags saved.
+    pub spared_tagged: usize,
+}
+
+impl Plan {
+    pub fn reclaimed_bytes(&self) -> u64 {
+        self.remove.iter().map(|c| c.bytes).sum()
+    }
+
+    /// Deletes everything the plan marked and returns the bytes freed.
+    /// Emptied project directories are removed too.
+    pub fn apply(&self) -> Result<u64> {
+        let mut freed = 0;
+        for candidate in &self.remove {
+            std::fs::remove_file(&candidate.path)
+                .with_context(|| format!("deleting {}", candidate.path.display()))?;
+            freed += candidate.bytes;
+            // remove_dir only succeeds on empty directories, which is
+            // exactly the cleanup wanted here.
+            if let Some(parent) = candidate.path.parent() {
+                let _ = std::fs::remove_dir(parent);
+            }
+        }
+        Ok(freed)
+    }
+}
+
+struct Entry {
+    path: PathBuf,
+    bytes: u64,
+    modified: SystemTime,
+    tagged: bool,
+}
+
+/// Evaluates the policy against the Claude home without deleting
+/// anything.
+pub fn plan(policy: &Policy) -> Result<Plan> {
+    let mut entries = collect_entries(policy.keep_tagged)?;
+    // Newest first, so the size budget keeps recent work.
+    entries.sort_by_key(|e| std::cmp::Reverse(e.modified));
+
+    let now = SystemTime::now();
+    let mut plan = Plan {
+        remove: Vec::new(),
+        kept: 0,
+        kept_bytes: 0,
+        spared_tagged: 0,
+    };
+    let mut used_bytes = 0u64;
+    for entry in entries {
+        let age_days = now
+            .duration_since(entry.modified)
+            .map(|age| age.as_secs() / 86_400)
+            .unwrap_or(0);
+        let too_old = policy.max_age_days.is_some_and(|max| age_days > max);
+        // Tagged entries still occupy budget; sparing them must not
+        // push untagged neighbours over the line unaccounted.
+        used_bytes += entry.bytes;
+        let over_budget = policy.max_total_bytes.is_some_and(|max| used_bytes > max);
+        let reason = if too_old {
+            "older than the age limit"
+        } else if over_budget {
+            "beyond the size budget"
+        } else {
+            plan.kept += 1;
+            plan.kept_bytes += entry.bytes;
+            continue;
+        };
+        if entry.tagged {
+            plan.spared_tagged += 1;
+            plan.kept += 1;
+            plan.kept_bytes += entry.bytes;
+            continue;
+        }
+        used_bytes -= entry.bytes;
+        plan.remove.push(Candidate {
+            path: entry.path,
+            bytes: entry.bytes,
+            age_days,
+            reason,
+        });
+    }
+    Ok(plan)
+}
+
+/// Everything retention applies to: one entry per transcript plus one
+/// per file under `file-history`.
+fn collect_entries(keep_tagged: bool) -> Result<Vec<Entry>> {
+    let tags = keep_tagged.then(Tags::load);
+    let mut entries = Vec::new();
+    for project in sessions::projects()? {
+        for session in project.sessions()? {
+            let meta = std::fs::metadata(&session.path)
+                .with_context(|| format!("reading {}", session.path.display()))?;
+            entries.push(Entry {
+                path: session.path.clone(),
+                bytes: meta.len(),
+                modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                tagged: tags
+                    .as_ref()
+                    .is_some_and(|t| !t.for_session(&session.id).is_empty()),
+            });
+        }
+    }
+    let history = sessions::claude_home().join("file-history");
+    if history.is_dir() {
+        collect_files(&history, &mut entries)?;
+    }
+    Ok(entries)
+}
+
+fn collect_files(dir: &std::path::Path, entries: &mut Vec<Entry>) -> Result<()> {
+    for item in std::fs::read_dir(dir)
+        .with_context(|| format!("reading {}", dir.display()))?
+    {
+        let path = item?.path();
+        if path.is_dir() {
+            collect_files(&path, entries)?;
+            continue;
+        }
+        let meta = std::fs::metadata(&path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        entries.push(Entry {
+            path,
+            bytes: meta.len(),
+            modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            tagged: false,
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,186 @@
+//! Installing a recurring export job, so the archive keeps itself
+//! current without hand-edited plists.
+//!
+//! On macOS this writes and loads a launchd agent; on Linux, a systemd
+//! user timer. The job runs `claude-export --all --dedup`, which skips
+//! everything already exported, so firing it daily is cheap.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+/// launchd label / systemd unit name for the job.
+const LABEL: &str = "com.zshrc.claude-export";
+
+/// When the export fires: `daily@HH:MM` or `hourly[@:MM]`.
+pub enum Spec {
+    Daily { hour: u32, minute: u32 },
+    Hourly { minute: u32 },
+}
+
+impl Spec {
+    pub fn parse(raw: &str) -> Result<Self> {
+        if raw == "hourly" {
+            return Ok(Spec::Hourly { minute: 0 });
+        }
+        if let Some(minute) = raw.strip_prefix("hourly@:") {
+            let minute: u32 = minute
+                .parse()
+                .with_context(|| format!("bad minute in {raw:?}"))?;
+            anyhow::ensure!(minute < 60, "minute out of range in {raw:?}");
+            return Ok(Spec::Hourly { minute });
+        }
+        if let Some(time) = raw.strip_prefix("daily@") {
+            let (hour, minute) = time
+                .split_once(':')
+                .with_context(|| format!("expected daily@HH:MM, got {raw:?}"))?;
+            let hour: u32 = hour
+                .parse()
+                .with_context(|| format!("bad hour in {raw:?}"))?;
+            let minute: u32 = minute
+                .parse()
+                .with_context(|| format!("bad minute in {raw:?}"))?;
+            anyhow::ensure!(
+                hour < 24 && minute < 60,
+                "time out of range in {raw:?}"
+            );
+            return Ok(Spec::Daily { hour, minute });
+        }
+        anyhow::bail!("unrecognized schedule {raw:?} (daily@HH:MM or hourly[@:MM])")
+    }
+}
+
+/// Writes and loads the job definition; returns the file it wrote.
+pub fn install(spec: &Spec) -> Result<PathBuf> {
+    let exe = std::env::current_exe().context("locating claude-export")?;
+    if cfg!(target_os = "macos") {
+        install_launchd(spec, &exe)
+    } else {
+        install_systemd(spec, &exe)
+    }
+}
+
+/// Unloads and removes the job. Already-absent pieces are fine — the
+/// point is ending up uninstalled, not erroring on the second run.
+pub fn uninstall() -> Result<()> {
+    if cfg!(target_os = "macos") {
+        let plist = launchd_path()?;
+        if plist.is_file() {
+            run("launchctl", &["unload", &plist.display().to_string()]);
+            std::fs::remove_file(&plist)
+                .with_context(|| format!("removing {}", plist.display()))?;
+        }
+    } else {
+        run("systemctl", &["--user", "disable", "--now", &format!("{LABEL}.timer")]);
+        let dir = systemd_dir()?;
+        for name in [format!("{LABEL}.timer"), format!("{LABEL}.service")] {
+            let path = dir.join(name);
+            if path.is_file() {
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("removing {}", path.display()))?;
+            }
+        }
+        run("systemctl", &["--user", "daemon-reload"]);
+    }
+    Ok(())
+}
+
+fn launchd_path() -> Result<PathBuf> {
+    Ok(dirs::home_dir()
+        .context("no home directory")?
+        .join("Library/LaunchAgents")
+        .join(format!("{LABEL}.plist")))
+}
+
+fn systemd_dir() -> Result<PathBuf> {
+    Ok(dirs::config_dir()
+        .context("no config directory")?
+        .join("systemd/user"))
+}
+
+fn install_launchd(spec: &Spec, exe: &std::path::Path) -> Result<PathBuf> {
+    let interval = match spec {
+        Spec::Daily { hour, minute } => format!(
+            "<dict>\n    <key>Hour</key><integer>{hour}</integer>\n    \
+             <key>Minute</key><integer>{minute}</integer>\n  </dict>"
+        ),
+        Spec::Hourly { minute } => format!(
+            "<dict>\n    <key>Minute</key><integer>{minute}</integer>\n  </dict>"
+        ),
+    };
+    let plist = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>Label</key><string>{LABEL}</string>
+  <key>ProgramArguments</key>
+  <array>
+    <string>{exe}</string>
+    <string>--all</string>
+    <string>--dedup</string>
+  </array>
+  <key>StartCalendarInterval</key>
+  {interval}
+</dict>
+</plist>
+"#,
+        exe = exe.display(),
+    );
+    let path = launchd_path()?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("creating {}", dir.display()))?;
+    }
+    std::fs::write(&path, plist)
+        .with_context(|| format!("writing {}", path.display()))?;
+    // Reload cleanly when a schedule was already installed.
+    run("launchctl", &["unload", &path.display().to_string()]);
+    let status = run("launchctl", &["load", "-w", &path.display().to_string()]);
+    anyhow::ensure!(status, "launchctl load failed");
+    Ok(path)
+}
+
+fn install_systemd(spec: &Spec, exe: &std::path::Path) -> Result<PathBuf> {
+    let on_calendar = match spec {
+        Spec::Daily { hour, minute } => format!("*-*-* {hour:02}:{minute:02}:00"),
+        Spec::Hourly { minute } => format!("*:{minute:02}:00"),
+    };
+    let dir = systemd_dir()?;
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("creating {}", dir.display()))?;
+    let service = format!(
+        "[Unit]\nDescription=Incremental Claude session export\n\n\
+         [Service]\nType=oneshot\nExecStart={} --all --dedup\n",
+        exe.display()
+    );
+    std::fs::write(dir.join(format!("{LABEL}.service")), service)
+        .context("writing service unit")?;
+    let timer = format!(
+        "[Unit]\nDescription=Schedule for the Claude session export\n\n\
+         [Timer]\nOnCalendar={on_calendar}\nPersistent=true\n\n\
+         [Install]\nWantedBy=timers.target\n"
+    );
+    let path = dir.join(format!("{LABEL}.timer"));
+    std::fs::write(&path, timer).context("writing timer unit")?;
+    let reloaded = run("systemctl", &["--user", "daemon-reload"]);
+    let enabled =
+        run("systemctl", &["--user", "enable", "--now", &format!("{LABEL}.timer")]);
+    anyhow::ensure!(reloaded && enabled, "systemctl failed to enable the timer");
+    Ok(path)
+}
+
+/// Runs a management command, records it in the audit log, and reports
+/// success; spawn failures count as failure rather than erroring so
+/// callers decide what is fatal.
+fn run(binary: &str, args: &[&str]) -> bool {
+    let output = Command::new(binary).args(args).output();
+    let code = output.as_ref().ok().and_then(|o| o.status.code());
+    crate::audit::record(
+        &format!("{binary} {}", args.join(" ")),
+        &std::env::current_dir().unwrap_or_default(),
+        code,
+    );
+    output.is_ok_and(|o| o.status.success())
+}
@@ -0,0 +1,318 @@
+//! Locating Claude Code projects and session transcripts on disk.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::logger;
+
+/// Root of the Claude Code data directory, `$CLAUDE_HOME` or `~/.claude`.
+pub fn claude_home() -> PathBuf {
+    std::env::var("CLAUDE_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| dirs::home_dir().unwrap_or_default().join(".claude"))
+}
+
+#[derive(Debug, Clone)]
+pub struct Project {
+    /// Directory name as Claude Code encodes it (path with `/` as `-`).
+    pub encoded_name: String,
+    pub path: PathBuf,
+}
+
+impl Project {
+    /// Human-friendly name: the last component of the original path.
+    /// The directory encoding is lossy (`/` and `-` both become `-`),
+    /// so the `cwd` recorded inside the transcripts is authoritative;
+    /// the dash-splitting heuristic only covers projects whose
+    /// transcripts never recorded one.
+    pub fn friendly_name(&self) -> String {
+        if let Some(name) = self.name_from_cwd() {
+            return name;
+        }
+        self.encoded_name
+            .rsplit('-')
+            .find(|part| !part.is_empty())
+            .unwrap_or(&self.encoded_name)
+            .to_string()
+    }
+
+    /// Last component of the first `cwd` found in any transcript's
+    /// head. Reads a few lines per file at most, like
+    /// [`Session::start_time`].
+    fn name_from_cwd(&self) -> Option<String> {
+        use std::io::BufRead;
+        let entries = std::fs::read_dir(&self.path).ok()?;
+        for path in entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == "jsonl"))
+        {
+            let Ok(file) = std::fs::File::open(&path) else { continue };
+            let reader = std::io::BufReader::new(file);
+            for line in reader.lines().take(25) {
+                let Ok(line) = line else { break };
+                let Ok(value) = serde_json::from_str::<serde_json::Value>(&line)
+                else {
+                    continue;
+                };
+                if let Some(cwd) = value.get("cwd").and_then(|c| c.as_str()) {
+                    if let Some(name) = Path::new(cwd).file_name() {
+                        return Some(name.to_string_lossy().into_owned());
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    pub fn sessions(&self) -> Result<Vec<Session>> {
+        let mut sessions: Vec<Session> = std::fs::read_dir(&self.path)
+            .with_context(|| format!("reading project {}", self.path.display()))?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == "jsonl"))
+            .map(|path| Session::from_path(path, self.clone()))
+            .collect();
+        // Newest first, matching how people look for "that session".
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.modified()));
+        Ok(sessions)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub id: String,
+    pub path: PathBuf,
+    pub project: Project,
+}
+
+impl Session {
+    fn from_path(path: PathBuf, project: Project) -> Self {
+        let id = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self { id, path, project }
+    }
+
+    /// Timestamp of the first entry that carries one. Reads the file
+    /// head only, so it is cheap enough to call while filtering.
+    pub fn start_time(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        use std::io::BufRead;
+        let file = std::fs::File::open(&self.path).ok()?;
+        let reader = std::io::BufReader::new(file);
+        for line in reader.lines().take(25) {
+            let line = line.ok()?;
+            let value: serde_json::Value = match serde_json::from_str(&line) {
+                Ok(value) => value,
+                Err(_) => continue,
+            };
+            if let Some(ts) = value.get("timestamp").and_then(|t| t.as_str()) {
+                if let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(ts) {
+                    return Some(parsed.with_timezone(&chrono::Utc));
+                }
+            }
+        }
+        None
+    }
+
+    pub fn modified(&self) -> std::time::SystemTime {
+        self.path
+            .metadata()
+            .and_then(|m| m.modified())
+            .unwrap_or(std::time::UNIX_EPOCH)
+    }
+}
+
+pub fn projects() -> Result<Vec<Project>> {
+    let root = claude_home().join("projects");
+    if !root.is_dir() {
+        bail!("no Claude projects directory at {}", root.display());
+    }
+    let mut projects: Vec<Project> = std::fs::read_dir(&root)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .map(|path| Project {
+            encoded_name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            path,
+        })
+        .collect();
+    projects.sort_by(|a, b| a.encoded_name.cmp(&b.encoded_name));
+    Ok(projects)
+}
+
+/// Fuzzy matcher behind `-p` style filters. Comma-separated patterns
+/// are OR-ed; each candidate name gets a score in `0..=1` (exact 1.0,
+/// prefix, substring, then character subsequence) and anything under
+/// the threshold is out.
+pub struct ProjectMatcher {
+    patterns: Vec<String>,
+    case_sensitive: bool,
+    min_score: f64,
+    prefer_prefix: bool,
+}
+
+impl ProjectMatcher {
+    pub fn new(spec: &str) -> Self {
+        Self {
+            patterns: spec
+                .split(',')
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty())
+                .collect(),
+            case_sensitive: false,
+            min_score: 0.5,
+            prefer_prefix: true,
+        }
+    }
+
+    pub fn with_case_sensitive(mut self, enabled: bool) -> Self {
+        self.case_sensitive = enabled;
+        self
+    }
+
+    pub fn with_min_score(mut self, min: f64) -> Self {
+        self.min_score = min;
+        self
+    }
+
+    /// When off, a prefix match scores no better than any substring.
+    pub fn with_prefer_prefix(mut self, enabled: bool) -> Self {
+        self.prefer_prefix = enabled;
+        self
+    }
+
+    /// Whether the spec OR-ed several patterns together.
+    pub fn is_multi(&self) -> bool {
+        self.patterns.len() > 1
+    }
+
+    /// Best score across the OR-ed patterns.
+    pub fn score(&self, name: &str) -> f64 {
+        self.patterns
+            .iter()
+            .map(|p| self.score_one(p, name))
+            .fold(0.0, f64::max)
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        self.score(name) >= self.min_score
+    }
+
+    fn score_one(&self, pattern: &str, name: &str) -> f64 {
+        let (pattern, name) = if self.case_sensitive {
+            (pattern.to_string(), name.to_string())
+        } else {
+            (pattern.to_lowercase(), name.to_lowercase())
+        };
+        if pattern == name {
+            1.0
+        } else if name.starts_with(&pattern) {
+            if self.prefer_prefix {
+                0.9
+            } else {
+                0.7
+            }
+        } else if name.contains(&pattern) {
+            0.7
+        } else if is_subsequence(&pattern, &name) {
+            0.5
+        } else {
+            0.0
+        }
+    }
+
+    /// Projects at or above the threshold, best first.
+    pub fn rank<'a>(&self, projects: &'a [Project]) -> Vec<(&'a Project, f64)> {
+        let mut ranked: Vec<(&Project, f64)> = projects
+            .iter()
+            .map(|p| (p, self.score(&p.friendly_name())))
+            .filter(|(_, score)| *score >= self.min_score)
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        ranked
+    }
+}
+
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut haystack = haystack.chars();
+    needle.chars().all(|n| haystack.any(|h| h == n))
+}
+
+/// Friendly names made unique across `projects`, keyed by encoded
+/// name. The directory encoding is lossy, so two different paths can
+/// end up with the same friendly name and would silently share an
+/// export directory; colliding names get a short hash suffix and the
+/// mapping is logged so nobody has to guess which is which.
+pub fn unique_names(projects: &[Project]) -> BTreeMap<String, String> {
+    let mut by_name: BTreeMap<String, Vec<&Project>> = BTreeMap::new();
+    for project in projects {
+        by_name
+            .entry(project.friendly_name())
+            .or_default()
+            .push(project);
+    }
+    let mut names = BTreeMap::new();
+    for (name, group) in by_name {
+        if let [only] = group.as_slice() {
+            names.insert(only.encoded_name.clone(), name);
+            continue;
+        }
+        for project in group {
+            let disambiguated = format!("{name}-{}", short_hash(&project.encoded_name));
+            logger::warn(format!(
+                "project name {name:?} is ambiguous: {} exports as {disambiguated}",
+                project.encoded_name
+            ));
+            names.insert(project.encoded_name.clone(), disambiguated);
+        }
+    }
+    names
+}
+
+/// Six hex characters — enough to tell collisions apart, short enough
+/// to stay readable in a directory listing.
+fn short_hash(input: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let digest = Sha256::digest(input.as_bytes());
+    digest[..3].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Resolves a user-supplied session reference: a full UUID, a unique
+/// prefix of one, or a path to a `.jsonl` file.
+pub fn find_session(query: &str) -> Result<Session> {
+    let as_path = Path::new(query);
+    if as_path.is_file() {
+        let project = Project {
+            encoded_name: as_path
+                .parent()
+                .and_then(|p| p.file_name())
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            path: as_path.parent().unwrap_or(Path::new(".")).to_path_buf(),
+        };
+        return Ok(Session::from_path(as_path.to_path_buf(), project));
+    }
+
+    let mut matches = Vec::new();
+    for project in projects()? {
+        for session in project.sessions()? {
+            if session.id.starts_with(query) {
+                matches.push(session);
+            }
+        }
+    }
+    match matches.len() {
+        0 => bail!("no session matching {query:?}"),
+        1 => Ok(matches.remove(0)),
+        n => bail!("{n} sessions match {query:?}; give a longer prefix"),
+    }
+}
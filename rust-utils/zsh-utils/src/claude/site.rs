@@ -0,0 +1,146 @@
+//! Static HTML site generation: a browsable archive of every session.
+//!
+//! The layout mirrors the export tree — `index.html` lists projects,
+//! each project directory gets its own index of sessions with dates
+//! and costs, and each session becomes one self-contained page. No
+//! Javascript, no external assets; the site works from `file://`. The
+//! one exception: pages containing `$...$` math pull KaTeX from a CDN
+//! to typeset it — such pages degrade to raw LaTeX offline.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use super::export::{render_markdown, RenderOptions};
+use super::pricing::Pricing;
+use super::sessions;
+use super::store::SessionStore;
+use super::tags::Tags;
+
+/// Small enough to inline into every page, so pages stay portable.
+const STYLE: &str = "body{font-family:sans-serif;max-width:52rem;margin:2rem auto;\
+padding:0 1rem;line-height:1.5}pre{background:#f4f4f4;padding:.8rem;\
+overflow-x:auto}code{background:#f4f4f4}table{border-collapse:collapse}\
+td,th{border:1px solid #ccc;padding:.3rem .6rem;text-align:left}\
+a{color:#0366d6}details{margin:.5rem 0}";
+
+/// KaTeX auto-render, added only to pages that contain math so every
+/// other page stays free of external assets.
+const KATEX: &str = "<link rel=\"stylesheet\" \
+href=\"https://cdn.jsdelivr.net/npm/katex@0.16.11/dist/katex.min.css\">\n\
+<script defer src=\"https://cdn.jsdelivr.net/npm/katex@0.16.11/dist/katex.min.js\">\
+</script>\n\
+<script defer \
+src=\"https://cdn.jsdelivr.net/npm/katex@0.16.11/dist/contrib/auto-render.min.js\" \
+onload=\"renderMathInElement(document.body,{delimiters:[\
+{left:'$$',right:'$$',display:true},{left:'$',right:'$',display:false}]})\">\
+</script>\n";
+
+/// Generates the whole site under `root` and returns the top index.
+pub fn generate(root: &Path, pricing: &Pricing) -> Result<PathBuf> {
+    let store = SessionStore::open()?;
+    let names = sessions::unique_names(store.projects());
+    let tags = Tags::load();
+    std::fs::create_dir_all(root)
+        .with_context(|| format!("creating {}", root.display()))?;
+
+    let mut project_rows = String::new();
+    for project in store.projects() {
+        let display = names
+            .get(&project.encoded_name)
+            .cloned()
+            .unwrap_or_else(|| project.friendly_name());
+        let dir = root.join(&display);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("creating {}", dir.display()))?;
+
+        let sessions = project.sessions()?;
+        let mut session_rows = String::new();
+        for session in &sessions {
+            let transcript = store.load(session)?;
+            let markdown = render_markdown(
+                session,
+                &transcript,
+                pricing,
+                &RenderOptions::default(),
+                &tags.for_session(&session.id),
+            );
+            let out = dir.join(format!("{}.html", session.id));
+            std::fs::write(&out, page(&display, &markdown_to_html(&markdown)))
+                .with_context(|| format!("writing {}", out.display()))?;
+
+            let date = session
+                .start_time()
+                .map(|t| t.format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| "—".to_string());
+            let cost = pricing.estimate(&transcript).total_usd;
+            session_rows.push_str(&format!(
+                "<tr><td><a href=\"{}.html\">{}</a></td><td>{date}</td>\
+                 <td>${cost:.4}</td></tr>\n",
+                href_escape(&session.id),
+                escape(&session.id),
+            ));
+        }
+
+        let index = format!(
+            "<h1>{}</h1>\n<p><a href=\"../index.html\">all projects</a></p>\n\
+             <table><tr><th>Session</th><th>Date</th><th>Cost</th></tr>\n\
+             {session_rows}</table>\n",
+            escape(&display)
+        );
+        std::fs::write(dir.join("index.html"), page(&display, &index))
+            .with_context(|| format!("writing {}", dir.join("index.html").display()))?;
+
+        project_rows.push_str(&format!(
+            "<tr><td><a href=\"{}/index.html\">{}</a></td><td>{}</td></tr>\n",
+            href_escape(&display),
+            escape(&display),
+            sessions.len(),
+        ));
+    }
+
+    let index = format!(
+        "<h1>Claude sessions</h1>\n\
+         <table><tr><th>Project</th><th>Sessions</th></tr>\n{project_rows}</table>\n"
+    );
+    let top = root.join("index.html");
+    std::fs::write(&top, page("Claude sessions", &index))
+        .with_context(|| format!("writing {}", top.display()))?;
+    Ok(top)
+}
+
+pub(crate) fn markdown_to_html(markdown: &str) -> String {
+    let mut options = pulldown_cmark::Options::empty();
+    options.insert(pulldown_cmark::Options::ENABLE_TABLES);
+    options.insert(pulldown_cmark::Options::ENABLE_STRIKETHROUGH);
+    let parser = pulldown_cmark::Parser::new_ext(markdown, options);
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, parser);
+    html
+}
+
+pub(crate) fn page(title: &str, body: &str) -> String {
+    let katex = if crate::math::contains_math(body) {
+        KATEX
+    } else {
+        ""
+    };
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<style>{STYLE}</style>\n{katex}</head>\n<body>\n\
+         {body}\n</body>\n</html>\n",
+        escape(title)
+    )
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Enough URL-escaping for the characters project names actually get.
+fn href_escape(text: &str) -> String {
+    escape(text).replace(' ', "%20")
+}
@@ -0,0 +1,383 @@
+//! Space-efficient export of the file versions a session produced.
+//!
+//! Tool uses carry enough to reconstruct how each file evolved: `Write`
+//! gives a full version, `Edit` rewrites a span of the previous one.
+//! Storing every version verbatim balloons the export directory with
+//! near-identical copies, so only the first version is stored whole;
+//! every later one becomes a unified diff against its predecessor.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use similar::TextDiff;
+
+use super::models::{ContentBlock, MessageContent};
+use super::parser::Transcript;
+use crate::logger;
+
+/// One tracked file: where its artifacts landed, relative to the
+/// export directory.
+pub struct Snapshot {
+    /// Original path inside the session's working tree.
+    pub path: String,
+    /// The stored base version, then one `.diff` per revision. Binary
+    /// files store only their newest version, under `assets/`.
+    pub artifacts: Vec<PathBuf>,
+    /// Size of the newest version in bytes.
+    pub bytes: u64,
+    pub binary: bool,
+    /// Set when nothing was stored; explains why.
+    pub skipped: Option<String>,
+}
+
+/// Retention policy for snapshot export. The default keeps everything;
+/// flags tighten it for people whose archives fill with lockfiles and
+/// build artifacts.
+#[derive(Default, Clone)]
+pub struct SnapshotPolicy {
+    /// Keep only the last n versions of each file.
+    pub keep_versions: Option<usize>,
+    /// Skip files where any single version exceeds this many bytes.
+    pub max_bytes: Option<u64>,
+    /// Skip files whose content looks binary.
+    pub skip_binary: bool,
+}
+
+/// What the policy decided to do with one file's history.
+enum Disposition {
+    /// Text file: kept versions, exported as base + diffs. `first` is
+    /// the 1-based version number of the first kept entry, so artifacts
+    /// keep their true ordinals when retention drops early versions.
+    Text { contents: Vec<String>, first: usize },
+    /// Binary file: diffs are meaningless, so only the newest version
+    /// is kept and it goes under `assets/` untouched.
+    Binary(String),
+    /// Nothing stored; the reason lands in the Markdown section.
+    Skipped(String),
+}
+
+impl SnapshotPolicy {
+    fn apply(&self, path: &str, mut contents: Vec<String>) -> Disposition {
+        if contents.iter().any(|c| looks_binary(c)) {
+            if self.skip_binary {
+                logger::warn(format!("snapshot skipped (binary): {path}"));
+                return Disposition::Skipped("binary".to_string());
+            }
+            let latest = contents.pop().expect("histories are never empty");
+            if self.max_bytes.is_some_and(|max| latest.len() as u64 > max) {
+                logger::warn(format!("snapshot skipped (over size limit): {path}"));
+                return Disposition::Skipped("over size limit".to_string());
+            }
+            return Disposition::Binary(latest);
+        }
+        if let Some(max) = self.max_bytes {
+            if contents.iter().any(|c| c.len() as u64 > max) {
+                logger::warn(format!("snapshot skipped (over size limit): {path}"));
+                return Disposition::Skipped("over size limit".to_string());
+            }
+        }
+        let mut first = 1;
+        if let Some(keep) = self.keep_versions {
+            if keep == 0 {
+                return Disposition::Skipped("version retention is 0".to_string());
+            }
+            if contents.len() > keep {
+                let dropped = contents.len() - keep;
+                contents.drain(..dropped);
+                first += dropped;
+            }
+        }
+        Disposition::Text { contents, first }
+    }
+}
+
+fn looks_binary(content: &str) -> bool {
+    content.bytes().take(8_192).any(|b| b == 0)
+}
+
+/// Reconstructs file histories from the transcript's tool uses and
+/// writes base + diffs under `dir`. Edits whose base content was never
+/// seen are skipped — a diff against an unknown base would be a lie.
+pub fn export_snapshots(
+    transcript: &Transcript,
+    dir: &Path,
+    policy: &SnapshotPolicy,
+) -> Result<Vec<Snapshot>> {
+    let versions = collect_versions(transcript, policy);
+
+    let mut snapshots = Vec::new();
+    for (path, disposition) in versions {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("creating {}", dir.display()))?;
+        match disposition {
+            Disposition::Text { contents, first } => {
+                let stem = sanitize(&path);
+                let mut artifacts = Vec::new();
+
+                let base = dir.join(format!("{stem}.v{first:03}"));
+                std::fs::write(&base, &contents[0])
+                    .with_context(|| format!("writing {}", base.display()))?;
+                artifacts.push(base);
+
+                for (i, window) in contents.windows(2).enumerate() {
+                    let out = dir.join(format!("{stem}.v{:03}.diff", first + i + 1));
+                    std::fs::write(&out, unified(&path, window, first + i))
+                        .with_context(|| format!("writing {}", out.display()))?;
+                    artifacts.push(out);
+                }
+                let bytes = contents.last().map_or(0, |c| c.len() as u64);
+                snapshots.push(Snapshot {
+                    path,
+                    artifacts,
+                    bytes,
+                    binary: false,
+                    skipped: None,
+                });
+            }
+            Disposition::Binary(latest) => {
+                // Original file name, so the asset opens in whatever
+                // handles that extension.
+                let assets = dir.join("assets");
+                std::fs::create_dir_all(&assets)
+                    .with_context(|| format!("creating {}", assets.display()))?;
+                let out = assets.join(file_name(&path));
+                std::fs::write(&out, latest.as_bytes())
+                    .with_context(|| format!("writing {}", out.display()))?;
+                snapshots.push(Snapshot {
+                    bytes: latest.len() as u64,
+                    path,
+                    artifacts: vec![out],
+                    binary: true,
+                    skipped: None,
+                });
+            }
+            Disposition::Skipped(reason) => snapshots.push(Snapshot {
+                path,
+                artifacts: Vec::new(),
+                bytes: 0,
+                binary: false,
+                skipped: Some(reason),
+            }),
+        }
+    }
+    Ok(snapshots)
+}
+
+/// Same reconstruction, but everything lands in a single zip archive.
+/// Synced folders (iCloud, Dropbox) choke on thousands of small files;
+/// one archive per session is one sync operation.
+pub fn export_snapshots_zip(
+    transcript: &Transcript,
+    zip_path: &Path,
+    policy: &SnapshotPolicy,
+) -> Result<Vec<(String, String)>> {
+    use std::io::Write;
+
+    let versions = collect_versions(transcript, policy);
+    if versions.is_empty() {
+        return Ok(Vec::new());
+    }
+    if let Some(dir) = zip_path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("creating {}", dir.display()))?;
+    }
+    let file = std::fs::File::create(zip_path)
+        .with_context(|| format!("creating {}", zip_path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let mut index = Vec::new();
+    for (path, disposition) in versions {
+        match disposition {
+            Disposition::Text { contents, first } => {
+                let stem = sanitize(&path);
+                zip.start_file(format!("{stem}.v{first:03}"), options)?;
+                zip.write_all(contents[0].as_bytes())?;
+                for (i, window) in contents.windows(2).enumerate() {
+                    zip.start_file(
+                        format!("{stem}.v{:03}.diff", first + i + 1),
+                        options,
+                    )?;
+                    zip.write_all(unified(&path, window, first + i).as_bytes())?;
+                }
+                let detail = if first > 1 {
+                    format!(
+                        "versions v{first:03}–v{:03}",
+                        first + contents.len() - 1
+                    )
+                } else {
+                    format!("{} versions", contents.len())
+                };
+                index.push((path, detail));
+            }
+            Disposition::Binary(latest) => {
+                zip.start_file(format!("assets/{}", file_name(&path)), options)?;
+                zip.write_all(latest.as_bytes())?;
+                index.push((
+                    path,
+                    format!("binary, {}", human_size(latest.len() as u64)),
+                ));
+            }
+            Disposition::Skipped(reason) => {
+                index.push((path, format!("skipped: {reason}")));
+            }
+        }
+    }
+    zip.finish().context("finalizing snapshot archive")?;
+    Ok(index)
+}
+
+/// `base` is the true 1-based version number of `window[0]`.
+fn unified(path: &str, window: &[String], base: usize) -> String {
+    TextDiff::from_lines(&window[0], &window[1])
+        .unified_diff()
+        .header(
+            &format!("{path} (v{base:03})"),
+            &format!("{path} (v{:03})", base + 1),
+        )
+        .to_string()
+}
+
+fn collect_versions(
+    transcript: &Transcript,
+    policy: &SnapshotPolicy,
+) -> BTreeMap<String, Disposition> {
+    let mut versions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for entry in &transcript.entries {
+        let Some(message) = entry.message() else { continue };
+        let MessageContent::Blocks(blocks) = &message.content else {
+            continue;
+        };
+        for block in blocks {
+            let ContentBlock::ToolUse { name, input, .. } = block else {
+                continue;
+            };
+            let Some(path) = input.get("file_path").and_then(|p| p.as_str()) else {
+                continue;
+            };
+            match name.as_str() {
+                "Write" => {
+                    if let Some(content) = input.get("content").and_then(|c| c.as_str()) {
+                        push_version(&mut versions, path, content.to_string());
+                    }
+                }
+                "Edit" => {
+                    let (Some(old), Some(new)) = (
+                        input.get("old_string").and_then(|s| s.as_str()),
+                        input.get("new_string").and_then(|s| s.as_str()),
+                    ) else {
+                        continue;
+                    };
+                    if let Some(previous) =
+                        versions.get(path).and_then(|v| v.last()).cloned()
+                    {
+                        if previous.contains(old) {
+                            push_version(
+                                &mut versions,
+                                path,
+                                previous.replacen(old, new, 1),
+                            );
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    versions
+        .into_iter()
+        .filter(|(_, contents)| !contents.is_empty())
+        .map(|(path, contents)| {
+            let disposition = policy.apply(&path, contents);
+            (path, disposition)
+        })
+        .collect()
+}
+
+/// Markdown section with clickable links to the stored base and diffs.
+/// Links are relative so the export directory can be moved wholesale.
+pub fn render_section(snapshots: &[Snapshot], markdown_dir: &Path) -> String {
+    let mut out = String::from("## File Snapshots\n\n");
+    for snapshot in snapshots {
+        if let Some(reason) = &snapshot.skipped {
+            out.push_str(&format!("- `{}` — skipped ({reason})\n", snapshot.path));
+            continue;
+        }
+        if snapshot.binary {
+            out.push_str(&format!(
+                "- `{}` (binary, {})\n",
+                snapshot.path,
+                human_size(snapshot.bytes)
+            ));
+        } else {
+            out.push_str(&format!(
+                "- `{}` ({} versions, {})\n",
+                snapshot.path,
+                snapshot.artifacts.len(),
+                human_size(snapshot.bytes)
+            ));
+        }
+        for artifact in &snapshot.artifacts {
+            let label = artifact
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let link = artifact
+                .strip_prefix(markdown_dir)
+                .unwrap_or(artifact)
+                .to_string_lossy()
+                .into_owned();
+            out.push_str(&format!("  - [{label}]({link})\n"));
+        }
+    }
+    out.push('\n');
+    out
+}
+
+/// Markdown section for the archived flavor: per-file detail plus one
+/// link to the zip.
+pub fn render_zip_section(index: &[(String, String)], zip_name: &str) -> String {
+    let mut out = String::from("## File Snapshots\n\n");
+    out.push_str(&format!("Archived in [{zip_name}]({zip_name}):\n\n"));
+    for (path, detail) in index {
+        out.push_str(&format!("- `{path}` ({detail})\n"));
+    }
+    out.push('\n');
+    out
+}
+
+fn push_version(versions: &mut BTreeMap<String, Vec<String>>, path: &str, content: String) {
+    let entry = versions.entry(path.to_string()).or_default();
+    if entry.last().map(String::as_str) != Some(content.as_str()) {
+        entry.push(content);
+    }
+}
+
+/// Turns an absolute path into a flat, filesystem-safe stem.
+fn sanitize(path: &str) -> String {
+    path.trim_start_matches('/')
+        .chars()
+        .map(|c| if c == '/' || c == '\\' { '-' } else { c })
+        .collect()
+}
+
+/// Last path component, unchanged — assets keep their real names.
+fn file_name(path: &str) -> String {
+    path.rsplit(['/', '\\']).next().unwrap_or(path).to_string()
+}
+
+/// Decimal units, one decimal place past kilobytes.
+fn human_size(bytes: u64) -> String {
+    if bytes >= 1_000_000_000 {
+        format!("{:.1} GB", bytes as f64 / 1e9)
+    } else if bytes >= 1_000_000 {
+        format!("{:.1} MB", bytes as f64 / 1e6)
+    } else if bytes >= 1_000 {
+        format!("{:.1} KB", bytes as f64 / 1e3)
+    } else {
+        format!("{bytes} B")
+    }
+}
@@ -0,0 +1,122 @@
+//! Shared read side of the Claude data: one place that lists projects,
+//! filters sessions, and parses transcripts with a small in-process
+//! cache, so the binaries stop re-discovering and re-parsing the same
+//! files independently.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+use super::parser::{self, Transcript};
+use super::sessions::{self, Project, ProjectMatcher, Session};
+
+pub struct SessionStore {
+    projects: Vec<Project>,
+    /// Parsed transcripts, keyed by path. Transcripts are immutable
+    /// once parsed, so every caller shares one copy.
+    cache: RefCell<HashMap<PathBuf, Rc<Transcript>>>,
+}
+
+impl SessionStore {
+    /// Opens the store over the Claude home directory.
+    pub fn open() -> Result<Self> {
+        Ok(Self {
+            projects: sessions::projects()?,
+            cache: RefCell::new(HashMap::new()),
+        })
+    }
+
+    pub fn projects(&self) -> &[Project] {
+        &self.projects
+    }
+
+    /// Every session across every project, newest first.
+    pub fn sessions(&self) -> Result<Vec<Session>> {
+        self.sessions_matching(None, None, None)
+    }
+
+    /// Sessions filtered by project matcher and date range, newest
+    /// first. `None` filters match everything.
+    pub fn sessions_matching(
+        &self,
+        matcher: Option<&ProjectMatcher>,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Session>> {
+        let mut sessions = Vec::new();
+        for project in &self.projects {
+            if matcher.is_some_and(|m| !m.matches(&project.friendly_name())) {
+                continue;
+            }
+            sessions.extend(
+                project
+                    .sessions()?
+                    .into_iter()
+                    .filter(|s| in_range(s, since, until)),
+            );
+        }
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.modified()));
+        Ok(sessions)
+    }
+
+    /// Parses the session's transcript, memoized per path.
+    pub fn load(&self, session: &Session) -> Result<Rc<Transcript>> {
+        if let Some(transcript) = self.cache.borrow().get(&session.path) {
+            return Ok(Rc::clone(transcript));
+        }
+        let transcript = Rc::new(parser::parse_file(&session.path)?);
+        self.cache
+            .borrow_mut()
+            .insert(session.path.clone(), Rc::clone(&transcript));
+        Ok(transcript)
+    }
+}
+
+/// Whether a session's start time falls inside the (inclusive) range.
+/// Sessions without a parseable start time are never silently dropped
+/// by a filter, and `until` names a whole day.
+pub fn in_range(
+    session: &Session,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> bool {
+    let Some(start) = session.start_time() else {
+        return true;
+    };
+    since.is_none_or(|s| start >= s)
+        && until.is_none_or(|u| start < u + chrono::Duration::days(1))
+}
+
+/// Whether a session clears the size thresholds (`--min-messages`,
+/// `--min-tokens`). Checking needs a parse, so the fast path is both
+/// thresholds unset; a transcript that fails to parse is kept — the
+/// exporter will surface the real error.
+pub fn meets_size(
+    session: &Session,
+    min_messages: Option<usize>,
+    min_tokens: Option<u64>,
+) -> bool {
+    if min_messages.is_none() && min_tokens.is_none() {
+        return true;
+    }
+    // Streamed, not collected: this runs per session while filtering
+    // and only needs two running totals.
+    let Ok(entries) = parser::stream_file(&session.path) else {
+        return true;
+    };
+    let mut messages = 0;
+    let mut tokens: u64 = 0;
+    for entry in entries {
+        let Some(message) = entry.message() else { continue };
+        messages += 1;
+        if let Some(usage) = &message.usage {
+            tokens += usage.input_tokens.unwrap_or(0) + usage.output_tokens.unwrap_or(0);
+        }
+    }
+    min_messages.is_none_or(|min| messages >= min)
+        && min_tokens.is_none_or(|min| tokens >= min)
+}
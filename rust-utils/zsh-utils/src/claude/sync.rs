@@ -0,0 +1,131 @@
+//! Built-in publish step for the export tree: git auto-commit (and
+//! push), or mirroring to a remote, so finished exports leave the
+//! machine without a wrapper script around `claude-export`.
+//!
+//! Configured in `$ZSH_CONFIG/claude-export.toml`:
+//!
+//! ```toml
+//! [sync]
+//! git = true                      # commit after export; push if a remote exists
+//! remote = "s3://bucket/claude"   # or any rclone remote (WebDAV included)
+//! ```
+//!
+//! `claude-export --sync-git` / `--sync-remote <target>` enable the
+//! same steps for one run. Remote targets shell out: `s3://` goes
+//! through the aws CLI, everything else through `rclone sync`.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::logger;
+
+#[derive(Deserialize, Default)]
+struct SyncFile {
+    #[serde(default)]
+    sync: Sync,
+}
+
+#[derive(Deserialize, Default, Clone)]
+pub struct Sync {
+    /// Commit the export tree after each run; push when the repository
+    /// has a remote.
+    #[serde(default)]
+    pub git: bool,
+    /// Mirror target: `s3://bucket/prefix` or an rclone remote spec.
+    #[serde(default)]
+    pub remote: Option<String>,
+}
+
+impl Sync {
+    /// Loads the `[sync]` table; a missing file means no sync.
+    pub fn from_config() -> Result<Self> {
+        let path = crate::llm::config_dir().join("claude-export.toml");
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        let file: SyncFile = toml::from_str(&raw)
+            .with_context(|| format!("parsing {}", path.display()))?;
+        Ok(file.sync)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.git && self.remote.is_none()
+    }
+
+    /// Runs the configured steps against the export root. Unlike the
+    /// post-export hooks these fail loudly — the user asked for the
+    /// sync, and a silently missing backup defeats it.
+    pub fn run(&self, root: &Path) -> Result<()> {
+        if self.git {
+            commit_and_push(root)?;
+        }
+        if let Some(remote) = &self.remote {
+            mirror(root, remote)?;
+        }
+        Ok(())
+    }
+}
+
+fn commit_and_push(root: &Path) -> Result<()> {
+    if !root.join(".git").exists() {
+        git(root, &["init", "--quiet"])?;
+        logger::info(format!(
+            "initialized a git repository in {}",
+            root.display()
+        ));
+    }
+    git(root, &["add", "-A"])?;
+    if git(root, &["status", "--porcelain"])?.is_empty() {
+        logger::info("sync: nothing new to commit");
+        return Ok(());
+    }
+    let message = format!("claude export {}", chrono::Utc::now().format("%Y-%m-%d %H:%M"));
+    git(root, &["commit", "--quiet", "-m", &message])?;
+    logger::success("sync: committed export directory");
+    if !git(root, &["remote"])?.is_empty() {
+        git(root, &["push", "--quiet"])?;
+        logger::success("sync: pushed to remote");
+    }
+    Ok(())
+}
+
+/// Runs one git command in `root` and returns its stdout; a non-zero
+/// exit becomes an error carrying git's stderr.
+fn git(root: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .with_context(|| format!("running git {}", args.join(" ")))?;
+    anyhow::ensure!(
+        output.status.success(),
+        "git {} failed: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn mirror(root: &Path, remote: &str) -> Result<()> {
+    let (tool, mut args): (&str, Vec<&str>) = if remote.starts_with("s3://") {
+        ("aws", vec!["s3", "sync"])
+    } else {
+        ("rclone", vec!["sync"])
+    };
+    let root_str = root.display().to_string();
+    args.push(&root_str);
+    args.push(remote);
+    let status = Command::new(tool)
+        .args(&args)
+        .status()
+        .with_context(|| format!("running {tool} (is it installed?)"))?;
+    anyhow::ensure!(status.success(), "{tool} sync to {remote} failed");
+    logger::success(format!("sync: mirrored export directory to {remote}"));
+    Ok(())
+}
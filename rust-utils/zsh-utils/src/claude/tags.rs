@@ -0,0 +1,82 @@
+//! Free-form session tags (`claude-export <session> --tag ...`).
+//!
+//! A small sidecar store mapping session ids to labels like `refactor`
+//! or `bug`. It lives in the Claude home next to the transcripts — not
+//! in the export tree — so tags survive `--clean` and re-exports, and
+//! the exporters fold them into frontmatter and filters.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use super::sessions;
+
+const TAGS_FILE: &str = "session-tags.json";
+
+pub struct Tags {
+    path: PathBuf,
+    /// Session id → its tags. Tags are stored trimmed and lowercased
+    /// so `Refactor` and `refactor` never silently diverge.
+    entries: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Tags {
+    /// Loads the store from the Claude home. Missing or corrupt files
+    /// start empty rather than blocking whatever the caller is doing.
+    pub fn load() -> Self {
+        let path = sessions::claude_home().join(TAGS_FILE);
+        let entries = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default();
+        Self { path, entries }
+    }
+
+    /// The session's tags, sorted.
+    pub fn for_session(&self, session: &str) -> Vec<String> {
+        self.entries
+            .get(session)
+            .map(|tags| tags.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Whether the session carries at least one of `wanted`.
+    pub fn matches_any(&self, session: &str, wanted: &[String]) -> bool {
+        let Some(tags) = self.entries.get(session) else {
+            return false;
+        };
+        wanted.iter().any(|tag| tags.contains(&normalize(tag)))
+    }
+
+    pub fn add(&mut self, session: &str, tags: &[String]) -> Result<()> {
+        let entry = self.entries.entry(session.to_string()).or_default();
+        entry.extend(tags.iter().map(|t| normalize(t)).filter(|t| !t.is_empty()));
+        self.save()
+    }
+
+    pub fn remove(&mut self, session: &str, tags: &[String]) -> Result<()> {
+        if let Some(entry) = self.entries.get_mut(session) {
+            for tag in tags {
+                entry.remove(&normalize(tag));
+            }
+            if entry.is_empty() {
+                self.entries.remove(session);
+            }
+        }
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("creating {}", dir.display()))?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(&self.entries)?)
+            .with_context(|| format!("writing {}", self.path.display()))
+    }
+}
+
+fn normalize(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
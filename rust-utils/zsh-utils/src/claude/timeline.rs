@@ -0,0 +1,109 @@
+//! Mermaid gantt timeline of a session — assistant turns and tool
+//! invocations laid out along the time axis, so it's obvious at a
+//! glance where a long session spent its wall-clock time.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use super::models::{ContentBlock, MessageContent, TranscriptEntry};
+use super::parser::Transcript;
+
+/// Renders a `## Timeline` section with a fenced `mermaid` gantt block,
+/// or `None` when the transcript has too few timestamps to chart.
+/// GitHub and most Markdown viewers render the block as a diagram;
+/// everywhere else it degrades to a readable code block.
+pub fn render_section(transcript: &Transcript) -> Option<String> {
+    let entries = &transcript.entries;
+    let times: Vec<Option<DateTime<Utc>>> =
+        entries.iter().map(entry_time).collect();
+    if times.iter().flatten().count() < 2 {
+        return None;
+    }
+
+    // Tool results carry the id of the call they answer; their
+    // timestamp is when the tool actually finished.
+    let mut result_times: HashMap<&str, DateTime<Utc>> = HashMap::new();
+    for (entry, time) in entries.iter().zip(&times) {
+        let (Some(message), Some(time)) = (entry.message(), time) else {
+            continue;
+        };
+        if let MessageContent::Blocks(blocks) = &message.content {
+            for block in blocks {
+                if let ContentBlock::ToolResult { tool_use_id: Some(id), .. } = block {
+                    result_times.entry(id.as_str()).or_insert(*time);
+                }
+            }
+        }
+    }
+
+    let mut turns = Vec::new();
+    let mut tools = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let TranscriptEntry::Assistant { message, .. } = entry else {
+            continue;
+        };
+        let Some(start) = times[i] else { continue };
+        // A turn runs until the next timestamped entry (usually the
+        // tool result or the user's reply).
+        let end = times[i + 1..].iter().flatten().next().copied().unwrap_or(start);
+        turns.push(task(&format!("Turn {}", turns.len() + 1), start, end));
+
+        if let MessageContent::Blocks(blocks) = &message.content {
+            for block in blocks {
+                let ContentBlock::ToolUse { id, name, .. } = block else {
+                    continue;
+                };
+                let end = id
+                    .as_deref()
+                    .and_then(|id| result_times.get(id))
+                    .copied()
+                    .unwrap_or(start);
+                tools.push(task(name, start, end));
+            }
+        }
+    }
+    if turns.is_empty() {
+        return None;
+    }
+
+    let mut out = String::from("## Timeline\n\n```mermaid\ngantt\n");
+    out.push_str("    dateFormat YYYY-MM-DDTHH:mm:ss\n");
+    out.push_str("    axisFormat %H:%M:%S\n");
+    out.push_str("    section Assistant\n");
+    for line in &turns {
+        out.push_str(line);
+    }
+    if !tools.is_empty() {
+        out.push_str("    section Tools\n");
+        for line in &tools {
+            out.push_str(line);
+        }
+    }
+    out.push_str("```\n\n");
+    Some(out)
+}
+
+/// One gantt task line. Mermaid needs a nonzero duration, so
+/// instantaneous events are stretched to a second.
+fn task(label: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> String {
+    let seconds = (end - start).num_seconds().max(1);
+    format!(
+        "    {} :{}, {}s\n",
+        escape(label),
+        start.format("%Y-%m-%dT%H:%M:%S"),
+        seconds
+    )
+}
+
+/// Colons and hashes are syntax inside a gantt task line.
+fn escape(label: &str) -> String {
+    label.replace([':', '#'], "-")
+}
+
+fn entry_time(entry: &TranscriptEntry) -> Option<DateTime<Utc>> {
+    let raw = entry.meta()?.timestamp.as_deref()?;
+    DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
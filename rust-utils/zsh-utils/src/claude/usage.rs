@@ -0,0 +1,317 @@
+//! Live token-usage dashboard for `claude-usage`.
+//!
+//! Tails the transcripts directory and shows today's and this week's
+//! token usage and estimated cost, currently-active sessions, and a
+//! per-project breakdown. Refreshes keep a per-file mtime cache so each
+//! tick only reparses transcripts that actually changed.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{List, ListItem, Paragraph};
+use ratatui::Terminal;
+
+use super::parser;
+use super::pricing::Pricing;
+use super::sessions;
+use crate::glyphs;
+use crate::term::{self, bordered};
+
+const TITLE: &str = " usage — q quit ";
+const TITLE_ASCII: &str = " usage - q quit ";
+/// A session whose transcript changed this recently counts as active.
+const ACTIVE_WINDOW: Duration = Duration::from_secs(120);
+
+/// One assistant message's worth of usage.
+struct Sample {
+    time: Option<DateTime<Utc>>,
+    tokens: u64,
+    cost_usd: f64,
+}
+
+struct FileUsage {
+    project: String,
+    modified: SystemTime,
+    samples: Vec<Sample>,
+}
+
+/// Incremental scanner over the transcripts directory.
+pub struct Collector {
+    pricing: Pricing,
+    cache: HashMap<PathBuf, FileUsage>,
+}
+
+#[derive(Default)]
+pub struct ProjectUsage {
+    pub name: String,
+    pub tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// What the dashboard shows on one tick.
+#[derive(Default)]
+pub struct Snapshot {
+    pub today_tokens: u64,
+    pub today_cost_usd: f64,
+    pub week_tokens: u64,
+    pub week_cost_usd: f64,
+    pub active_sessions: usize,
+    /// This week's usage per project, most expensive first.
+    pub projects: Vec<ProjectUsage>,
+}
+
+impl Collector {
+    pub fn new(pricing: Pricing) -> Self {
+        Self {
+            pricing,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Rescans the transcripts directory, reparsing only files whose
+    /// mtime moved since the previous refresh.
+    pub fn refresh(&mut self) -> Result<Snapshot> {
+        let now = SystemTime::now();
+        let mut snapshot = Snapshot::default();
+        let today = Local::now().date_naive();
+        let week_start = today - chrono::Days::new(6);
+        let mut by_project: HashMap<String, ProjectUsage> = HashMap::new();
+
+        for project in sessions::projects()? {
+            let name = project.friendly_name();
+            for session in project.sessions()? {
+                let modified = session.modified();
+                if now
+                    .duration_since(modified)
+                    .map(|age| age <= ACTIVE_WINDOW)
+                    .unwrap_or(false)
+                {
+                    snapshot.active_sessions += 1;
+                }
+                let cached = self
+                    .cache
+                    .get(&session.path)
+                    .is_some_and(|f| f.modified == modified);
+                if !cached {
+                    self.cache.insert(
+                        session.path.clone(),
+                        FileUsage {
+                            project: name.clone(),
+                            modified,
+                            samples: collect_samples(&session.path, &self.pricing),
+                        },
+                    );
+                }
+                let file = &self.cache[&session.path];
+                for sample in &file.samples {
+                    let Some(day) = sample.time.map(|t| {
+                        t.with_timezone(&Local).date_naive()
+                    }) else {
+                        continue;
+                    };
+                    if day < week_start || day > today {
+                        continue;
+                    }
+                    snapshot.week_tokens += sample.tokens;
+                    snapshot.week_cost_usd += sample.cost_usd;
+                    if day == today {
+                        snapshot.today_tokens += sample.tokens;
+                        snapshot.today_cost_usd += sample.cost_usd;
+                    }
+                    let entry = by_project
+                        .entry(file.project.clone())
+                        .or_default();
+                    entry.tokens += sample.tokens;
+                    entry.cost_usd += sample.cost_usd;
+                }
+            }
+        }
+
+        snapshot.projects = by_project
+            .into_iter()
+            .map(|(name, mut usage)| {
+                usage.name = name;
+                usage
+            })
+            .collect();
+        snapshot.projects.sort_by(|a, b| {
+            b.cost_usd
+                .partial_cmp(&a.cost_usd)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        Ok(snapshot)
+    }
+}
+
+/// Per-message usage for one transcript. An unopenable file
+/// contributes nothing rather than killing the dashboard; entries are
+/// streamed so a huge transcript never sits in memory whole.
+fn collect_samples(path: &std::path::Path, pricing: &Pricing) -> Vec<Sample> {
+    let Ok(entries) = parser::stream_file(path) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| {
+            let message = entry.message()?;
+            let usage = message.usage.as_ref()?;
+            let tokens =
+                usage.input_tokens.unwrap_or(0) + usage.output_tokens.unwrap_or(0);
+            let cost_usd = message
+                .model
+                .as_deref()
+                .and_then(|model| pricing.rates_for(model))
+                .map(|rates| {
+                    (usage.input_tokens.unwrap_or(0) as f64 * rates.input
+                        + usage.output_tokens.unwrap_or(0) as f64 * rates.output
+                        + usage.cache_creation_input_tokens.unwrap_or(0) as f64
+                            * rates.cache_creation
+                        + usage.cache_read_input_tokens.unwrap_or(0) as f64
+                            * rates.cache_read)
+                        / 1_000_000.0
+                })
+                .unwrap_or(0.0);
+            let time = entry
+                .meta()
+                .and_then(|m| m.timestamp.as_deref())
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&Utc));
+            Some(Sample {
+                time,
+                tokens,
+                cost_usd,
+            })
+        })
+        .collect()
+}
+
+/// Runs the dashboard until the user quits.
+pub fn run(pricing: Pricing) -> Result<()> {
+    let mut collector = Collector::new(pricing);
+    let mut snapshot = collector.refresh()?;
+
+    crossterm::terminal::enable_raw_mode()?;
+    let mut stdout = std::io::stdout();
+    crossterm::execute!(stdout, crossterm::terminal::EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let result = event_loop(&mut terminal, &mut collector, &mut snapshot);
+
+    crossterm::terminal::disable_raw_mode()?;
+    crossterm::execute!(
+        terminal.backend_mut(),
+        crossterm::terminal::LeaveAlternateScreen
+    )?;
+    result
+}
+
+fn event_loop(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    collector: &mut Collector,
+    snapshot: &mut Snapshot,
+) -> Result<()> {
+    loop {
+        terminal.draw(|frame| draw(frame, snapshot))?;
+        if event::poll(Duration::from_secs(2))? {
+            if let Event::Key(key) = event::read()? {
+                match (key.code, key.modifiers) {
+                    (KeyCode::Char('c'), KeyModifiers::CONTROL) => return Ok(()),
+                    (KeyCode::Char('q') | KeyCode::Esc, _) => return Ok(()),
+                    _ => {}
+                }
+            }
+            continue;
+        }
+        // Poll timed out: a quiet tick, refresh the numbers.
+        *snapshot = collector.refresh()?;
+    }
+}
+
+fn draw(frame: &mut ratatui::Frame, snapshot: &Snapshot) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(5), Constraint::Min(1)])
+        .split(frame.size());
+
+    let summary = vec![
+        Line::from(format!(
+            " today   {:>10}  {:>9}",
+            human_tokens(snapshot.today_tokens),
+            usd(snapshot.today_cost_usd),
+        )),
+        Line::from(format!(
+            " week    {:>10}  {:>9}",
+            human_tokens(snapshot.week_tokens),
+            usd(snapshot.week_cost_usd),
+        )),
+        Line::from(format!(
+            " active  {} session{}",
+            snapshot.active_sessions,
+            if snapshot.active_sessions == 1 { "" } else { "s" },
+        )),
+    ];
+    let title = glyphs::pick(TITLE, TITLE_ASCII);
+    frame.render_widget(Paragraph::new(summary).block(bordered(title)), chunks[0]);
+
+    let max_cost = snapshot
+        .projects
+        .first()
+        .map(|p| p.cost_usd)
+        .filter(|c| *c > 0.0)
+        .unwrap_or(1.0);
+    let name_width = snapshot
+        .projects
+        .iter()
+        .map(|p| p.name.len())
+        .max()
+        .unwrap_or(0);
+    let items: Vec<ListItem> = snapshot
+        .projects
+        .iter()
+        .map(|project| {
+            let bar_len = ((project.cost_usd / max_cost) * 20.0).round() as usize;
+            let bar: String = glyphs::pick("█", "#").repeat(bar_len);
+            ListItem::new(Line::from(format!(
+                " {:name_width$}  {:>10}  {:>9}  {bar}",
+                project.name,
+                human_tokens(project.tokens),
+                usd(project.cost_usd),
+            )))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(bordered(" this week by project "))
+        .highlight_style(term::themed(
+            Style::default().add_modifier(Modifier::REVERSED),
+        ));
+    frame.render_widget(list, chunks[1]);
+
+    if snapshot.projects.is_empty() {
+        let empty = Paragraph::new(" no usage recorded this week")
+            .style(term::themed(Style::default().fg(Color::DarkGray)));
+        let inner = Layout::default()
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .margin(1)
+            .split(chunks[1]);
+        frame.render_widget(empty, inner[0]);
+    }
+}
+
+fn human_tokens(tokens: u64) -> String {
+    match tokens {
+        0..=999 => format!("{tokens} tok"),
+        1_000..=999_999 => format!("{:.1}k tok", tokens as f64 / 1_000.0),
+        _ => format!("{:.1}M tok", tokens as f64 / 1_000_000.0),
+    }
+}
+
+fn usd(cost: f64) -> String {
+    format!("${cost:.2}")
+}
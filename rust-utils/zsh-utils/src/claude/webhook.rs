@@ -0,0 +1,118 @@
+//! Publishing per-session metadata summaries to an HTTP endpoint —
+//! the feed for personal dashboards. Only metadata leaves the machine;
+//! the transcript text itself never does.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+
+use super::models::{ContentBlock, MessageContent};
+use super::parser::Transcript;
+use super::pricing::Pricing;
+use super::sessions::Session;
+use crate::logger;
+
+const ATTEMPTS: u32 = 3;
+
+pub struct WebhookPublisher {
+    url: String,
+    /// Sent as `Authorization: Bearer …` when set.
+    token: Option<String>,
+    http: reqwest::blocking::Client,
+}
+
+impl WebhookPublisher {
+    /// Auth comes from `$CLAUDE_WEBHOOK_TOKEN`; endpoints without auth
+    /// just never see the header.
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            token: std::env::var("CLAUDE_WEBHOOK_TOKEN").ok(),
+            http: reqwest::blocking::Client::new(),
+        }
+    }
+
+    /// POSTs the session summary, retrying transient failures with a
+    /// short growing backoff.
+    pub fn publish_session(
+        &self,
+        session: &Session,
+        transcript: &Transcript,
+        pricing: &Pricing,
+    ) -> Result<()> {
+        let payload = summary(session, transcript, pricing);
+        let mut last_err = None;
+        for attempt in 1..=ATTEMPTS {
+            let result = self
+                .request(&payload)
+                .and_then(|r| r.error_for_status().context("endpoint rejected the summary"));
+            match result {
+                Ok(_) => return Ok(()),
+                Err(err) => {
+                    if attempt < ATTEMPTS {
+                        logger::warn(format!(
+                            "webhook attempt {attempt}/{ATTEMPTS} failed: {err:#}"
+                        ));
+                        std::thread::sleep(std::time::Duration::from_millis(
+                            500 * u64::from(attempt),
+                        ));
+                    }
+                    last_err = Some(err);
+                }
+            }
+        }
+        Err(last_err.expect("loop ran at least once"))
+    }
+
+    fn request(&self, payload: &Value) -> Result<reqwest::blocking::Response> {
+        let mut request = self.http.post(&self.url).json(payload);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        request.send().context("sending session summary")
+    }
+}
+
+fn summary(session: &Session, transcript: &Transcript, pricing: &Pricing) -> Value {
+    let mut input_tokens: u64 = 0;
+    let mut output_tokens: u64 = 0;
+    let mut files = std::collections::BTreeSet::new();
+    for message in transcript.entries.iter().filter_map(|e| e.message()) {
+        if let Some(usage) = &message.usage {
+            input_tokens += usage.input_tokens.unwrap_or(0);
+            output_tokens += usage.output_tokens.unwrap_or(0);
+        }
+        if let MessageContent::Blocks(blocks) = &message.content {
+            for block in blocks {
+                if let ContentBlock::ToolUse { input, .. } = block {
+                    if let Some(path) = input.get("file_path").and_then(|p| p.as_str()) {
+                        files.insert(path.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    let times: Vec<chrono::DateTime<chrono::Utc>> = transcript
+        .entries
+        .iter()
+        .filter_map(|e| e.meta())
+        .filter_map(|m| m.timestamp.as_deref())
+        .filter_map(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&chrono::Utc))
+        .collect();
+    let duration_seconds = match (times.first(), times.last()) {
+        (Some(first), Some(last)) => (*last - *first).num_seconds().max(0),
+        _ => 0,
+    };
+
+    json!({
+        "session_id": session.id,
+        "project": session.project.friendly_name(),
+        "start_time": session.start_time().map(|t| t.to_rfc3339()),
+        "duration_seconds": duration_seconds,
+        "input_tokens": input_tokens,
+        "output_tokens": output_tokens,
+        "estimated_cost_usd": pricing.estimate(transcript).total_usd,
+        "files": files,
+    })
+}
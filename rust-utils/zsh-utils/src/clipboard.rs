@@ -0,0 +1,39 @@
+//! Clipboard access by shelling out to the platform tool: pbcopy/pbpaste
+//! on macOS, xclip on Linux. Keeping this external means no X11/AppKit
+//! linkage in the binaries.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::Result;
+
+pub fn copy(text: &str) -> Result<()> {
+    let candidates: &[(&str, &[&str])] =
+        &[("pbcopy", &[]), ("xclip", &["-selection", "clipboard"])];
+    for (tool, args) in candidates {
+        let child = Command::new(tool).args(*args).stdin(Stdio::piped()).spawn();
+        if let Ok(mut child) = child {
+            if let Some(stdin) = child.stdin.as_mut() {
+                stdin.write_all(text.as_bytes())?;
+            }
+            child.wait()?;
+            return Ok(());
+        }
+    }
+    anyhow::bail!("no clipboard tool found (tried pbcopy, xclip)");
+}
+
+pub fn paste() -> Result<String> {
+    let candidates: &[(&str, &[&str])] = &[
+        ("pbpaste", &[]),
+        ("xclip", &["-selection", "clipboard", "-o"]),
+    ];
+    for (tool, args) in candidates {
+        if let Ok(out) = Command::new(tool).args(*args).output() {
+            if out.status.success() {
+                return Ok(String::from_utf8_lossy(&out.stdout).into_owned());
+            }
+        }
+    }
+    anyhow::bail!("no clipboard tool found (tried pbpaste, xclip)");
+}
@@ -0,0 +1,125 @@
+//! Plain-terminal display helpers (as opposed to the full-screen TUI in
+//! [`crate::chat`]).
+
+use std::io::IsTerminal;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::glyphs;
+
+static PORCELAIN: AtomicBool = AtomicBool::new(false);
+
+/// Applies the `--porcelain` flag: stable tab-separated records on
+/// stdout, no emoji, no colors, no progress. Binaries call this once
+/// from `main`, right after [`glyphs::init`]. In this mode the logger
+/// drops its chatty stdout lines and tags stderr lines plainly, so
+/// zsh functions can parse output without tracking the human styling.
+pub fn init_porcelain(enabled: bool) {
+    PORCELAIN.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_porcelain() -> bool {
+    PORCELAIN.load(Ordering::Relaxed)
+}
+
+/// Emits one porcelain record: fields joined by tabs, one line,
+/// nothing styled. The first field names the record kind and is part
+/// of the stable contract — add kinds, never rename them. Embedded
+/// tabs and newlines become spaces so a record is always one line.
+pub fn porcelain(fields: &[&str]) {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|f| f.replace(['\t', '\n'], " "))
+        .collect();
+    println!("{}", fields.join("\t"));
+}
+
+/// True when the terminal is known to render OSC 8 hyperlinks. The
+/// detection is allowlist-based: emitting the escapes on a terminal
+/// that does not support them prints garbage around every path.
+pub fn supports_hyperlinks() -> bool {
+    if !std::io::stdout().is_terminal() || glyphs::is_ascii() {
+        return false;
+    }
+    if std::env::var("VTE_VERSION").is_ok() {
+        return true;
+    }
+    matches!(
+        std::env::var("TERM_PROGRAM").as_deref(),
+        Ok("iTerm.app") | Ok("WezTerm") | Ok("vscode") | Ok("ghostty") | Ok("kitty")
+    )
+}
+
+/// Wraps `text` in an OSC 8 hyperlink to `url` when supported,
+/// otherwise returns the text unchanged.
+pub fn hyperlink(url: &str, text: &str) -> String {
+    if supports_hyperlinks() {
+        format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+    } else {
+        text.to_string()
+    }
+}
+
+/// A cmd-clickable rendering of a local path (file:// link).
+pub fn path_link(path: &Path) -> String {
+    let display = path.display().to_string();
+    let absolute = path
+        .canonicalize()
+        .unwrap_or_else(|_| path.to_path_buf());
+    hyperlink(&format!("file://{}", absolute.display()), &display)
+}
+
+/// A node in a [`TreeDisplay`].
+pub struct TreeNode {
+    pub label: String,
+    pub children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    pub fn leaf(label: impl Into<String>) -> Self {
+        Self { label: label.into(), children: Vec::new() }
+    }
+
+    pub fn branch(label: impl Into<String>, children: Vec<TreeNode>) -> Self {
+        Self { label: label.into(), children }
+    }
+}
+
+/// Renders a tree with box-drawing guides, `tree(1)` style.
+pub struct TreeDisplay {
+    root: TreeNode,
+}
+
+impl TreeDisplay {
+    pub fn new(root: TreeNode) -> Self {
+        Self { root }
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&self.root.label);
+        out.push('\n');
+        Self::render_children(&self.root.children, "", &mut out);
+        out
+    }
+
+    fn render_children(children: &[TreeNode], prefix: &str, out: &mut String) {
+        let last = children.len().saturating_sub(1);
+        for (i, child) in children.iter().enumerate() {
+            let (guide, pad) = if i == last {
+                (glyphs::pick("└── ", "`-- "), "    ")
+            } else {
+                (glyphs::pick("├── ", "|-- "), glyphs::pick("│   ", "|   "))
+            };
+            out.push_str(prefix);
+            out.push_str(guide);
+            out.push_str(&child.label);
+            out.push('\n');
+            Self::render_children(&child.children, &format!("{prefix}{pad}"), out);
+        }
+    }
+
+    pub fn print(&self) {
+        print!("{}", self.render());
+    }
+}
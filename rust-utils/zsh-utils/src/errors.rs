@@ -0,0 +1,54 @@
+//! Top-level error reporting: the causal chain, plus a suggestion when
+//! the failure is one we have seen a hundred times before.
+
+use crate::{logger, term};
+
+/// Prints an anyhow error as a readable chain and a suggestion for
+/// known failure classes. Binaries call this from `main` instead of
+/// letting anyhow debug-print the error.
+pub fn report(err: &anyhow::Error) {
+    let mut chain = err.chain();
+    if let Some(root) = chain.next() {
+        logger::error(root.to_string());
+    }
+    for cause in chain {
+        eprintln!("  {} {cause}", term::paint("2", "caused by:"));
+    }
+    if let Some(suggestion) = suggest(&format!("{err:#}")) {
+        eprintln!("  {} {suggestion}", term::paint("1;33", "hint:"));
+    }
+}
+
+/// Pattern-matches the rendered chain against failure classes worth a
+/// hint. Keep these few and high-confidence; a wrong hint is worse
+/// than none.
+fn suggest(rendered: &str) -> Option<&'static str> {
+    let lower = rendered.to_lowercase();
+    if lower.contains(".claude") && lower.contains("no claude projects directory") {
+        return Some("no Claude Code data found — run claude once, or set CLAUDE_HOME");
+    }
+    if lower.contains("llm config") || lower.contains("llm.toml") {
+        return Some("create $ZSH_CONFIG/llm.toml with base_url, model, and api_key_env");
+    }
+    if lower.contains("api key env var") {
+        return Some("export the API key variable named in llm.toml before running");
+    }
+    if lower.contains("connection refused")
+        || lower.contains("dns error")
+        || lower.contains("error sending request")
+    {
+        return Some("the LLM endpoint is unreachable — check base_url and your network");
+    }
+    if lower.contains("permission denied") {
+        return Some("check ownership/permissions of the path above");
+    }
+    None
+}
+
+/// `main` wrapper used by every binary: report nicely, exit nonzero.
+pub fn exit_on_error(result: anyhow::Result<()>) {
+    if let Err(err) = result {
+        report(&err);
+        std::process::exit(1);
+    }
+}
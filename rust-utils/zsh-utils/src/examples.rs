@@ -0,0 +1,81 @@
+//! Copy-pasteable example invocations for each tool, in one place.
+//!
+//! With this many binaries, discoverability needs structure: every
+//! command renders its block into clap's `after_help`, and the
+//! `show-examples` tool prints any block on demand without opening a man
+//! page that does not exist.
+
+/// Command name → example invocations, one per line, indented ready
+/// for terminal output. Keep entries alphabetical.
+const EXAMPLES: &[(&str, &str)] = &[
+    (
+        "audit",
+        "  ZSH_AUDIT_LOG=1 claude-export --all    # record commands while exporting\n  \
+         audit -n 20                            # last 20 recorded commands\n  \
+         audit --clear                          # truncate the log",
+    ),
+    (
+        "cache",
+        "  cache                                  # cached completion count and size\n  \
+         cache clear                            # drop every cached completion",
+    ),
+    (
+        "chatgpt-export",
+        "  chatgpt-export ~/Downloads/chatgpt-export.zip\n  \
+         chatgpt-export conversations.json      # already-unpacked archive",
+    ),
+    (
+        "claude-export",
+        "  claude-export -p zshrc                 # everything from one project\n  \
+         claude-export --all --dedup            # incremental full export\n  \
+         claude-export abc123 -f pdf            # one session, typeset\n  \
+         claude-export abc123 --bundle s.claudepack\n  \
+         claude-export abc123 --tag refactor,bug\n  \
+         claude-export --all --tag refactor     # only tagged sessions\n  \
+         claude-export --source all --since 2026-08-01\n  \
+         claude-export --install-schedule daily@09:00",
+    ),
+    (
+        "claude-import",
+        "  claude-import s.claudepack             # unpack a bundle from another machine\n  \
+         claude-import s.claudepack --force     # overwrite an existing transcript",
+    ),
+    (
+        "claude-prune",
+        "  claude-prune --older-than 90 -n        # dry run: what 90-day retention frees\n  \
+         claude-prune --older-than 90           # apply it\n  \
+         claude-prune --max-size 2GB            # keep the newest 2GB",
+    ),
+    (
+        "claude-usage",
+        "  claude-usage                           # live token/cost dashboard\n  \
+         claude-usage --pricing-file rates.toml # with corrected rates",
+    ),
+    (
+        "llm-chat",
+        "  llm-chat                               # full-screen chat\n  \
+         llm-chat --plain                       # line-based REPL for dumb terminals",
+    ),
+];
+
+/// The raw example block for one command.
+pub fn lookup(command: &str) -> Option<&'static str> {
+    EXAMPLES
+        .iter()
+        .find(|(name, _)| *name == command)
+        .map(|(_, examples)| *examples)
+}
+
+/// Every command that has examples, in registry order.
+pub fn commands() -> impl Iterator<Item = &'static str> {
+    EXAMPLES.iter().map(|(name, _)| *name)
+}
+
+/// The block formatted for clap's `after_help`; empty when a command
+/// has no entry yet, which clap renders as no section at all.
+pub fn after_help(command: &str) -> String {
+    match lookup(command) {
+        Some(examples) => format!("Examples:\n{examples}"),
+        None => String::new(),
+    }
+}
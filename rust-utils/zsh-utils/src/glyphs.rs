@@ -0,0 +1,42 @@
+//! Process-wide choice between Unicode and plain-ASCII output.
+//!
+//! Remote boxes with a C locale or an old terminfo render our emoji and
+//! box-drawing output as mojibake, so every glyph the tools print goes
+//! through here. Binaries call [`init`] once from `main`; everything
+//! else just asks [`is_ascii`].
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+/// Applies the `--ascii` flag, falling back to environment detection
+/// when the flag is not given.
+pub fn init(force_ascii: bool) {
+    ASCII.store(force_ascii || detect_ascii(), Ordering::Relaxed);
+}
+
+pub fn is_ascii() -> bool {
+    ASCII.load(Ordering::Relaxed)
+}
+
+/// Picks the variant matching the current mode.
+pub fn pick<'a>(unicode: &'a str, ascii: &'a str) -> &'a str {
+    if is_ascii() {
+        ascii
+    } else {
+        unicode
+    }
+}
+
+/// True when the environment suggests the terminal cannot render
+/// Unicode: a dumb/linux TERM or a locale without UTF-8.
+fn detect_ascii() -> bool {
+    if matches!(std::env::var("TERM").as_deref(), Ok("dumb") | Ok("linux")) {
+        return true;
+    }
+    let locale = std::env::var("LC_ALL")
+        .or_else(|_| std::env::var("LC_CTYPE"))
+        .or_else(|_| std::env::var("LANG"))
+        .unwrap_or_default();
+    !locale.to_uppercase().contains("UTF-8") && !locale.to_uppercase().contains("UTF8")
+}
@@ -0,0 +1,21 @@
+//! Shared plumbing for the small Rust tools that ship with this zsh config.
+//!
+//! Each binary under `src/bin/` stays thin; anything two tools could
+//! conceivably share lives here instead.
+
+pub mod audit;
+pub mod cache;
+pub mod chat;
+pub mod claude;
+pub mod clipboard;
+pub mod display;
+pub mod errors;
+pub mod examples;
+pub mod glyphs;
+pub mod llm;
+pub mod logger;
+pub mod markdown;
+pub mod math;
+pub mod progress;
+pub mod rename;
+pub mod term;
@@ -0,0 +1,28 @@
+//! Splitting large inputs into model-sized chunks.
+
+/// Splits a unified diff into chunks of at most `max_chars`, cutting
+/// only at file boundaries so the model always sees whole files. A
+/// single file larger than the budget becomes its own oversized chunk —
+/// better to try than to drop it.
+pub fn chunk_diff(diff: &str, max_chars: usize) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    for line in diff.lines() {
+        let start_of_file = line.starts_with("diff --git ");
+        if start_of_file || files.is_empty() {
+            files.push(String::new());
+        }
+        let current = files.last_mut().expect("just pushed");
+        current.push_str(line);
+        current.push('\n');
+    }
+
+    let mut chunks: Vec<String> = Vec::new();
+    for file in files {
+        match chunks.last_mut() {
+            Some(last) if last.len() + file.len() <= max_chars => last.push_str(&file),
+            _ => chunks.push(file),
+        }
+    }
+    chunks.retain(|c| !c.trim().is_empty());
+    chunks
+}
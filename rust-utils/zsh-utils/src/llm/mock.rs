@@ -0,0 +1,105 @@
+//! Canned [`ChatProvider`] for demos and deterministic TUI testing: no
+//! backend, no API key, replies after a configurable delay and fails on
+//! a configurable schedule.
+//!
+//! The defaults are usable out of the box; `$ZSH_CONFIG/llm-mock.toml`
+//! overrides them:
+//!
+//! ```toml
+//! replies = ["First reply.", "Second reply with `code`."]
+//! delay_ms = 400
+//! fail_every = 3
+//! ```
+
+use std::cell::Cell;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use super::{config_dir, ChatMessage, ChatProvider};
+
+#[derive(Deserialize)]
+struct MockConfig {
+    #[serde(default)]
+    replies: Vec<String>,
+    #[serde(default = "default_delay_ms")]
+    delay_ms: u64,
+    /// Every n-th call returns an error, for exercising error paths.
+    fail_every: Option<usize>,
+}
+
+fn default_delay_ms() -> u64 {
+    400
+}
+
+pub struct MockProvider {
+    replies: Vec<String>,
+    delay: Duration,
+    fail_every: Option<usize>,
+    calls: Cell<usize>,
+}
+
+/// Demo replies chosen to exercise the Markdown renderer: headings,
+/// inline code, and a fenced block.
+const DEMO_REPLIES: &[&str] = &[
+    "Hello! This is the **mock** provider — no backend involved.\n\n\
+     Ask anything; replies just cycle through a canned script.",
+    "## A heading\n\nSome *emphasis*, some `inline code`, and a list:\n\n\
+     - one\n- two\n- three",
+    "Here is a code block:\n\n```rust\nfn main() {\n    println!(\"hi\");\n}\n```",
+];
+
+impl MockProvider {
+    pub fn new() -> Self {
+        Self {
+            replies: DEMO_REPLIES.iter().map(|r| r.to_string()).collect(),
+            delay: Duration::from_millis(default_delay_ms()),
+            fail_every: None,
+            calls: Cell::new(0),
+        }
+    }
+
+    /// Applies `$ZSH_CONFIG/llm-mock.toml` when present; a missing file
+    /// just means the built-in script.
+    pub fn from_config() -> Result<Self> {
+        let path = config_dir().join("llm-mock.toml");
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        let config: MockConfig = toml::from_str(&raw).context("parsing llm-mock.toml")?;
+        let mut provider = Self::new();
+        if !config.replies.is_empty() {
+            provider.replies = config.replies;
+        }
+        provider.delay = Duration::from_millis(config.delay_ms);
+        provider.fail_every = config.fail_every;
+        Ok(provider)
+    }
+}
+
+impl Default for MockProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChatProvider for MockProvider {
+    fn model(&self) -> &str {
+        "mock"
+    }
+
+    fn complete(&self, _messages: &[ChatMessage]) -> Result<String> {
+        let call = self.calls.get();
+        self.calls.set(call + 1);
+        std::thread::sleep(self.delay);
+        if let Some(n) = self.fail_every {
+            if n > 0 && (call + 1).is_multiple_of(n) {
+                bail!("mock provider failed on schedule (call {})", call + 1);
+            }
+        }
+        Ok(self.replies[call % self.replies.len()].clone())
+    }
+}
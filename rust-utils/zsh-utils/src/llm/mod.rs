@@ -0,0 +1,275 @@
+//! Minimal client for OpenAI-compatible chat completion endpoints.
+//!
+//! Configuration lives in `$ZSH_CONFIG/llm.toml` so it travels with the
+//! rest of the zsh setup:
+//!
+//! ```toml
+//! base_url = "https://api.openai.com/v1"
+//! model = "gpt-4o-mini"
+//! api_key_env = "OPENAI_API_KEY"
+//!
+//! [policy]
+//! local_only_patterns = ["acme", "AKIA", "BEGIN RSA PRIVATE KEY"]
+//! ```
+//!
+//! The `[policy]` denylist is a guardrail against pasting production
+//! secrets into a cloud model: a prompt matching any pattern is only
+//! allowed out when the endpoint is localhost.
+
+pub mod chunk;
+pub mod mock;
+pub mod preset;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Where the LLM config files live: `$ZSH_CONFIG`, falling back to the
+/// platform config directory.
+pub(crate) fn config_dir() -> std::path::PathBuf {
+    std::env::var("ZSH_CONFIG")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| dirs::config_dir().unwrap_or_default().join("zsh"))
+}
+
+/// Anything that can turn a conversation into an assistant reply: the
+/// real endpoint client, or the mock used for demos and TUI tests.
+pub trait ChatProvider {
+    fn model(&self) -> &str;
+    fn complete(&self, messages: &[ChatMessage]) -> Result<String>;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LLMConfig {
+    pub base_url: String,
+    pub model: String,
+    /// Name of the environment variable holding the API key. We never
+    /// store the key itself in the config file.
+    pub api_key_env: String,
+    /// Model for the embeddings endpoint; unset disables features that
+    /// need embeddings (e.g. semantic search).
+    #[serde(default)]
+    pub embedding_model: Option<String>,
+    /// Opt-in prompt cache for chat: identical conversations within
+    /// this many minutes return the stored reply instead of billing
+    /// again (see [`crate::cache::CachedProvider`]). Unset disables
+    /// caching; `--no-cache` overrides it per run.
+    #[serde(default)]
+    pub cache_ttl_minutes: Option<u64>,
+    /// Sensitive-content guardrail (see [`Policy`]).
+    #[serde(default)]
+    pub policy: Policy,
+}
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct Policy {
+    /// Substrings (matched case-insensitively) that must never leave
+    /// the machine — client names, key prefixes like `AKIA`, PEM
+    /// headers. A prompt containing any of them is blocked before it
+    /// is sent, unless the endpoint is localhost.
+    #[serde(default)]
+    pub local_only_patterns: Vec<String>,
+}
+
+impl LLMConfig {
+    pub fn load() -> Result<Self> {
+        let path = config_dir().join("llm.toml");
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading LLM config at {}", path.display()))?;
+        toml::from_str(&raw).context("parsing llm.toml")
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: String,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn system(content: impl Into<String>) -> Self {
+        Self { role: "system".into(), content: content.into() }
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self { role: "user".into(), content: content.into() }
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self { role: "assistant".into(), content: content.into() }
+    }
+}
+
+pub struct LLMClient {
+    config: LLMConfig,
+    http: reqwest::blocking::Client,
+}
+
+#[derive(Serialize)]
+struct CompletionRequest<'a> {
+    model: &'a str,
+    messages: &'a [ChatMessage],
+}
+
+#[derive(Deserialize)]
+struct CompletionResponse {
+    choices: Vec<Choice>,
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    message: ChatMessage,
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+}
+
+impl LLMClient {
+    pub fn new(config: LLMConfig) -> Self {
+        Self { config, http: reqwest::blocking::Client::new() }
+    }
+
+    pub fn from_config() -> Result<Self> {
+        Ok(Self::new(LLMConfig::load()?))
+    }
+
+    pub fn model(&self) -> &str {
+        &self.config.model
+    }
+
+    /// The local-only guardrail: errors when the endpoint is remote
+    /// and any outgoing text matches the `[policy]` denylist. The
+    /// error names the pattern and where it matched, never the
+    /// matching text itself.
+    fn enforce_policy<'t>(&self, texts: impl Iterator<Item = &'t str>) -> Result<()> {
+        let patterns = &self.config.policy.local_only_patterns;
+        if patterns.is_empty() || is_localhost(&self.config.base_url) {
+            return Ok(());
+        }
+        for (i, text) in texts.enumerate() {
+            let lower = text.to_lowercase();
+            for pattern in patterns {
+                if lower.contains(&pattern.to_lowercase()) {
+                    anyhow::bail!(
+                        "blocked: message {} matches local-only pattern {:?} and {} \
+                         is not a localhost endpoint (policy.local_only_patterns \
+                         in llm.toml)",
+                        i + 1,
+                        pattern,
+                        self.config.base_url
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends a full conversation and returns the assistant reply.
+    pub fn complete(&self, messages: &[ChatMessage]) -> Result<String> {
+        self.enforce_policy(messages.iter().map(|m| m.content.as_str()))?;
+        let key = std::env::var(&self.config.api_key_env).with_context(|| {
+            format!("API key env var {} is not set", self.config.api_key_env)
+        })?;
+        let url = format!("{}/chat/completions", self.config.base_url.trim_end_matches('/'));
+        let body = CompletionRequest { model: &self.config.model, messages };
+        let resp: CompletionResponse = self
+            .http
+            .post(url)
+            .bearer_auth(key)
+            .json(&body)
+            .send()
+            .context("sending chat completion request")?
+            .error_for_status()
+            .context("chat completion request failed")?
+            .json()
+            .context("decoding chat completion response")?;
+        resp.choices
+            .into_iter()
+            .next()
+            .map(|c| c.message.content)
+            .context("empty completion response")
+    }
+
+    /// Embeds a batch of texts via the `/embeddings` endpoint, in
+    /// input order. Requires `embedding_model` in llm.toml.
+    pub fn embed(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.enforce_policy(inputs.iter().map(String::as_str))?;
+        let model = self.config.embedding_model.as_deref().context(
+            "embedding_model is not set in llm.toml (required for embeddings)",
+        )?;
+        let key = std::env::var(&self.config.api_key_env).with_context(|| {
+            format!("API key env var {} is not set", self.config.api_key_env)
+        })?;
+        let url = format!("{}/embeddings", self.config.base_url.trim_end_matches('/'));
+        let body = EmbeddingRequest { model, input: inputs };
+        let resp: EmbeddingResponse = self
+            .http
+            .post(url)
+            .bearer_auth(key)
+            .json(&body)
+            .send()
+            .context("sending embeddings request")?
+            .error_for_status()
+            .context("embeddings request failed")?
+            .json()
+            .context("decoding embeddings response")?;
+        if resp.data.len() != inputs.len() {
+            anyhow::bail!(
+                "embeddings response has {} vectors for {} inputs",
+                resp.data.len(),
+                inputs.len()
+            );
+        }
+        Ok(resp.data.into_iter().map(|d| d.embedding).collect())
+    }
+
+    /// Batch mode: runs several independent conversations and collects
+    /// the replies in order. Sequential on purpose — these endpoints
+    /// rate-limit aggressively and the tools using this are interactive.
+    pub fn complete_batch(&self, conversations: &[Vec<ChatMessage>]) -> Result<Vec<String>> {
+        conversations
+            .iter()
+            .map(|messages| self.complete(messages))
+            .collect()
+    }
+}
+
+/// Whether a base URL points at this machine. Conservative on
+/// purpose: anything unrecognized counts as remote.
+fn is_localhost(base_url: &str) -> bool {
+    let rest = base_url
+        .split_once("://")
+        .map_or(base_url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?']).next().unwrap_or(rest);
+    let host = if let Some(bracketed) = authority.strip_prefix('[') {
+        bracketed.split(']').next().unwrap_or(bracketed)
+    } else {
+        match authority.rsplit_once(':') {
+            Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => host,
+            _ => authority,
+        }
+    };
+    matches!(host, "localhost" | "127.0.0.1" | "::1" | "0.0.0.0")
+}
+
+impl ChatProvider for LLMClient {
+    fn model(&self) -> &str {
+        LLMClient::model(self)
+    }
+
+    fn complete(&self, messages: &[ChatMessage]) -> Result<String> {
+        LLMClient::complete(self, messages)
+    }
+}
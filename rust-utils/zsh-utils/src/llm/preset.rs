@@ -0,0 +1,168 @@
+//! Per-project chat presets (`.utils/llm.toml` at a repo root).
+//!
+//! Dropped into a repository, the file makes `llm-chat` project-aware
+//! with zero flags: the model can differ from the global default, the
+//! system prompt can mention the stack, and context globs pull key
+//! files into every conversation.
+//!
+//! ```toml
+//! model = "gpt-4o"
+//! system = "You are helping on a Rust CLI workspace built on clap and anyhow."
+//! context = ["README.md", "docs/*.md"]
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// Total bytes of context files folded into the system message. Files
+/// that would blow the budget are skipped whole — a truncated file
+/// misleads more than a missing one.
+const CONTEXT_BUDGET: usize = 64_000;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Preset {
+    /// Overrides the llm.toml model for chats inside this repo.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Standing system prompt for the project.
+    #[serde(default)]
+    pub system: Option<String>,
+    /// Globs relative to the repo root (`*` within a segment, `**` for
+    /// any directory depth) whose contents ride along in the system
+    /// message.
+    #[serde(default)]
+    pub context: Vec<String>,
+}
+
+/// A preset together with the repo root it was found in, which anchors
+/// its context globs.
+pub struct ProjectPreset {
+    pub root: PathBuf,
+    pub preset: Preset,
+}
+
+/// The preset governing the current directory: the first
+/// `.utils/llm.toml` found walking up from the cwd. `Ok(None)` when no
+/// ancestor has one.
+pub fn discover() -> Result<Option<ProjectPreset>> {
+    discover_in(&std::env::current_dir()?)
+}
+
+/// [`discover`] from an explicit starting directory.
+pub fn discover_in(dir: &Path) -> Result<Option<ProjectPreset>> {
+    for root in dir.ancestors() {
+        let path = root.join(".utils").join("llm.toml");
+        if path.is_file() {
+            let raw = std::fs::read_to_string(&path)
+                .with_context(|| format!("reading {}", path.display()))?;
+            let preset = toml::from_str(&raw)
+                .with_context(|| format!("parsing {}", path.display()))?;
+            return Ok(Some(ProjectPreset {
+                root: root.to_path_buf(),
+                preset,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+impl ProjectPreset {
+    /// The standing system text this preset amounts to: the prompt
+    /// plus every context file, each under a header naming it so the
+    /// model can say where facts came from. `None` when the preset
+    /// only overrides the model.
+    pub fn system_text(&self) -> Result<Option<String>> {
+        let mut text = self.preset.system.clone().unwrap_or_default();
+        let mut budget = CONTEXT_BUDGET;
+        for pattern in &self.preset.context {
+            for file in expand(&self.root, pattern) {
+                let contents = std::fs::read_to_string(&file)
+                    .with_context(|| format!("reading context file {}", file.display()))?;
+                if contents.len() > budget {
+                    continue;
+                }
+                budget -= contents.len();
+                let rel = file.strip_prefix(&self.root).unwrap_or(&file);
+                text.push_str(&format!(
+                    "\n\n--- {} ---\n{}",
+                    rel.display(),
+                    contents.trim_end()
+                ));
+            }
+        }
+        Ok((!text.trim().is_empty()).then_some(text))
+    }
+}
+
+/// Expands one glob under `root`, in sorted order. Only files match;
+/// a pattern that matches nothing simply contributes nothing.
+fn expand(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let segments: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+    let mut matches = Vec::new();
+    step(root, &segments, &mut matches);
+    matches.sort();
+    matches
+}
+
+fn step(dir: &Path, segments: &[&str], out: &mut Vec<PathBuf>) {
+    let Some((segment, rest)) = segments.split_first() else {
+        return;
+    };
+    if *segment == "**" {
+        // `**` matches zero directories here, or one more level down.
+        step(dir, rest, out);
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    step(&path, segments, out);
+                }
+            }
+        }
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        if !segment_matches(&name.to_string_lossy(), segment) {
+            continue;
+        }
+        if rest.is_empty() {
+            if path.is_file() {
+                out.push(path);
+            }
+        } else if path.is_dir() {
+            step(&path, rest, out);
+        }
+    }
+}
+
+/// Classic `*` wildcard match within one path segment.
+fn segment_matches(name: &str, pattern: &str) -> bool {
+    let name: Vec<char> = name.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let (mut n, mut p) = (0, 0);
+    let (mut star, mut star_n) = (None, 0);
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == name[n] || pattern[p] == '?') {
+            n += 1;
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some(p);
+            star_n = n;
+            p += 1;
+        } else if let Some(s) = star {
+            p = s + 1;
+            star_n += 1;
+            n = star_n;
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,48 @@
+//! Emoji-prefixed logging helpers used by every binary in this crate.
+//!
+//! These are deliberately plain functions rather than a `log` facade:
+//! the tools are interactive and short-lived, and we want the output to
+//! read like a conversation, not a log file. All prefixes degrade to
+//! ASCII tags when [`crate::glyphs`] says the terminal cannot cope, and
+//! the stdout flavors go silent under `--porcelain`
+//! ([`crate::display::is_porcelain`]) so scripts only ever see records.
+
+use crate::{display, glyphs};
+
+pub fn info(msg: impl AsRef<str>) {
+    if display::is_porcelain() {
+        return;
+    }
+    println!("{} {}", glyphs::pick("ℹ️ ", "[i]"), msg.as_ref());
+}
+
+pub fn success(msg: impl AsRef<str>) {
+    if display::is_porcelain() {
+        return;
+    }
+    println!("{} {}", glyphs::pick("✅", "[ok]"), msg.as_ref());
+}
+
+pub fn warn(msg: impl AsRef<str>) {
+    if display::is_porcelain() {
+        eprintln!("warning\t{}", msg.as_ref());
+        return;
+    }
+    eprintln!("{} {}", glyphs::pick("⚠️ ", "[!]"), msg.as_ref());
+}
+
+pub fn error(msg: impl AsRef<str>) {
+    if display::is_porcelain() {
+        eprintln!("error\t{}", msg.as_ref());
+        return;
+    }
+    eprintln!("{} {}", glyphs::pick("❌", "[x]"), msg.as_ref());
+}
+
+/// A progress-style line for multi-step operations.
+pub fn step(msg: impl AsRef<str>) {
+    if display::is_porcelain() {
+        return;
+    }
+    println!("{} {}", glyphs::pick("🔧", "[*]"), msg.as_ref());
+}
@@ -0,0 +1,278 @@
+//! One Markdown renderer for both worlds: ANSI text for plain-terminal
+//! commands and ratatui lines for the chat TUI.
+//!
+//! Rendering happens in two stages: pulldown-cmark events become a flat
+//! list of styled lines, and each frontend maps the styles to whatever
+//! it can display.
+
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Normal,
+    Heading(u8),
+    Code,
+    InlineCode,
+    Emphasis,
+    Strong,
+}
+
+/// A piece of text with one style, never spanning a line break.
+#[derive(Debug, Clone)]
+pub struct Chunk {
+    pub text: String,
+    pub kind: Kind,
+}
+
+pub type StyledLine = Vec<Chunk>;
+
+/// Parses Markdown into styled lines, wrapping prose at `width`.
+pub fn parse(markdown: &str, width: usize) -> Vec<StyledLine> {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+
+    let mut lines: Vec<StyledLine> = Vec::new();
+    let mut current: StyledLine = Vec::new();
+    let mut kind_stack: Vec<Kind> = vec![Kind::Normal];
+    let mut in_code_block = false;
+    let mut list_depth: usize = 0;
+    let mut in_table_row = false;
+
+    let flush = |current: &mut StyledLine, lines: &mut Vec<StyledLine>| {
+        if !current.is_empty() {
+            lines.push(std::mem::take(current));
+        }
+    };
+
+    for event in Parser::new_ext(markdown, options) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                flush(&mut current, &mut lines);
+                lines.push(Vec::new());
+                kind_stack.push(Kind::Heading(heading_depth(level)));
+                let marker = "#".repeat(heading_depth(level) as usize);
+                current.push(Chunk { text: format!("{marker} "), kind: Kind::Heading(heading_depth(level)) });
+            }
+            Event::End(TagEnd::Heading(..)) => {
+                kind_stack.pop();
+                flush(&mut current, &mut lines);
+                lines.push(Vec::new());
+            }
+            Event::Start(Tag::CodeBlock(_)) => {
+                flush(&mut current, &mut lines);
+                in_code_block = true;
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                in_code_block = false;
+                lines.push(Vec::new());
+            }
+            Event::Start(Tag::Emphasis) => kind_stack.push(Kind::Emphasis),
+            Event::End(TagEnd::Emphasis) => {
+                kind_stack.pop();
+            }
+            Event::Start(Tag::Strong) => kind_stack.push(Kind::Strong),
+            Event::End(TagEnd::Strong) => {
+                kind_stack.pop();
+            }
+            Event::Start(Tag::List(_)) => list_depth += 1,
+            Event::End(TagEnd::List(_)) => {
+                list_depth = list_depth.saturating_sub(1);
+                if list_depth == 0 {
+                    lines.push(Vec::new());
+                }
+            }
+            Event::Start(Tag::Item) => {
+                flush(&mut current, &mut lines);
+                let indent = "  ".repeat(list_depth.saturating_sub(1));
+                current.push(Chunk { text: format!("{indent}• "), kind: Kind::Normal });
+            }
+            Event::End(TagEnd::Item) => flush(&mut current, &mut lines),
+            Event::Start(Tag::TableRow) | Event::Start(Tag::TableHead) => {
+                in_table_row = true;
+            }
+            Event::End(TagEnd::TableRow) | Event::End(TagEnd::TableHead) => {
+                in_table_row = false;
+                flush(&mut current, &mut lines);
+            }
+            Event::Start(Tag::Paragraph) => flush(&mut current, &mut lines),
+            Event::End(TagEnd::Paragraph) => {
+                flush(&mut current, &mut lines);
+                lines.push(Vec::new());
+            }
+            Event::Text(text) => {
+                if in_code_block {
+                    for code_line in text.lines() {
+                        lines.push(vec![Chunk {
+                            text: format!("    {code_line}"),
+                            kind: Kind::Code,
+                        }]);
+                    }
+                } else {
+                    let kind = *kind_stack.last().unwrap_or(&Kind::Normal);
+                    // `$...$` math becomes its Unicode approximation
+                    // (x² instead of raw x^{2}); ASCII mode keeps the
+                    // LaTeX, which at least round-trips.
+                    let text = if !crate::glyphs::is_ascii()
+                        && crate::math::contains_math(&text)
+                    {
+                        crate::math::render_inline(&text)
+                    } else {
+                        text.into_string()
+                    };
+                    current.push(Chunk { text, kind });
+                    if in_table_row {
+                        current.push(Chunk { text: " │ ".into(), kind: Kind::Normal });
+                    }
+                }
+            }
+            Event::Code(code) => {
+                current.push(Chunk { text: code.into_string(), kind: Kind::InlineCode });
+            }
+            Event::SoftBreak => current.push(Chunk { text: " ".into(), kind: Kind::Normal }),
+            Event::HardBreak => flush(&mut current, &mut lines),
+            Event::Rule => {
+                flush(&mut current, &mut lines);
+                lines.push(vec![Chunk { text: "─".repeat(width.min(40)), kind: Kind::Normal }]);
+            }
+            _ => {}
+        }
+    }
+    flush(&mut current, &mut lines);
+    wrap_lines(lines, width)
+}
+
+fn heading_depth(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+/// Greedy word wrap that keeps each chunk's style. Code lines are left
+/// alone so indentation survives.
+fn wrap_lines(lines: Vec<StyledLine>, width: usize) -> Vec<StyledLine> {
+    let mut wrapped = Vec::new();
+    for line in lines {
+        if width == 0 || line.iter().any(|c| c.kind == Kind::Code) {
+            wrapped.push(line);
+            continue;
+        }
+        let mut out: StyledLine = Vec::new();
+        let mut used = 0;
+        for chunk in line {
+            for word in chunk.text.split_inclusive(' ') {
+                if used + word.len() > width && used > 0 {
+                    wrapped.push(std::mem::take(&mut out));
+                    used = 0;
+                }
+                out.push(Chunk { text: word.to_string(), kind: chunk.kind });
+                used += word.len();
+            }
+        }
+        wrapped.push(out);
+    }
+    wrapped
+}
+
+/// ANSI rendering for plain-terminal output. Color usage goes through
+/// [`crate::term::paint`], which owns the NO_COLOR decision.
+pub fn render_ansi(markdown: &str, width: usize) -> String {
+    let mut out = String::new();
+    for line in parse(markdown, width) {
+        for chunk in &line {
+            match ansi_code(chunk.kind) {
+                Some(code) => out.push_str(&crate::term::paint(code, &chunk.text)),
+                None => out.push_str(&chunk.text),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn ansi_code(kind: Kind) -> Option<&'static str> {
+    match kind {
+        Kind::Heading(1) => Some("1;4;36"),
+        Kind::Heading(_) => Some("1;36"),
+        Kind::Code => Some("33"),
+        Kind::InlineCode => Some("36"),
+        Kind::Emphasis => Some("3"),
+        Kind::Strong => Some("1"),
+        Kind::Normal => None,
+    }
+}
+
+/// ratatui rendering for the chat TUI.
+pub fn render_tui(markdown: &str, width: usize) -> Vec<Line<'static>> {
+    parse(markdown, width)
+        .into_iter()
+        .map(|line| {
+            Line::from(
+                line.into_iter()
+                    .map(|chunk| Span::styled(chunk.text, crate::term::themed(tui_style(chunk.kind))))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect()
+}
+
+/// Memoized [`render_tui`], keyed by document hash and width. TUIs
+/// redraw on every keystroke; re-parsing an unchanged transcript each
+/// frame is wasted work, but a resize (new width) re-wraps correctly.
+pub struct RenderCache {
+    entries: std::collections::HashMap<(u64, usize), Vec<Line<'static>>>,
+}
+
+/// Documents cached before the cache resets; long chats cycle through
+/// few distinct (document, width) pairs, so this is rarely hit.
+const CACHE_LIMIT: usize = 256;
+
+impl RenderCache {
+    pub fn new() -> Self {
+        Self {
+            entries: std::collections::HashMap::new(),
+        }
+    }
+
+    pub fn render(&mut self, markdown: &str, width: usize) -> &[Line<'static>] {
+        if self.entries.len() > CACHE_LIMIT {
+            self.entries.clear();
+        }
+        self.entries
+            .entry((content_hash(markdown), width))
+            .or_insert_with(|| render_tui(markdown, width))
+    }
+}
+
+impl Default for RenderCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn content_hash(text: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn tui_style(kind: Kind) -> Style {
+    match kind {
+        Kind::Heading(_) => Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+        Kind::Code => Style::default().fg(Color::Yellow),
+        Kind::InlineCode => Style::default().fg(Color::Cyan),
+        Kind::Emphasis => Style::default().add_modifier(Modifier::ITALIC),
+        Kind::Strong => Style::default().add_modifier(Modifier::BOLD),
+        Kind::Normal => Style::default(),
+    }
+}
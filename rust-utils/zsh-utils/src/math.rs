@@ -0,0 +1,307 @@
+//! Unicode approximation of LaTeX math for terminal output.
+//!
+//! Assistant replies routinely contain `$...$` math. Terminals cannot
+//! typeset it, but Unicode gets surprisingly close: superscript and
+//! subscript digits, Greek letters, and operator glyphs cover most of
+//! what actually shows up. Anything without a Unicode form passes
+//! through unchanged, so the worst case reads like the original LaTeX
+//! minus the backslash noise. HTML output goes the other way — the
+//! site keeps the raw delimiters and lets KaTeX typeset them properly
+//! (see [`crate::claude::site`]).
+
+/// Replaces every `$...$` and `$$...$$` span in `text` with its
+/// Unicode approximation. Dollar amounts survive: a span only counts
+/// as math when its content has no leading/trailing whitespace, so
+/// "costs $5 and $10" never matches.
+pub fn render_inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some((before, latex, after)) = next_span(rest) {
+        out.push_str(before);
+        out.push_str(&approximate(latex));
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Whether `text` contains at least one math span [`render_inline`]
+/// would rewrite.
+pub fn contains_math(text: &str) -> bool {
+    next_span(text).is_some()
+}
+
+/// Finds the first math span: `(text before, latex inside, text after)`.
+fn next_span(text: &str) -> Option<(&str, &str, &str)> {
+    let mut from = 0;
+    while let Some(open) = text[from..].find('$').map(|i| from + i) {
+        // `$$` display math: delimiters may span lines.
+        let (start, close_pat) = if text[open + 1..].starts_with('$') {
+            (open + 2, "$$")
+        } else {
+            (open + 1, "$")
+        };
+        let close = text[start..].find(close_pat).map(|i| start + i)?;
+        let inner = &text[start..close];
+        let plausible = !inner.is_empty()
+            && !inner.starts_with(char::is_whitespace)
+            && !inner.ends_with(char::is_whitespace)
+            && (close_pat == "$$" || !inner.contains('\n'));
+        if plausible {
+            return Some((
+                &text[..open],
+                inner,
+                &text[close + close_pat.len()..],
+            ));
+        }
+        from = start;
+    }
+    None
+}
+
+/// Translates one LaTeX expression (without delimiters) to Unicode.
+pub fn approximate(latex: &str) -> String {
+    let mut out = String::with_capacity(latex.len());
+    let mut chars = latex.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => render_command(&mut chars, &mut out),
+            '^' => render_script(&mut chars, &mut out, '^', superscript),
+            '_' => render_script(&mut chars, &mut out, '_', subscript),
+            // Stray grouping braces carry no meaning of their own.
+            '{' | '}' => {}
+            '~' => out.push(' '),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+type Chars<'a> = std::iter::Peekable<std::str::Chars<'a>>;
+
+fn render_command(chars: &mut Chars, out: &mut String) {
+    let mut name = String::new();
+    while chars.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+        name.push(chars.next().expect("peeked"));
+    }
+    match name.as_str() {
+        // One-char spacing/escape commands: `\,` `\;` `\!` `\{` ...
+        "" => match chars.next() {
+            Some(',') | Some(';') => out.push(' '),
+            Some('!') | None => {}
+            Some(other) => out.push(other),
+        },
+        "frac" => {
+            let num = read_group(chars);
+            let den = read_group(chars);
+            out.push_str(&parenthesize(&approximate(&num)));
+            out.push('/');
+            out.push_str(&parenthesize(&approximate(&den)));
+        }
+        "sqrt" => {
+            let body = approximate(&read_group(chars));
+            out.push('√');
+            out.push_str(&parenthesize(&body));
+        }
+        // Their argument is already plain text.
+        "text" | "mathrm" | "operatorname" => out.push_str(&read_group(chars)),
+        // Sizing-only commands; the delimiter itself follows.
+        "left" | "right" | "big" | "Big" => {}
+        "quad" | "qquad" => out.push(' '),
+        _ => match symbol(&name) {
+            Some(glyph) => out.push_str(glyph),
+            // No glyph: keep the name so nothing is silently dropped.
+            None => out.push_str(&name),
+        },
+    }
+}
+
+/// `^`/`_` with its argument. When every character has a super- or
+/// subscript form the script disappears into the glyphs (`x^2` → `x²`);
+/// otherwise the original notation stays, parenthesized if grouped.
+fn render_script(
+    chars: &mut Chars,
+    out: &mut String,
+    marker: char,
+    map: fn(char) -> Option<char>,
+) {
+    let body = approximate(&read_group(chars));
+    let mapped: Option<String> = body.chars().map(map).collect();
+    match mapped {
+        Some(glyphs) if !glyphs.is_empty() => out.push_str(&glyphs),
+        _ => {
+            out.push(marker);
+            out.push_str(&parenthesize(&body));
+        }
+    }
+}
+
+/// Reads one argument: a balanced `{...}` group, a `\command`, or a
+/// single character.
+fn read_group(chars: &mut Chars) -> String {
+    match chars.peek() {
+        Some('{') => {
+            chars.next();
+            let mut depth = 1;
+            let mut group = String::new();
+            for c in chars.by_ref() {
+                match c {
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+                group.push(c);
+            }
+            group
+        }
+        Some('\\') => {
+            chars.next();
+            let mut group = String::from('\\');
+            while chars.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+                group.push(chars.next().expect("peeked"));
+            }
+            group
+        }
+        _ => chars.next().map(String::from).unwrap_or_default(),
+    }
+}
+
+/// Wraps multi-character terms so `\frac{a+1}{b}` reads `(a+1)/b`, not
+/// `a+1/b`.
+fn parenthesize(term: &str) -> String {
+    if term.chars().count() <= 1 || term.chars().all(char::is_alphanumeric) {
+        term.to_string()
+    } else {
+        format!("({term})")
+    }
+}
+
+fn superscript(c: char) -> Option<char> {
+    Some(match c {
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        '9' => '⁹',
+        '+' => '⁺',
+        '-' => '⁻',
+        '=' => '⁼',
+        '(' => '⁽',
+        ')' => '⁾',
+        'n' => 'ⁿ',
+        'i' => 'ⁱ',
+        _ => return None,
+    })
+}
+
+fn subscript(c: char) -> Option<char> {
+    Some(match c {
+        '0' => '₀',
+        '1' => '₁',
+        '2' => '₂',
+        '3' => '₃',
+        '4' => '₄',
+        '5' => '₅',
+        '6' => '₆',
+        '7' => '₇',
+        '8' => '₈',
+        '9' => '₉',
+        '+' => '₊',
+        '-' => '₋',
+        '=' => '₌',
+        '(' => '₍',
+        ')' => '₎',
+        'i' => 'ᵢ',
+        'j' => 'ⱼ',
+        'n' => 'ₙ',
+        'x' => 'ₓ',
+        _ => return None,
+    })
+}
+
+/// The command names that are a straight glyph substitution.
+fn symbol(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" | "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" => "θ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "pi" => "π",
+        "rho" => "ρ",
+        "sigma" => "σ",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" | "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        "times" => "×",
+        "cdot" => "·",
+        "div" => "÷",
+        "pm" => "±",
+        "mp" => "∓",
+        "le" | "leq" => "≤",
+        "ge" | "geq" => "≥",
+        "ne" | "neq" => "≠",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "propto" => "∝",
+        "infty" => "∞",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "sum" => "∑",
+        "prod" => "∏",
+        "int" => "∫",
+        "in" => "∈",
+        "notin" => "∉",
+        "subset" => "⊂",
+        "subseteq" => "⊆",
+        "cup" => "∪",
+        "cap" => "∩",
+        "forall" => "∀",
+        "exists" => "∃",
+        "emptyset" | "varnothing" => "∅",
+        "to" | "rightarrow" => "→",
+        "leftarrow" => "←",
+        "Rightarrow" | "implies" => "⇒",
+        "Leftrightarrow" | "iff" => "⇔",
+        "ldots" | "dots" => "…",
+        "cdots" => "⋯",
+        "perp" => "⊥",
+        "parallel" => "∥",
+        "angle" => "∠",
+        "circ" => "°",
+        _ => return None,
+    })
+}
@@ -0,0 +1,103 @@
+//! Terminal progress reporting for long-running byte-shovelling work.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::{glyphs, term};
+
+/// Thread-safe progress bar writing to stderr. Callers `add` bytes as
+/// they move them; drawing is throttled so hot loops stay hot.
+pub struct ProgressReporter {
+    label: String,
+    total: Option<u64>,
+    done: AtomicU64,
+    start: Instant,
+    last_draw: Mutex<Instant>,
+}
+
+impl ProgressReporter {
+    pub fn new(label: impl Into<String>, total: Option<u64>) -> Self {
+        Self {
+            label: label.into(),
+            total,
+            done: AtomicU64::new(0),
+            start: Instant::now(),
+            last_draw: Mutex::new(Instant::now() - std::time::Duration::from_secs(1)),
+        }
+    }
+
+    pub fn add(&self, bytes: u64) {
+        self.done.fetch_add(bytes, Ordering::Relaxed);
+        let mut last = self.last_draw.lock().expect("progress lock");
+        if last.elapsed().as_millis() >= 100 {
+            *last = Instant::now();
+            self.draw();
+        }
+    }
+
+    /// Used when resuming: counts bytes that were already on disk.
+    pub fn set_done(&self, bytes: u64) {
+        self.done.store(bytes, Ordering::Relaxed);
+    }
+
+    pub fn finish(&self) {
+        if crate::display::is_porcelain() {
+            return;
+        }
+        self.draw();
+        eprintln!();
+    }
+
+    fn draw(&self) {
+        // Carriage-return redrawing is exactly what a parsing script
+        // must never see.
+        if crate::display::is_porcelain() {
+            return;
+        }
+        let done = self.done.load(Ordering::Relaxed);
+        let elapsed = self.start.elapsed().as_secs_f64().max(0.001);
+        let rate = done as f64 / elapsed;
+        let mut line = format!("\r{}: {}", self.label, human_bytes(done));
+        if let Some(total) = self.total {
+            let percent = (done as f64 / total.max(1) as f64 * 100.0).min(100.0);
+            let bar_width = 24usize;
+            let filled = (percent / 100.0 * bar_width as f64) as usize;
+            let (full, empty) = (glyphs::pick("█", "#"), glyphs::pick("░", "-"));
+            line = format!(
+                "\r{}: [{}{}] {percent:3.0}% {}/{}",
+                self.label,
+                full.repeat(filled),
+                empty.repeat(bar_width - filled),
+                human_bytes(done),
+                human_bytes(total),
+            );
+        }
+        line.push_str(&format!(" {}/s", human_bytes(rate as u64)));
+        // Never wrap: that turns the bar into scrolling spam. Clip by
+        // characters, not bytes — the Unicode bar glyphs are three
+        // bytes each, so a byte index would land mid-character and
+        // panic. Every glyph we draw is one cell wide, and the leading
+        // `\r` takes no cell at all.
+        let max = term::columns() as usize + 1;
+        if line.chars().count() > max {
+            line = line.chars().take(max).collect();
+        }
+        eprint!("{line}");
+    }
+}
+
+pub fn human_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes}{}", UNITS[0])
+    } else {
+        format!("{value:.1}{}", UNITS[unit])
+    }
+}
@@ -0,0 +1,65 @@
+//! A small rename engine: validate a batch of renames up front, then
+//! apply them atomically enough for interactive use.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+
+pub struct RenameItem {
+    pub from: PathBuf,
+    /// New file name (not a path); the file stays in its directory.
+    pub to: String,
+}
+
+pub struct RenamePlan {
+    items: Vec<RenameItem>,
+}
+
+impl RenamePlan {
+    /// Builds a plan, rejecting anything that could clobber a file:
+    /// empty or path-like targets, duplicate targets, and targets that
+    /// already exist on disk.
+    pub fn new(items: Vec<RenameItem>) -> Result<Self> {
+        let mut seen = HashSet::new();
+        for item in &items {
+            if item.to.trim().is_empty() {
+                bail!("empty target name for {}", item.from.display());
+            }
+            if item.to.contains('/') {
+                bail!("target {:?} must be a bare file name", item.to);
+            }
+            if !seen.insert(item.to.clone()) {
+                bail!("duplicate target name {:?}", item.to);
+            }
+            let dest = item.from.with_file_name(&item.to);
+            if dest != item.from && dest.exists() {
+                bail!("target {} already exists", dest.display());
+            }
+        }
+        Ok(Self { items })
+    }
+
+    pub fn items(&self) -> &[RenameItem] {
+        &self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Applies the renames, skipping no-ops. Returns how many files moved.
+    pub fn apply(&self) -> Result<usize> {
+        let mut moved = 0;
+        for item in &self.items {
+            let dest = item.from.with_file_name(&item.to);
+            if dest == item.from {
+                continue;
+            }
+            std::fs::rename(&item.from, &dest)
+                .with_context(|| format!("renaming {}", item.from.display()))?;
+            moved += 1;
+        }
+        Ok(moved)
+    }
+}
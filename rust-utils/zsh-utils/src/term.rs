@@ -0,0 +1,77 @@
+//! Terminal capability probing shared by the CLI and TUI sides.
+
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+/// Current terminal size in columns/rows, with a sane fallback when we
+/// are not attached to a tty (pipes, CI).
+pub fn size() -> (u16, u16) {
+    crossterm::terminal::size().unwrap_or((80, 24))
+}
+
+pub fn columns() -> u16 {
+    size().0
+}
+
+/// The one place color decisions are made, honoring the informal
+/// standards: `CLICOLOR_FORCE` wins, `NO_COLOR` disables, and piped
+/// output or a dumb terminal disables by default.
+pub fn colors_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        if std::env::var_os("CLICOLOR_FORCE").is_some_and(|v| v != "0") {
+            return true;
+        }
+        if std::env::var_os("NO_COLOR").is_some() {
+            return false;
+        }
+        if std::env::var("TERM").as_deref() == Ok("dumb") {
+            return false;
+        }
+        std::io::stdout().is_terminal()
+    })
+}
+
+/// Wraps `text` in the given SGR code (e.g. `"1;36"`) when colors are
+/// enabled, and returns it untouched otherwise.
+pub fn paint(code: &str, text: &str) -> String {
+    if colors_enabled() {
+        format!("\x1b[{code}m{text}\x1b[0m")
+    } else {
+        text.to_string()
+    }
+}
+
+/// Strips a ratatui style down to nothing when colors are disabled, so
+/// the TUIs degrade to monochrome instead of ignoring NO_COLOR.
+pub fn themed(style: ratatui::style::Style) -> ratatui::style::Style {
+    if colors_enabled() {
+        style
+    } else {
+        ratatui::style::Style::default()
+    }
+}
+
+/// Border drawn with characters every code page has.
+const ASCII_BORDER: ratatui::symbols::border::Set = ratatui::symbols::border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// A titled box that falls back to `+--+` borders in ASCII mode, used
+/// by every TUI in the crate.
+pub fn bordered(title: &str) -> ratatui::widgets::Block<'_> {
+    use ratatui::widgets::{Block, Borders};
+    let block = Block::default().borders(Borders::ALL).title(title);
+    if crate::glyphs::is_ascii() {
+        block.border_set(ASCII_BORDER)
+    } else {
+        block
+    }
+}
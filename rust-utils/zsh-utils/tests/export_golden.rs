@@ -0,0 +1,108 @@
+//! Golden-file tests for the exporters, driven by synthetic fixture
+//! transcripts covering every entry type and the usual edge cases
+//! (missing timestamps, branched parents, malformed trailing lines).
+
+use std::path::PathBuf;
+
+use zsh_utils::claude::export::{build_json, build_jsonl, render_markdown, RenderOptions};
+use zsh_utils::claude::org::render_org;
+use zsh_utils::claude::parser;
+use zsh_utils::claude::pricing::Pricing;
+use zsh_utils::claude::sessions::{Project, Session};
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn fixture_session(name: &str) -> Session {
+    let project = Project {
+        encoded_name: "-tmp-demo".to_string(),
+        path: fixtures_dir(),
+    };
+    Session {
+        id: name.to_string(),
+        path: fixtures_dir().join(format!("{name}.jsonl")),
+        project,
+    }
+}
+
+#[test]
+fn markdown_export_matches_golden() {
+    let session = fixture_session("basic");
+    let transcript = parser::parse_file(&session.path).expect("fixture parses");
+    let rendered = render_markdown(
+        &session,
+        &transcript,
+        &Pricing::builtin(),
+        &RenderOptions::default(),
+        &[],
+    );
+    let golden = include_str!("golden/basic.md");
+    assert_eq!(rendered, golden);
+}
+
+#[test]
+fn json_export_matches_golden() {
+    let session = fixture_session("basic");
+    let transcript = parser::parse_file(&session.path).expect("fixture parses");
+    let actual =
+        serde_json::to_value(build_json(&session, &transcript, &Pricing::builtin(), Vec::new()))
+            .expect("serializes");
+    let golden: serde_json::Value =
+        serde_json::from_str(include_str!("golden/basic.json")).expect("golden parses");
+    assert_eq!(actual, golden);
+}
+
+#[test]
+fn jsonl_export_matches_golden() {
+    let session = fixture_session("basic");
+    let transcript = parser::parse_file(&session.path).expect("fixture parses");
+    let actual: Vec<serde_json::Value> = build_jsonl(&session, &transcript)
+        .iter()
+        .map(|r| serde_json::to_value(r).expect("serializes"))
+        .collect();
+    let golden: Vec<serde_json::Value> = include_str!("golden/basic.jsonl")
+        .lines()
+        .map(|l| serde_json::from_str(l).expect("golden parses"))
+        .collect();
+    assert_eq!(actual, golden);
+}
+
+#[test]
+fn org_export_matches_golden() {
+    let session = fixture_session("basic");
+    let transcript = parser::parse_file(&session.path).expect("fixture parses");
+    let rendered = render_org(
+        &session,
+        &transcript,
+        &Pricing::builtin(),
+        &RenderOptions::default(),
+        &[],
+    );
+    let golden = include_str!("golden/basic.org");
+    assert_eq!(rendered, golden);
+}
+
+#[test]
+fn basic_fixture_reports_the_truncated_line() {
+    let transcript =
+        parser::parse_file(&fixture_session("basic").path).expect("fixture parses");
+    assert_eq!(transcript.entries.len(), 6);
+    assert_eq!(transcript.skipped, 1);
+}
+
+#[test]
+fn edge_cases_parse_without_panicking() {
+    let transcript =
+        parser::parse_file(&fixture_session("edge").path).expect("fixture parses");
+    // Three real entries; the non-JSON line is skipped, the blank line
+    // is ignored entirely.
+    assert_eq!(transcript.entries.len(), 3);
+    assert_eq!(transcript.skipped, 1);
+    // Missing timestamps stay None instead of failing the entry.
+    assert!(transcript.entries[0]
+        .meta()
+        .expect("user entry has meta")
+        .timestamp
+        .is_none());
+}
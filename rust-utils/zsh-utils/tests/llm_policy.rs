@@ -0,0 +1,49 @@
+//! Behavior tests for the local-only sensitive-content guardrail.
+
+use zsh_utils::llm::{ChatMessage, LLMClient, LLMConfig, Policy};
+
+fn config(base_url: &str, patterns: &[&str]) -> LLMConfig {
+    LLMConfig {
+        base_url: base_url.to_string(),
+        model: "test-model".to_string(),
+        // Deliberately unset, so a request that passes the policy
+        // fails on the missing key instead of hitting the network.
+        api_key_env: "ZSH_UTILS_TEST_UNSET_KEY".to_string(),
+        embedding_model: None,
+        cache_ttl_minutes: None,
+        policy: Policy {
+            local_only_patterns: patterns.iter().map(|p| p.to_string()).collect(),
+        },
+    }
+}
+
+#[test]
+fn denylisted_prompts_never_reach_remote_endpoints() {
+    let client = LLMClient::new(config("https://api.openai.com/v1", &["ACME", "AKIA"]));
+    let err = client
+        .complete(&[ChatMessage::user("notes about Acme Corp billing")])
+        .expect_err("policy blocks");
+    let text = format!("{err:#}");
+    assert!(text.contains("local-only pattern"), "got {text}");
+    assert!(text.contains("ACME"), "names the trigger: {text}");
+    // The matching text itself must not leak into the error.
+    assert!(!text.contains("billing"), "got {text}");
+}
+
+#[test]
+fn localhost_endpoints_are_exempt() {
+    let client = LLMClient::new(config("http://localhost:11434/v1", &["ACME"]));
+    let err = client
+        .complete(&[ChatMessage::user("notes about Acme Corp")])
+        .expect_err("fails later, on the missing key");
+    assert!(format!("{err:#}").contains("ZSH_UTILS_TEST_UNSET_KEY"));
+}
+
+#[test]
+fn clean_prompts_pass_the_policy() {
+    let client = LLMClient::new(config("https://api.openai.com/v1", &["ACME"]));
+    let err = client
+        .complete(&[ChatMessage::user("how do I reverse a list in python")])
+        .expect_err("fails later, on the missing key");
+    assert!(format!("{err:#}").contains("ZSH_UTILS_TEST_UNSET_KEY"));
+}
@@ -0,0 +1,56 @@
+//! Behavior tests for project preset discovery and context assembly.
+
+use zsh_utils::llm::preset::discover_in;
+
+fn write(path: &std::path::Path, contents: &str) {
+    std::fs::create_dir_all(path.parent().expect("has parent")).expect("mkdir");
+    std::fs::write(path, contents).expect("write");
+}
+
+#[test]
+fn discovery_walks_up_to_the_repo_root() {
+    let repo = tempfile::tempdir().expect("tempdir");
+    write(
+        &repo.path().join(".utils/llm.toml"),
+        "model = \"gpt-4o\"\nsystem = \"Rust CLI workspace.\"\n",
+    );
+    let nested = repo.path().join("src/deeply/nested");
+    std::fs::create_dir_all(&nested).expect("mkdir");
+
+    let found = discover_in(&nested).expect("discovery").expect("preset found");
+    assert_eq!(found.root, repo.path());
+    assert_eq!(found.preset.model.as_deref(), Some("gpt-4o"));
+}
+
+#[test]
+fn no_preset_file_means_none() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    assert!(discover_in(dir.path()).expect("discovery").is_none());
+}
+
+#[test]
+fn context_globs_land_in_the_system_text_with_headers() {
+    let repo = tempfile::tempdir().expect("tempdir");
+    write(
+        &repo.path().join(".utils/llm.toml"),
+        "system = \"About this repo.\"\ncontext = [\"README.md\", \"docs/*.md\"]\n",
+    );
+    write(&repo.path().join("README.md"), "the readme\n");
+    write(&repo.path().join("docs/guide.md"), "the guide\n");
+    write(&repo.path().join("docs/notes.txt"), "not matched\n");
+
+    let found = discover_in(repo.path()).expect("discovery").expect("preset found");
+    let text = found.system_text().expect("context reads").expect("has text");
+    assert!(text.starts_with("About this repo."));
+    assert!(text.contains("--- README.md ---\nthe readme"));
+    assert!(text.contains("--- docs/guide.md ---\nthe guide"));
+    assert!(!text.contains("not matched"));
+}
+
+#[test]
+fn model_only_presets_have_no_system_text() {
+    let repo = tempfile::tempdir().expect("tempdir");
+    write(&repo.path().join(".utils/llm.toml"), "model = \"gpt-4o\"\n");
+    let found = discover_in(repo.path()).expect("discovery").expect("preset found");
+    assert!(found.system_text().expect("assembles").is_none());
+}
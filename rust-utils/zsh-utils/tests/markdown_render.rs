@@ -0,0 +1,55 @@
+//! Behavior tests for the shared Markdown renderer: wrapping, code
+//! block handling, and the width-keyed render cache.
+
+use zsh_utils::markdown::{parse, Kind, RenderCache};
+
+fn line_text(line: &[zsh_utils::markdown::Chunk]) -> String {
+    line.iter().map(|c| c.text.as_str()).collect()
+}
+
+#[test]
+fn prose_wraps_at_the_given_width() {
+    let lines = parse("one two three four five six seven eight", 15);
+    for line in &lines {
+        assert!(
+            line_text(line).len() <= 16,
+            "line too wide: {:?}",
+            line_text(line)
+        );
+    }
+    assert!(lines.len() > 1);
+}
+
+#[test]
+fn code_blocks_keep_their_indentation_and_never_wrap() {
+    let markdown = "```\n        let deeply_indented_thing = some_function(a, b, c);\n```";
+    let lines = parse(markdown, 10);
+    let code: Vec<_> = lines
+        .iter()
+        .filter(|l| l.iter().any(|c| c.kind == Kind::Code))
+        .collect();
+    assert_eq!(code.len(), 1);
+    assert!(line_text(code[0]).contains("        let deeply_indented_thing"));
+}
+
+#[test]
+fn headings_keep_their_marker() {
+    let lines = parse("## Section", 80);
+    let heading = lines
+        .iter()
+        .find(|l| !l.is_empty())
+        .expect("heading line exists");
+    assert_eq!(line_text(heading), "## Section");
+    assert_eq!(heading[0].kind, Kind::Heading(2));
+}
+
+#[test]
+fn cache_rewraps_per_width() {
+    let mut cache = RenderCache::new();
+    let markdown = "a few words that will wrap differently";
+    let narrow = cache.render(markdown, 10).len();
+    let wide = cache.render(markdown, 200).len();
+    assert!(narrow > wide);
+    // Same inputs come back from the cache unchanged.
+    assert_eq!(cache.render(markdown, 10).len(), narrow);
+}
@@ -0,0 +1,53 @@
+//! Behavior tests for the LaTeX → Unicode math approximation and its
+//! integration with the shared Markdown renderer.
+
+use zsh_utils::math::{approximate, contains_math, render_inline};
+
+#[test]
+fn common_notation_gets_unicode_glyphs() {
+    assert_eq!(approximate("x^2 + y^2"), "x² + y²");
+    assert_eq!(approximate("a_1 \\cdot b_2"), "a₁ · b₂");
+    assert_eq!(approximate("\\alpha \\to \\infty"), "α → ∞");
+    assert_eq!(approximate("\\sqrt{2}"), "√2");
+}
+
+#[test]
+fn fractions_read_left_to_right() {
+    assert_eq!(approximate("\\frac{a}{b}"), "a/b");
+    // Multi-term numerators keep their grouping.
+    assert_eq!(approximate("\\frac{a+1}{b}"), "(a+1)/b");
+}
+
+#[test]
+fn unknown_commands_survive_instead_of_vanishing() {
+    assert_eq!(approximate("\\mathfrak{g}"), "mathfrakg");
+    // The exponent that has no superscript form keeps its notation.
+    assert_eq!(approximate("e^{x+1}"), "e^(x+1)");
+}
+
+#[test]
+fn dollar_amounts_are_not_math() {
+    let text = "it costs $5 now and $10 later";
+    assert!(!contains_math(text));
+    assert_eq!(render_inline(text), text);
+}
+
+#[test]
+fn inline_spans_are_replaced_in_place() {
+    assert_eq!(
+        render_inline("the area is $\\pi r^2$ here"),
+        "the area is π r² here"
+    );
+    assert!(contains_math("display: $$\\sum_{i} x_i$$"));
+}
+
+#[test]
+fn markdown_renderer_picks_up_math() {
+    let lines = zsh_utils::markdown::parse("energy is $E = mc^2$", 80);
+    let text: String = lines
+        .iter()
+        .flatten()
+        .map(|c| c.text.as_str())
+        .collect();
+    assert!(text.contains("mc²"), "got {text:?}");
+}
@@ -0,0 +1,61 @@
+//! Property tests for the transcript parser. Real transcripts contain
+//! surprising garbage after crashes — truncated lines, partial UTF-8,
+//! editor droppings — and none of it may panic the parser or slip
+//! through unaccounted.
+
+use proptest::prelude::*;
+
+use zsh_utils::claude::parser;
+
+const BASIC: &str = include_str!("fixtures/basic.jsonl");
+
+fn non_blank_lines(raw: &str) -> usize {
+    raw.lines().filter(|l| !l.trim().is_empty()).count()
+}
+
+proptest! {
+    /// Arbitrary input never panics, and every non-blank line is either
+    /// parsed or counted as skipped.
+    #[test]
+    fn arbitrary_lines_are_parsed_or_skipped(
+        lines in prop::collection::vec(any::<String>(), 0..20)
+    ) {
+        let raw = lines.join("\n");
+        let transcript = parser::parse_str(&raw);
+        prop_assert_eq!(
+            transcript.entries.len() + transcript.skipped,
+            non_blank_lines(&raw)
+        );
+    }
+
+    /// A transcript truncated mid-write (crash, full disk) parses up to
+    /// the cut with at most the damaged line reported as skipped.
+    #[test]
+    fn truncation_at_any_point_is_handled(cut in 0..=BASIC.len()) {
+        let mut cut = cut;
+        while !BASIC.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        let raw = &BASIC[..cut];
+        let transcript = parser::parse_str(raw);
+        prop_assert_eq!(
+            transcript.entries.len() + transcript.skipped,
+            non_blank_lines(raw)
+        );
+    }
+
+    /// Garbage appended after a valid transcript is reported as skipped
+    /// without losing any of the real entries. The generator requires at
+    /// least one non-whitespace character — a whitespace-only tail is a
+    /// blank line, which the parser rightly ignores rather than skips.
+    #[test]
+    fn trailing_garbage_is_skipped_cleanly(
+        garbage in "[^\n{\\[]{0,40}[^\\s{\\[][^\n{\\[]{0,40}"
+    ) {
+        let raw = format!("{BASIC}{garbage}\n");
+        let with_garbage = parser::parse_str(&raw);
+        let clean = parser::parse_str(BASIC);
+        prop_assert_eq!(with_garbage.entries.len(), clean.entries.len());
+        prop_assert_eq!(with_garbage.skipped, clean.skipped + 1);
+    }
+}